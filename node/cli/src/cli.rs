@@ -23,6 +23,29 @@ pub struct Cli {
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub run: sc_cli::RunCmd,
+
+	/// Replace BABE and GRANDPA with a sealing engine. Only available for dev chains.
+	///
+	/// Possible values are `instant` (a block per incoming transaction), `manual` (blocks are
+	/// created through the `engine_createBlock` RPC) or a block interval in milliseconds.
+	#[clap(long)]
+	pub sealing: Option<ternoa_service::Sealing>,
+
+	/// Keep the transactions indexed by the transaction storage pallet.
+	///
+	/// Indexed transactions are stored along with the block bodies, so the bodies of all the
+	/// finalized blocks are kept and `--blocks-pruning` can't be given a number of blocks. Block
+	/// authors need the data stored during the last storage period to include the storage proofs.
+	#[clap(long)]
+	pub transaction_indexing: bool,
+
+	/// Index the events of the NFT, marketplace, auction, rent and bridge pallets in the given
+	/// SQLite database, and serve them through the `indexer_*` RPC methods.
+	///
+	/// Only finalized blocks are indexed. Indexing blocks older than the pruned state needs
+	/// `--state-pruning archive`.
+	#[clap(long, value_name = "PATH")]
+	pub indexer_db: Option<std::path::PathBuf>,
 }
 
 /// Possible subcommands of the main binary.
@@ -31,6 +54,9 @@ pub enum Subcommand {
 	/// Build a chain specification.
 	BuildSpec(sc_cli::BuildSpecCmd),
 
+	/// Generate the human and raw chain specifications of a network described by a TOML file.
+	GenerateSpec(crate::generate_spec::GenerateSpecCmd),
+
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
 
@@ -40,6 +66,9 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Turn a state exported with `export-state` into a development chain spec.
+	ForkOff(crate::fork_off::ForkOffCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
@@ -84,4 +113,10 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Mine NPoS election solutions and submit them during the signed phase.
+	StakingMiner(crate::staking_miner::StakingMinerCmd),
+
+	/// Run a local testnet of several validators in this process.
+	LocalTestnet(crate::local_testnet::LocalTestnetCmd),
 }
@@ -14,16 +14,22 @@
 // You should have received a copy of the GNU General Public License
 // along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::cli::{Cli, Subcommand};
+use crate::{
+	cli::{Cli, Subcommand},
+	local_testnet::LocalTestnetCmd,
+	staking_miner::StakingMinerCmd,
+};
 use frame_benchmarking_cli::BenchmarkCmd;
 use node_inspect::cli::InspectCmd;
 use sc_cli::{
 	BuildSpecCmd, ChainInfoCmd, ChainSpec, CheckBlockCmd, ExportBlocksCmd, ExportStateCmd,
 	ImportBlocksCmd, PurgeChainCmd, Result, RevertCmd, RuntimeVersion, SubstrateCli,
 };
-use sc_service::{Arc, PartialComponents};
+use sc_service::{config::BlocksPruning, Arc, PartialComponents};
 use ternoa_client::benchmarking::{inherent_benchmark_data, RemarkBuilder};
-use ternoa_service::{chain_spec, new_full, new_partial, IdentifyVariant};
+use ternoa_service::{
+	chain_spec, new_dev, new_full, new_full_base, new_partial, IdentifyVariant, NewFullBase,
+};
 
 #[cfg(feature = "alphanet-native")]
 use ternoa_service::alphanet_runtime;
@@ -65,42 +71,54 @@ impl SubstrateCli for Cli {
 		Ok(match id {
 			"alphanet" => Box::new(chain_spec::alphanet_config()?),
 			#[cfg(feature = "alphanet-native")]
+			"chaosnet" => Box::new(chain_spec::alphanet::chaosnet_config()),
+			#[cfg(feature = "alphanet-native")]
 			"alphanet-dev" | "a-dev" | "dev" => Box::new(chain_spec::alphanet::development_config()),
+			#[cfg(feature = "alphanet-native")]
+			"alphanet-local" => Box::new(chain_spec::alphanet::local_testnet_config(
+				chain_spec::LOCAL_TESTNET_VALIDATORS,
+			)),
 
 			"mainnet" => Box::new(chain_spec::mainnet_config()?),
 			#[cfg(feature = "mainnet-native")]
 			"mainnet-dev" | "m-dev" => Box::new(chain_spec::mainnet::development_config()),
+			#[cfg(feature = "mainnet-native")]
+			"mainnet-local" => Box::new(chain_spec::mainnet::local_testnet_config(
+				chain_spec::LOCAL_TESTNET_VALIDATORS,
+			)),
 
 			"" => return Err("Please specify which chain you want to run!".into()),
 			path => {
 				let path = std::path::PathBuf::from(path);
 
-				let chain_spec =
-					Box::new(chain_spec::MainnetChainSpec::from_json_file(path.clone())?)
-						as Box<dyn sc_service::ChainSpec>;
-
-				if chain_spec.is_alphanet() {
-					Box::new(chain_spec::AlphanetChainSpec::from_json_file(path)?)
-				} else {
-					chain_spec
+				match chain_spec::Runtime::of_json_file(&path)? {
+					#[cfg(feature = "alphanet-native")]
+					chain_spec::Runtime::Alphanet =>
+						Box::new(chain_spec::AlphanetChainSpec::from_json_file(path)?),
+					#[cfg(feature = "mainnet-native")]
+					chain_spec::Runtime::Mainnet => Box::new(chain_spec::MainnetChainSpec::from_json_file(path)?),
+					#[allow(unreachable_patterns)]
+					runtime =>
+						return Err(format!(
+							"The {} runtime of {} is not built in this binary",
+							runtime.name(),
+							path.display(),
+						)),
 				}
 			},
 		})
 	}
 
 	fn native_runtime_version(spec: &Box<dyn ChainSpec>) -> &'static RuntimeVersion {
-		#[cfg(feature = "alphanet-native")]
-		if spec.is_alphanet() {
-			return &alphanet_runtime::VERSION
-		}
-
-		#[cfg(feature = "mainnet-native")]
-		{
-			return &mainnet_runtime::VERSION
+		// `load_spec` only gives the chain specs of the runtimes built in this binary.
+		match spec.runtime() {
+			#[cfg(feature = "alphanet-native")]
+			Ok(chain_spec::Runtime::Alphanet) => &alphanet_runtime::VERSION,
+			#[cfg(feature = "mainnet-native")]
+			Ok(chain_spec::Runtime::Mainnet) => &mainnet_runtime::VERSION,
+			#[allow(unreachable_patterns)]
+			runtime => panic!("No native runtime for {}: {:?}", spec.id(), runtime),
 		}
-
-		#[cfg(not(feature = "mainnet-native"))]
-		panic!("No runtime feature (alphanet, mainnet) is enabled");
 	}
 }
 
@@ -114,9 +132,11 @@ pub fn run() -> Result<()> {
 	match &cli.subcommand {
 		None => run_wo_args(&cli),
 		Some(Subcommand::BuildSpec(cmd)) => build_spec(&cli, cmd),
+		Some(Subcommand::GenerateSpec(cmd)) => cmd.run(),
 		Some(Subcommand::CheckBlock(cmd)) => check_block(&cli, cmd),
 		Some(Subcommand::ExportBlocks(cmd)) => export_blocks(&cli, cmd),
 		Some(Subcommand::ExportState(cmd)) => export_state(&cli, cmd),
+		Some(Subcommand::ForkOff(cmd)) => cmd.run(),
 		Some(Subcommand::ImportBlocks(cmd)) => import_blocks(&cli, cmd),
 		Some(Subcommand::PurgeChain(cmd)) => purge_chain(&cli, cmd),
 		Some(Subcommand::Revert(cmd)) => revert(&cli, cmd),
@@ -133,6 +153,8 @@ pub fn run() -> Result<()> {
 		Some(Subcommand::Vanity(cmd)) => Ok(cmd.run()?),
 		Some(Subcommand::Sign(cmd)) => Ok(cmd.run()?),
 		Some(Subcommand::ChainInfo(cmd)) => chain_info(&cli, cmd),
+		Some(Subcommand::StakingMiner(cmd)) => staking_miner(&cli, cmd),
+		Some(Subcommand::LocalTestnet(cmd)) => local_testnet(&cli, cmd),
 	}?;
 
 	Ok(())
@@ -146,44 +168,76 @@ fn ensure_dev(spec: &Box<dyn sc_service::ChainSpec>) -> Result<()> {
 	}
 }
 
+/// Error of a chain whose runtime is not built in this binary.
+fn runtime_not_built(runtime: chain_spec::Runtime) -> sc_cli::Error {
+	format!("The {} runtime is not built in this binary", runtime.name()).into()
+}
+
+/// Runs `$code` with the `Block`, `RuntimeApi` and `ExecutorDispatch` of the runtime of the chain
+/// spec, returning the error of [`chain_spec::Runtime::of`] when it can't be told.
 macro_rules! with_runtime {
 	($chain_spec:expr, $code:expr) => {
-		#[cfg(feature = "alphanet-native")]
-		if $chain_spec.is_alphanet() {
-			#[allow(unused_imports)]
-			use alphanet_runtime::Block;
-			#[allow(unused_imports)]
-			use alphanet_runtime::RuntimeApi;
-			#[allow(unused_imports)]
-			use AlphanetExecutorDispatch as ExecutorDispatch;
-
-			return $code
-		}
-
-		#[cfg(feature = "mainnet-native")]
-		{
-			#[allow(unused_imports)]
-			use mainnet_runtime::Block;
-			#[allow(unused_imports)]
-			use mainnet_runtime::RuntimeApi;
-			#[allow(unused_imports)]
-			use MainnetExecutorDispatch as ExecutorDispatch;
-
-			return $code
+		match $chain_spec.runtime()? {
+			#[cfg(feature = "alphanet-native")]
+			chain_spec::Runtime::Alphanet => {
+				#[allow(unused_imports)]
+				use alphanet_runtime::Block;
+				#[allow(unused_imports)]
+				use alphanet_runtime::RuntimeApi;
+				#[allow(unused_imports)]
+				use AlphanetExecutorDispatch as ExecutorDispatch;
+
+				return $code
+			},
+			#[cfg(feature = "mainnet-native")]
+			chain_spec::Runtime::Mainnet => {
+				#[allow(unused_imports)]
+				use mainnet_runtime::Block;
+				#[allow(unused_imports)]
+				use mainnet_runtime::RuntimeApi;
+				#[allow(unused_imports)]
+				use MainnetExecutorDispatch as ExecutorDispatch;
+
+				return $code
+			},
+			#[allow(unreachable_patterns)]
+			runtime => return Err(runtime_not_built(runtime)),
 		}
-
-		#[cfg(not(feature = "mainnet-native"))]
-		panic!("No runtime feature (alphanet, mainnet) is enabled");
 	};
 }
 
 fn run_wo_args(cli: &Cli) -> Result<()> {
 	let runner = cli.create_runner(&cli.run)?;
+	if cli.transaction_indexing {
+		if let BlocksPruning::Some(blocks) = runner.config().blocks_pruning {
+			return Err(format!(
+				"`--transaction-indexing` keeps the bodies of all the finalized blocks and can't be \
+				used with `--blocks-pruning {}`",
+				blocks
+			)
+			.into())
+		}
+	}
 	let chain_spec = &runner.config().chain_spec.cloned_box();
 
+	if let Some(sealing) = cli.sealing {
+		ensure_dev(chain_spec)?;
+		if cli.indexer_db.is_some() {
+			return Err("The event indexer is not available with `--sealing`".into())
+		}
+		with_runtime!(chain_spec, {
+			runner.run_node_until_exit(|config| async move {
+				new_dev::<RuntimeApi, ExecutorDispatch>(config, sealing)
+					.map_err(sc_cli::Error::Service)
+			})
+		});
+	}
+
+	let indexer_db = cli.indexer_db.clone();
 	with_runtime!(chain_spec, {
 		runner.run_node_until_exit(|config| async move {
-			new_full::<RuntimeApi, ExecutorDispatch>(config).map_err(sc_cli::Error::Service)
+			new_full::<RuntimeApi, ExecutorDispatch>(config, indexer_db)
+				.map_err(sc_cli::Error::Service)
 		})
 	});
 }
@@ -303,52 +357,53 @@ fn benchmark(cli: &Cli, cmd: &BenchmarkCmd) -> Result<()> {
 			// });
 		},
 		BenchmarkCmd::Overhead(cmd) => {
-			#[cfg(feature = "alphanet-native")]
-			if chain_spec.is_alphanet() {
-				return runner.sync_run(|config| {
-					let PartialComponents { client, .. } = new_partial::<
-						alphanet_runtime::RuntimeApi,
-						AlphanetExecutorDispatch,
-					>(&config)?;
-
-					// We need to create Arc<Client> out of the raw client.
-
-					let new_client = ternoa_client::Client::Alphanet(client.clone());
-					let arc_client = Arc::new(new_client);
-
-					let builder = RemarkBuilder::new(arc_client.clone());
-					cmd.run(
-						config,
-						client.clone(),
-						inherent_benchmark_data().unwrap(),
-						Vec::new(),
-						&builder,
-					)
-				})
-			}
-
-			#[cfg(feature = "mainnet-native")]
-			{
-				return runner.sync_run(|config| {
-					let PartialComponents { client, .. } = new_partial::<
-						mainnet_runtime::RuntimeApi,
-						MainnetExecutorDispatch,
-					>(&config)?;
-
-					// We need to create Arc<Client> out of the raw client.
-
-					let new_client = ternoa_client::Client::Mainnet(client.clone());
-					let arc_client = Arc::new(new_client);
-
-					let builder = RemarkBuilder::new(arc_client.clone());
-					cmd.run(
-						config,
-						client.clone(),
-						inherent_benchmark_data().unwrap(),
-						Vec::new(),
-						&builder,
-					)
-				})
+			match chain_spec.runtime()? {
+				#[cfg(feature = "alphanet-native")]
+				chain_spec::Runtime::Alphanet =>
+					return runner.sync_run(|config| {
+						let PartialComponents { client, .. } = new_partial::<
+							alphanet_runtime::RuntimeApi,
+							AlphanetExecutorDispatch,
+						>(&config)?;
+
+						// We need to create Arc<Client> out of the raw client.
+
+						let new_client = ternoa_client::Client::Alphanet(client.clone());
+						let arc_client = Arc::new(new_client);
+
+						let builder = RemarkBuilder::new(arc_client.clone());
+						cmd.run(
+							config,
+							client.clone(),
+							inherent_benchmark_data().unwrap(),
+							Vec::new(),
+							&builder,
+						)
+					}),
+				#[cfg(feature = "mainnet-native")]
+				chain_spec::Runtime::Mainnet =>
+					return runner.sync_run(|config| {
+						let PartialComponents { client, .. } = new_partial::<
+							mainnet_runtime::RuntimeApi,
+							MainnetExecutorDispatch,
+						>(&config)?;
+
+						// We need to create Arc<Client> out of the raw client.
+
+						let new_client = ternoa_client::Client::Mainnet(client.clone());
+						let arc_client = Arc::new(new_client);
+
+						let builder = RemarkBuilder::new(arc_client.clone());
+						cmd.run(
+							config,
+							client.clone(),
+							inherent_benchmark_data().unwrap(),
+							Vec::new(),
+							&builder,
+						)
+					}),
+				#[allow(unreachable_patterns)]
+				runtime => return Err(runtime_not_built(runtime)),
 			}
 		},
 		BenchmarkCmd::Block(cmd) => {
@@ -397,12 +452,73 @@ fn chain_info(cli: &Cli, cmd: &ChainInfoCmd) -> Result<()> {
 
 	with_runtime!(chain_spec, {
 		runner.run_node_until_exit(|config| async move {
-			new_full::<RuntimeApi, ExecutorDispatch>(config).map_err(sc_cli::Error::Service)
+			new_full::<RuntimeApi, ExecutorDispatch>(config, None).map_err(sc_cli::Error::Service)
 		})
 	});
 }
 
+fn staking_miner(cli: &Cli, cmd: &StakingMinerCmd) -> Result<()> {
+	let runner = cli.create_runner(cmd)?;
+	let chain_spec = &runner.config().chain_spec.cloned_box();
+
+	macro_rules! run_miner {
+		($miner:ident) => {
+			runner.async_run(|config| {
+				// only need a task manager to do `async_run`.
+				let registry = config.prometheus_config.as_ref().map(|cfg| &cfg.registry);
+				let task_manager =
+					sc_service::TaskManager::new(config.tokio_handle.clone(), registry)
+						.map_err(|e| sc_cli::Error::Service(sc_service::Error::Prometheus(e)))?;
+
+				Ok((crate::staking_miner::$miner::run(cmd.clone()), task_manager))
+			})
+		};
+	}
+
+	match chain_spec.runtime()? {
+		#[cfg(feature = "alphanet-native")]
+		chain_spec::Runtime::Alphanet => run_miner!(alphanet),
+		#[cfg(feature = "mainnet-native")]
+		chain_spec::Runtime::Mainnet => run_miner!(mainnet),
+		#[allow(unreachable_patterns)]
+		runtime => Err(runtime_not_built(runtime)),
+	}
+}
+
 //
 //
 //
 //
+
+fn local_testnet(cli: &Cli, cmd: &LocalTestnetCmd) -> Result<()> {
+	let runner = cli.create_runner(&cmd.node_cmd(0, None)?)?;
+	let chain_spec = &runner.config().chain_spec.cloned_box();
+
+	with_runtime!(chain_spec, {
+		runner.run_node_until_exit(|config| async move {
+			let tokio_handle = config.tokio_handle.clone();
+
+			let NewFullBase { mut task_manager, network, .. } =
+				new_full_base::<RuntimeApi, ExecutorDispatch>(
+					cmd.prepare(config, 0)?,
+					None,
+					|_, _| (),
+				)?;
+			let bootnode = cmd.bootnode(network.local_peer_id());
+
+			// The other validators are children of the first one, they all stop together.
+			for index in 1..cmd.validators {
+				let node_cmd = cmd.node_cmd(index, Some(&bootnode))?;
+				let config = cli.create_configuration(&node_cmd, tokio_handle.clone())?;
+				let node = new_full_base::<RuntimeApi, ExecutorDispatch>(
+					cmd.prepare(config, index)?,
+					None,
+					|_, _| (),
+				)?;
+				task_manager.add_child(node.task_manager);
+			}
+
+			Ok::<_, sc_cli::Error>(task_manager)
+		})
+	});
+}
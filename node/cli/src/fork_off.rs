@@ -0,0 +1,330 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The `fork-off` subcommand.
+//!
+//! Turns a state snapshot, the raw chain spec written by `export-state`, into a development
+//! chain spec which a single validator can run. The consensus authorities, the session keys and
+//! the technical committee are replaced by the ones of a development account, the BABE epochs
+//! start over from the first block and the eras are no longer changed, so that the validator set
+//! stays the same. The rest of the state is kept as is.
+
+use frame_support::storage::storage_prefix;
+use parity_scale_codec::Encode;
+use sc_cli::Result;
+use sc_service::ChainSpec;
+use sp_core::{
+	crypto::{key_types, KeyTypeId, Pair},
+	ed25519,
+	hashing::{twox_128, twox_64},
+	sr25519,
+	storage::{well_known_keys, Storage},
+};
+use std::{collections::BTreeMap, path::PathBuf};
+use ternoa_core_primitives::AccountId;
+use ternoa_service::chain_spec::{DummyChainSpec, Runtime, RUNTIME_PROPERTY};
+
+/// `Forcing::ForceNone` of the staking pallet.
+const FORCE_NONE: u8 = 2;
+/// Storage key of the GRANDPA authorities, as defined by `sp_finality_grandpa`.
+const GRANDPA_AUTHORITIES_KEY: &[u8] = b":grandpa_authorities";
+/// Version of the GRANDPA authority list.
+const GRANDPA_AUTHORITIES_VERSION: u8 = 1;
+
+/// Builds a development chain spec out of a state snapshot.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ForkOffCmd {
+	/// Raw chain spec holding the state to fork off, as written by `export-state`.
+	#[clap(long, value_name = "PATH")]
+	pub from_snapshot: PathBuf,
+
+	/// Wasm runtime replacing the one of the snapshot.
+	#[clap(long, value_name = "PATH")]
+	pub runtime: Option<PathBuf>,
+
+	/// Development account taking over as validator and technical committee.
+	#[clap(long, default_value = "Alice")]
+	pub seed: String,
+
+	/// Path of the chain spec to write, `<id>-dev.json` by default.
+	#[clap(long, value_name = "PATH")]
+	pub output: Option<PathBuf>,
+}
+
+/// Keys of the development account replacing the authorities.
+struct DevKeys {
+	stash: AccountId,
+	grandpa: ed25519::Public,
+	babe: sr25519::Public,
+	im_online: sr25519::Public,
+	authority_discovery: sr25519::Public,
+}
+
+impl DevKeys {
+	fn from_seed(seed: &str) -> Result<Self> {
+		let sr25519 = |suri: String| {
+			sr25519::Pair::from_string(&suri, None)
+				.map(|pair| pair.public())
+				.map_err(|e| format!("Invalid seed {}: {:?}", suri, e))
+		};
+		let grandpa = ed25519::Pair::from_string(&format!("//{}", seed), None)
+			.map_err(|e| format!("Invalid seed {}: {:?}", seed, e))?
+			.public();
+
+		Ok(Self {
+			stash: sr25519(format!("//{}//stash", seed))?.into(),
+			grandpa,
+			babe: sr25519(format!("//{}", seed))?,
+			im_online: sr25519(format!("//{}", seed))?,
+			authority_discovery: sr25519(format!("//{}", seed))?,
+		})
+	}
+
+	/// The session keys, encoded as the `SessionKeys` of the runtimes.
+	fn session_keys(&self) -> Vec<u8> {
+		(self.grandpa, self.babe, self.im_online, self.authority_discovery).encode()
+	}
+
+	fn key_ids(&self) -> [(KeyTypeId, Vec<u8>); 4] {
+		[
+			(key_types::GRANDPA, self.grandpa.0.to_vec()),
+			(key_types::BABE, self.babe.0.to_vec()),
+			(key_types::IM_ONLINE, self.im_online.0.to_vec()),
+			(key_types::AUTHORITY_DISCOVERY, self.authority_discovery.0.to_vec()),
+		]
+	}
+}
+
+fn key(pallet: &str, item: &str) -> Vec<u8> {
+	storage_prefix(pallet.as_bytes(), item.as_bytes()).to_vec()
+}
+
+fn twox_64_concat(data: &[u8]) -> Vec<u8> {
+	[&twox_64(data)[..], data].concat()
+}
+
+fn remove_prefix(top: &mut BTreeMap<Vec<u8>, Vec<u8>>, prefix: &[u8]) {
+	top.retain(|key, _| !key.starts_with(prefix));
+}
+
+/// Replaces the authorities of the state by the development account.
+fn fork_off(storage: &mut Storage, keys: &DevKeys) {
+	let top = &mut storage.top;
+
+	// BABE starts over as from a genesis, only the epoch config is kept.
+	let epoch_config = top.get(&key("Babe", "EpochConfig")).cloned();
+	remove_prefix(top, &twox_128(b"Babe"));
+	if let Some(epoch_config) = epoch_config {
+		top.insert(key("Babe", "EpochConfig"), epoch_config);
+	}
+	let babe_authorities = vec![(keys.babe, 1u64)].encode();
+	top.insert(key("Babe", "Authorities"), babe_authorities.clone());
+	top.insert(key("Babe", "NextAuthorities"), babe_authorities);
+
+	top.insert(
+		GRANDPA_AUTHORITIES_KEY.to_vec(),
+		(GRANDPA_AUTHORITIES_VERSION, vec![(keys.grandpa, 1u64)]).encode(),
+	);
+	for item in ["PendingChange", "NextForced", "Stalled", "SetIdSession"] {
+		remove_prefix(top, &key("Grandpa", item));
+	}
+	top.insert(key("Grandpa", "CurrentSetId"), 0u64.encode());
+	// `StoredState::Live`.
+	top.insert(key("Grandpa", "State"), 0u8.encode());
+
+	for item in ["NextKeys", "KeyOwner"] {
+		remove_prefix(top, &key("Session", item));
+	}
+	top.insert(key("Session", "Validators"), vec![keys.stash.clone()].encode());
+	top.insert(
+		key("Session", "QueuedKeys"),
+		vec![(keys.stash.clone(), keys.session_keys())].encode(),
+	);
+	top.insert(key("Session", "QueuedChanged"), false.encode());
+	top.insert(key("Session", "DisabledValidators"), Vec::<u32>::new().encode());
+	top.insert(
+		[key("Session", "NextKeys"), twox_64_concat(&keys.stash.encode())].concat(),
+		keys.session_keys(),
+	);
+	for key_id in keys.key_ids() {
+		top.insert(
+			[key("Session", "KeyOwner"), twox_64_concat(&key_id.encode())].concat(),
+			keys.stash.encode(),
+		);
+	}
+
+	top.insert(key("ImOnline", "Keys"), vec![keys.im_online].encode());
+	top.insert(key("AuthorityDiscovery", "Keys"), vec![keys.authority_discovery].encode());
+	top.insert(key("AuthorityDiscovery", "NextKeys"), vec![keys.authority_discovery].encode());
+
+	// A new era would elect the validators of the snapshot again.
+	top.insert(key("Staking", "ForceEra"), FORCE_NONE.encode());
+
+	for pallet in ["TechnicalMembership", "TechnicalCommittee"] {
+		top.insert(key(pallet, "Members"), vec![keys.stash.clone()].encode());
+		top.remove(&key(pallet, "Prime"));
+	}
+}
+
+impl ForkOffCmd {
+	/// Runs the command.
+	pub fn run(&self) -> Result<()> {
+		let mut spec = DummyChainSpec::from_json_file(self.from_snapshot.clone())?;
+		let mut storage = spec
+			.as_storage_builder()
+			.build_storage()
+			.map_err(|e| format!("The snapshot must be a raw chain spec: {}", e))?;
+
+		let keys = DevKeys::from_seed(&self.seed)?;
+		fork_off(&mut storage, &keys);
+		if let Some(runtime) = &self.runtime {
+			let code = std::fs::read(runtime)
+				.map_err(|e| format!("Failed to read {}: {}", runtime.display(), e))?;
+			storage.top.insert(well_known_keys::CODE.to_vec(), code);
+		}
+		spec.set_storage(storage);
+
+		// The runtime is recorded so that the fork runs the native runtime of its code.
+		let runtime = Runtime::of(&spec)?;
+		let id = format!("{}-dev", spec.id().trim_end_matches("-dev"));
+		let mut json: serde_json::Value = serde_json::from_str(&spec.as_json(true)?)
+			.map_err(|e| format!("Failed to read the chain spec: {}", e))?;
+		json["id"] = id.clone().into();
+		json["name"] = format!("{} Fork", spec.name()).into();
+		json["chainType"] = "Development".into();
+		json["bootNodes"] = serde_json::Value::Array(vec![]);
+		json["telemetryEndpoints"] = serde_json::Value::Null;
+		json["properties"][RUNTIME_PROPERTY] = runtime.name().into();
+
+		let output = self.output.clone().unwrap_or_else(|| format!("{}.json", id).into());
+		let json = serde_json::to_string_pretty(&json)
+			.map_err(|e| format!("Failed to write the chain spec: {}", e))?;
+		std::fs::write(&output, json)?;
+		println!("Wrote {}, run it with --chain {}", output.display(), output.display());
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn keys_under<'a>(storage: &'a Storage, prefix: &[u8]) -> Vec<&'a [u8]> {
+		storage
+			.top
+			.keys()
+			.filter(|key| key.starts_with(prefix))
+			.map(|key| &key[..])
+			.collect()
+	}
+
+	/// State of a chain validated by `Bob`, with an entry of a pallet left alone.
+	fn snapshot() -> (Storage, Vec<u8>) {
+		let bob = DevKeys::from_seed("Bob").unwrap();
+		let untouched = [key("System", "Account"), twox_64_concat(b"account")].concat();
+
+		let mut top = BTreeMap::new();
+		top.insert(untouched.clone(), b"balance".to_vec());
+		top.insert(key("Babe", "EpochIndex"), 42u64.encode());
+		top.insert(key("Babe", "Randomness"), [7u8; 32].encode());
+		top.insert(key("Babe", "EpochConfig"), b"epoch config".to_vec());
+		top.insert(key("Babe", "Authorities"), vec![(bob.babe, 1u64)].encode());
+		top.insert(
+			GRANDPA_AUTHORITIES_KEY.to_vec(),
+			(GRANDPA_AUTHORITIES_VERSION, vec![(bob.grandpa, 1u64)]).encode(),
+		);
+		top.insert(key("Grandpa", "PendingChange"), b"change".to_vec());
+		top.insert(key("Grandpa", "CurrentSetId"), 3u64.encode());
+		top.insert(
+			[key("Session", "NextKeys"), twox_64_concat(&bob.stash.encode())].concat(),
+			bob.session_keys(),
+		);
+		for key_id in bob.key_ids() {
+			top.insert(
+				[key("Session", "KeyOwner"), twox_64_concat(&key_id.encode())].concat(),
+				bob.stash.encode(),
+			);
+		}
+
+		(Storage { top, ..Default::default() }, untouched)
+	}
+
+	#[test]
+	fn session_keys_are_replaced() {
+		let (mut storage, _) = snapshot();
+		let alice = DevKeys::from_seed("Alice").unwrap();
+		fork_off(&mut storage, &alice);
+
+		let next_keys =
+			[key("Session", "NextKeys"), twox_64_concat(&alice.stash.encode())].concat();
+		assert_eq!(keys_under(&storage, &key("Session", "NextKeys")), vec![&next_keys[..]]);
+		assert_eq!(storage.top[&next_keys], alice.session_keys());
+
+		let key_owners = keys_under(&storage, &key("Session", "KeyOwner"));
+		assert_eq!(key_owners.len(), 4);
+		for key_id in alice.key_ids() {
+			let key_owner = [key("Session", "KeyOwner"), twox_64_concat(&key_id.encode())].concat();
+			assert_eq!(storage.top[&key_owner], alice.stash.encode());
+		}
+
+		assert_eq!(storage.top[&key("Session", "Validators")], vec![alice.stash.clone()].encode());
+	}
+
+	#[test]
+	fn grandpa_authorities_are_replaced() {
+		let (mut storage, _) = snapshot();
+		let alice = DevKeys::from_seed("Alice").unwrap();
+		fork_off(&mut storage, &alice);
+
+		assert_eq!(
+			storage.top[GRANDPA_AUTHORITIES_KEY],
+			(GRANDPA_AUTHORITIES_VERSION, vec![(alice.grandpa, 1u64)]).encode()
+		);
+		assert!(!storage.top.contains_key(&key("Grandpa", "PendingChange")));
+		assert_eq!(storage.top[&key("Grandpa", "CurrentSetId")], 0u64.encode());
+	}
+
+	#[test]
+	fn babe_starts_over() {
+		let (mut storage, _) = snapshot();
+		let alice = DevKeys::from_seed("Alice").unwrap();
+		fork_off(&mut storage, &alice);
+
+		let authorities = vec![(alice.babe, 1u64)].encode();
+		let mut expected = vec![
+			(key("Babe", "EpochConfig"), b"epoch config".to_vec()),
+			(key("Babe", "Authorities"), authorities.clone()),
+			(key("Babe", "NextAuthorities"), authorities),
+		];
+		expected.sort();
+		let babe: Vec<_> = storage
+			.top
+			.iter()
+			.filter(|(key, _)| key.starts_with(&twox_128(b"Babe")))
+			.map(|(key, value)| (key.clone(), value.clone()))
+			.collect();
+		assert_eq!(babe, expected);
+	}
+
+	#[test]
+	fn other_pallets_are_kept() {
+		let (mut storage, untouched) = snapshot();
+		fork_off(&mut storage, &DevKeys::from_seed("Alice").unwrap());
+
+		assert_eq!(storage.top[&untouched], b"balance".to_vec());
+	}
+}
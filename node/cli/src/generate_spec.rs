@@ -0,0 +1,71 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The `generate-spec` subcommand.
+//!
+//! Builds the chain spec of a network described by a TOML file, see
+//! [`ternoa_service::chain_spec::network`] for the format, and writes it in its human and raw
+//! forms next to each other, as `<id>_human.json` and `<id>_raw.json`.
+
+use sc_cli::Result;
+use sc_service::ChainSpec;
+use std::path::PathBuf;
+use ternoa_service::chain_spec::{self, network::NetworkConfig, Runtime};
+
+/// Generates the human and raw chain specs of a network described by a TOML file.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct GenerateSpecCmd {
+	/// Path to the network file.
+	#[clap(long, value_name = "PATH")]
+	pub config: PathBuf,
+
+	/// Directory in which the chain specs are written.
+	#[clap(long, value_name = "PATH", default_value = ".")]
+	pub output: PathBuf,
+}
+
+impl GenerateSpecCmd {
+	/// Runs the command.
+	pub fn run(&self) -> Result<()> {
+		let network = NetworkConfig::from_toml_file(&self.config)?;
+
+		let spec: Box<dyn ChainSpec> = match network.runtime {
+			#[cfg(feature = "alphanet-native")]
+			Runtime::Alphanet => Box::new(chain_spec::alphanet::network_config(&network)?),
+			#[cfg(feature = "mainnet-native")]
+			Runtime::Mainnet => Box::new(chain_spec::mainnet::network_config(&network)?),
+			#[allow(unreachable_patterns)]
+			runtime => {
+				let error = format!("The {:?} runtime is not built in this binary", runtime);
+				return Err(error.into())
+			},
+		};
+
+		let human = spec.as_json(false)?;
+		self.write(&format!("{}_human.json", network.id), human)?;
+
+		let raw = spec.as_json(true)?;
+		self.write(&format!("{}_raw.json", network.id), raw)
+	}
+
+	fn write(&self, file_name: &str, json: String) -> Result<()> {
+		let path = self.output.join(file_name);
+		std::fs::write(&path, json)?;
+		println!("Wrote {}", path.display());
+
+		Ok(())
+	}
+}
@@ -18,11 +18,27 @@
 mod cli;
 #[cfg(feature = "cli")]
 mod command;
+#[cfg(feature = "cli")]
+mod fork_off;
+#[cfg(feature = "cli")]
+mod generate_spec;
+#[cfg(feature = "cli")]
+mod local_testnet;
+#[cfg(feature = "cli")]
+mod staking_miner;
 
 #[cfg(feature = "cli")]
 pub use cli::*;
 #[cfg(feature = "cli")]
 pub use command::*;
+#[cfg(feature = "cli")]
+pub use fork_off::ForkOffCmd;
+#[cfg(feature = "cli")]
+pub use generate_spec::GenerateSpecCmd;
+#[cfg(feature = "cli")]
+pub use local_testnet::LocalTestnetCmd;
+#[cfg(feature = "cli")]
+pub use staking_miner::StakingMinerCmd;
 
 #[cfg(feature = "cli")]
 pub use sc_cli::Result;
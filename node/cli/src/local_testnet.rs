@@ -0,0 +1,145 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The `local-testnet` subcommand.
+//!
+//! Runs a testnet of validators in a single process, each of them being a full node with its own
+//! temporary database and listening on the loopback interface. The validators are the ones of
+//! the `alphanet-local` or `mainnet-local` chain, with the development accounts first, and they
+//! all boot from the first one.
+
+use clap::Parser;
+use sc_cli::{Result, RunCmd};
+use sc_keystore::LocalKeystore;
+use sc_service::{config::KeystoreConfig, ChainSpec, Configuration};
+use sp_core::crypto::key_types;
+use sp_keystore::SyncCryptoStore;
+use ternoa_service::{
+	chain_spec::{self, local_testnet_seeds},
+	IdentifyVariant,
+};
+
+/// Runs a local testnet of several validators in this process.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct LocalTestnetCmd {
+	/// Number of validators to run.
+	#[clap(long, default_value = "3")]
+	pub validators: u32,
+
+	/// Local chain whose runtime is used, `alphanet-local` or `mainnet-local`.
+	#[clap(long, default_value = "mainnet-local")]
+	pub chain: String,
+
+	/// Port of the p2p network of the first validator, the next ones use the following ports.
+	#[clap(long, default_value = "30333")]
+	pub port: u16,
+
+	/// Websocket RPC port of the first validator, the next ones use the following ports.
+	#[clap(long, default_value = "9944")]
+	pub ws_port: u16,
+
+	/// HTTP RPC port of the first validator, the next ones use the following ports.
+	#[clap(long, default_value = "9933")]
+	pub rpc_port: u16,
+
+	/// Prometheus port of the first validator, the next ones use the following ports.
+	#[clap(long, default_value = "9615")]
+	pub prometheus_port: u16,
+}
+
+impl LocalTestnetCmd {
+	/// Node command of the validator at `index`, booting from `bootnode` if given.
+	pub fn node_cmd(&self, index: u32, bootnode: Option<&str>) -> Result<RunCmd> {
+		if self.validators == 0 {
+			return Err("At least one validator is needed".into())
+		}
+
+		let seed = &local_testnet_seeds(index + 1)[index as usize];
+		let port = |base: u16| {
+			base.checked_add(index as u16)
+				.ok_or_else(|| format!("No port left for the validator {}", seed))
+		};
+
+		let mut args = vec![
+			"local-testnet".to_string(),
+			"--chain".into(),
+			self.chain.clone(),
+			"--tmp".into(),
+			"--validator".into(),
+			"--name".into(),
+			seed.clone(),
+			"--port".into(),
+			port(self.port)?.to_string(),
+			"--ws-port".into(),
+			port(self.ws_port)?.to_string(),
+			"--rpc-port".into(),
+			port(self.rpc_port)?.to_string(),
+			"--prometheus-port".into(),
+			port(self.prometheus_port)?.to_string(),
+			"--no-telemetry".into(),
+			"--no-mdns".into(),
+			"--discover-local".into(),
+		];
+		if let Some(bootnode) = bootnode {
+			args.extend(["--bootnodes".into(), bootnode.to_string()]);
+		}
+
+		RunCmd::try_parse_from(args).map_err(|e| e.to_string().into())
+	}
+
+	/// Address of the first validator, given its peer id.
+	pub fn bootnode(&self, peer_id: impl std::fmt::Display) -> String {
+		format!("/ip4/127.0.0.1/tcp/{}/p2p/{}", self.port, peer_id)
+	}
+
+	/// Gives the configuration of the validator at `index` the chain spec with all the validators
+	/// and puts the session keys of the validator in its keystore.
+	pub fn prepare(&self, mut config: Configuration, index: u32) -> Result<Configuration> {
+		config.chain_spec = self.chain_spec(&config.chain_spec)?;
+
+		let path = match &config.keystore {
+			KeystoreConfig::Path { path, .. } => path,
+			KeystoreConfig::InMemory => return Err("The validators need a keystore path".into()),
+		};
+		let keystore = LocalKeystore::open(path, None)
+			.map_err(|e| format!("Failed to open the keystore: {}", e))?;
+
+		let seed = &local_testnet_seeds(index + 1)[index as usize];
+		let suri = format!("//{}", seed);
+		let insert_error = |e| format!("Failed to insert the keys of {}: {:?}", seed, e);
+		for key_type in [key_types::BABE, key_types::IM_ONLINE, key_types::AUTHORITY_DISCOVERY] {
+			keystore.sr25519_generate_new(key_type, Some(&suri)).map_err(insert_error)?;
+		}
+		keystore
+			.ed25519_generate_new(key_types::GRANDPA, Some(&suri))
+			.map_err(insert_error)?;
+
+		Ok(config)
+	}
+
+	fn chain_spec(&self, spec: &Box<dyn ChainSpec>) -> Result<Box<dyn ChainSpec>> {
+		match spec.runtime()? {
+			#[cfg(feature = "alphanet-native")]
+			chain_spec::Runtime::Alphanet =>
+				Ok(Box::new(chain_spec::alphanet::local_testnet_config(self.validators))),
+			#[cfg(feature = "mainnet-native")]
+			chain_spec::Runtime::Mainnet =>
+				Ok(Box::new(chain_spec::mainnet::local_testnet_config(self.validators))),
+			#[allow(unreachable_patterns)]
+			_ => Err(format!("{} is not a chain of this binary", spec.id()).into()),
+		}
+	}
+}
@@ -0,0 +1,317 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The `staking-miner` subcommand.
+//!
+//! Connects to a node, waits for the signed phase of `pallet_election_provider_multi_phase`,
+//! mines a solution out of the voters snapshot of the round using the native runtime and submits
+//! it if it beats the best known score by at least `BetterSignedThreshold`.
+//!
+//! Mining is done with the native runtime, the node has to run the same runtime version as this
+//! binary.
+
+use jsonrpsee::{
+	core::client::ClientT,
+	rpc_params,
+	types::ParamsSer,
+	ws_client::{WsClient, WsClientBuilder},
+};
+use parity_scale_codec::Decode;
+use sc_cli::{CliConfiguration, Result, SharedParams};
+use serde::de::DeserializeOwned;
+use sp_core::storage::{StorageData, StorageKey};
+use std::time::Duration;
+use ternoa_core_primitives::Hash;
+
+/// Time to wait between two checks of the election phase.
+const POLL_INTERVAL: Duration = Duration::from_secs(6);
+
+/// Mines NPoS election solutions and submits them during the signed phase.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct StakingMinerCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	/// Websocket endpoint of the node to connect to.
+	#[clap(long, default_value = "ws://127.0.0.1:9944")]
+	pub uri: String,
+
+	/// Secret URI of the sr25519 account submitting the solutions, e.g. `//Alice` or a mnemonic.
+	///
+	/// The account pays the transaction fees and the submission deposit.
+	#[clap(long)]
+	pub suri: String,
+
+	/// Mine and log the solutions without submitting them.
+	#[clap(long)]
+	pub dry_run: bool,
+
+	/// Exit after the first signed phase instead of waiting for the next rounds.
+	#[clap(long)]
+	pub once: bool,
+}
+
+impl CliConfiguration for StakingMinerCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+}
+
+/// Calls an RPC method of the node.
+async fn rpc<R: DeserializeOwned>(
+	client: &WsClient,
+	method: &str,
+	params: Option<ParamsSer<'_>>,
+) -> Result<R> {
+	client
+		.request(method, params)
+		.await
+		.map_err(|e| format!("RPC call `{}` failed: {:?}", method, e).into())
+}
+
+/// Reads and decodes a storage value at the given block.
+async fn storage<V: Decode>(client: &WsClient, key: Vec<u8>, at: Hash) -> Result<Option<V>> {
+	let data: Option<StorageData> =
+		rpc(client, "state_getStorage", rpc_params![StorageKey(key), at]).await?;
+
+	data.map(|data| {
+		V::decode(&mut &*data.0).map_err(|e| format!("Failed to decode storage: {:?}", e).into())
+	})
+	.transpose()
+}
+
+macro_rules! impl_staking_miner {
+	($name:ident, $runtime:ident) => {
+		/// Staking miner for the runtime of the same name.
+		pub mod $name {
+			use super::*;
+
+			use frame_election_provider_support::NposSolution;
+			use frame_support::traits::Get;
+			use pallet_election_provider_multi_phase as epm;
+			use parity_scale_codec::Encode;
+			use sp_core::{crypto::Pair as _, sr25519, Bytes};
+			use sp_runtime::{generic::Era, traits::Header as _, SaturatedConversion};
+			use sp_version::RuntimeVersion;
+			use ternoa_core_primitives::{AccountId, Block, BlockNumber, Header, Index};
+			use ternoa_runtime_common::election_provider_multi_phase::{
+				NposCompactSolution24, Solver,
+			};
+			use ternoa_service::$runtime as runtime;
+
+			use runtime::{Runtime, RuntimeCall};
+
+			/// Mines and submits a solution during each signed phase.
+			pub async fn run(cmd: StakingMinerCmd) -> Result<()> {
+				let signer = sr25519::Pair::from_string(&cmd.suri, None)
+					.map_err(|e| format!("Invalid secret URI: {:?}", e))?;
+
+				let client = WsClientBuilder::default()
+					.max_request_body_size(u32::MAX)
+					.build(&cmd.uri)
+					.await
+					.map_err(|e| format!("Failed to connect to {}: {:?}", cmd.uri, e))?;
+
+				// The solutions are mined and the transactions signed with the native runtime.
+				let version: RuntimeVersion =
+					rpc(&client, "state_getRuntimeVersion", rpc_params![]).await?;
+				if version.spec_name != runtime::VERSION.spec_name ||
+					version.spec_version != runtime::VERSION.spec_version
+				{
+					return Err(format!(
+						"The node runs {}-{} but this binary was built for {}-{}",
+						version.spec_name,
+						version.spec_version,
+						runtime::VERSION.spec_name,
+						runtime::VERSION.spec_version,
+					)
+					.into())
+				}
+
+				let genesis: Hash = rpc(&client, "chain_getBlockHash", rpc_params![0u32]).await?;
+
+				let mut last_round = None;
+				loop {
+					let header: Header = rpc(&client, "chain_getHeader", rpc_params![]).await?;
+					let at = header.hash();
+
+					let phase = storage::<epm::Phase<BlockNumber>>(
+						&client,
+						epm::CurrentPhase::<Runtime>::hashed_key().to_vec(),
+						at,
+					)
+					.await?
+					.unwrap_or_default();
+					let round =
+						storage::<u32>(&client, epm::Round::<Runtime>::hashed_key().to_vec(), at)
+							.await?
+							.unwrap_or(1);
+
+					if phase.is_signed() && last_round != Some(round) {
+						last_round = Some(round);
+						if let Err(e) =
+							mine_and_submit(&cmd, &client, &signer, genesis, &header).await
+						{
+							log::error!(target: "staking-miner", "Round {}: {}", round, e);
+						}
+
+						if cmd.once {
+							return Ok(())
+						}
+					}
+
+					futures_timer::Delay::new(POLL_INTERVAL).await;
+				}
+			}
+
+			/// Mines a solution at the given block and submits it if it is good enough.
+			async fn mine_and_submit(
+				cmd: &StakingMinerCmd,
+				client: &WsClient,
+				signer: &sr25519::Pair,
+				genesis: Hash,
+				header: &Header,
+			) -> Result<()> {
+				let at = header.hash();
+
+				let mut ext = remote_externalities::Builder::<Block>::new()
+					.mode(remote_externalities::Mode::Online(remote_externalities::OnlineConfig {
+						transport: cmd.uri.clone().into(),
+						at: Some(at),
+						pallets: vec!["ElectionProviderMultiPhase".to_owned()],
+						..Default::default()
+					}))
+					.build()
+					.await
+					.map_err(|e| format!("Failed to fetch the election state: {}", e))?;
+
+				let raw_solution = ext.execute_with(|| -> Result<_> {
+					let epm::RoundSnapshot { voters, targets } = epm::Pallet::<Runtime>::snapshot()
+						.ok_or("The snapshot of the round is missing")?;
+					let desired_targets = epm::Pallet::<Runtime>::desired_targets()
+						.ok_or("The desired targets of the round are missing")?;
+					let voter_count = voters.len();
+
+					let (solution, score) = epm::Miner::<Runtime>::mine_solution_with_snapshot::<
+						Solver<Runtime>,
+					>(voters, targets, desired_targets)
+					.map_err(|e| format!("Failed to mine a solution: {:?}", e))?;
+
+					log::info!(
+						target: "staking-miner",
+						"Mined a solution with {} of {} voters, score {:?}",
+						solution.voter_count(),
+						voter_count,
+						score,
+					);
+
+					// The score has to beat both the queued signed submissions and the minimum
+					// score set by governance.
+					let best = epm::SignedSubmissionIndices::<Runtime>::get()
+						.iter()
+						.map(|(score, ..)| *score)
+						.chain(epm::Pallet::<Runtime>::minimum_untrusted_score())
+						.max();
+					let threshold = <Runtime as epm::Config>::BetterSignedThreshold::get();
+					if let Some(best) = best {
+						if !score.strict_threshold_better(best, threshold) {
+							log::info!(
+								target: "staking-miner",
+								"Not submitting, the best known score is {:?}",
+								best,
+							);
+							return Ok(None)
+						}
+					}
+
+					Ok(Some(epm::RawSolution::<NposCompactSolution24> {
+						solution,
+						score,
+						round: epm::Pallet::<Runtime>::round(),
+					}))
+				})?;
+
+				let raw_solution = match raw_solution {
+					Some(raw_solution) if !cmd.dry_run => raw_solution,
+					_ => return Ok(()),
+				};
+
+				let call = RuntimeCall::ElectionProviderMultiPhase(epm::Call::submit {
+					raw_solution: Box::new(raw_solution),
+				});
+
+				let account = AccountId::from(signer.public());
+				let nonce: Index =
+					rpc(client, "system_accountNextIndex", rpc_params![account.clone()]).await?;
+				let period = ternoa_runtime_common::BlockHashCount::get()
+					.checked_next_power_of_two()
+					.map(|c| c / 2)
+					.unwrap_or(2) as u64;
+
+				let extra: runtime::SignedExtra = (
+					frame_system::CheckSpecVersion::<Runtime>::new(),
+					frame_system::CheckTxVersion::<Runtime>::new(),
+					frame_system::CheckGenesis::<Runtime>::new(),
+					frame_system::CheckEra::<Runtime>::from(Era::mortal(
+						period,
+						(*header.number()).saturated_into(),
+					)),
+					frame_system::CheckNonce::<Runtime>::from(nonce),
+					frame_system::CheckWeight::<Runtime>::new(),
+					pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(0, None),
+				);
+
+				// With the periods in use the era starts at the current block.
+				let payload = runtime::SignedPayload::from_raw(
+					call.clone(),
+					extra.clone(),
+					(
+						runtime::VERSION.spec_version,
+						runtime::VERSION.transaction_version,
+						genesis,
+						at,
+						(),
+						(),
+						(),
+					),
+				);
+				let signature = payload.using_encoded(|p| signer.sign(p));
+				let extrinsic = runtime::UncheckedExtrinsic::new_signed(
+					call,
+					runtime::Address::Id(account),
+					ternoa_core_primitives::Signature::Sr25519(signature),
+					extra,
+				);
+
+				let hash: Hash = rpc(
+					client,
+					"author_submitExtrinsic",
+					rpc_params![Bytes(extrinsic.encode())],
+				)
+				.await?;
+				log::info!(target: "staking-miner", "Submitted the solution in {:?}", hash);
+
+				Ok(())
+			}
+		}
+	};
+}
+
+#[cfg(feature = "alphanet-native")]
+impl_staking_miner!(alphanet, alphanet_runtime);
+#[cfg(feature = "mainnet-native")]
+impl_staking_miner!(mainnet, mainnet_runtime);
@@ -95,7 +95,7 @@ impl BenchmarkCallSigner<mainnet_runtime::RuntimeCall, sp_core::sr25519::Pair>
 			)),
 			frame_system::CheckNonce::<runtime::Runtime>::from(nonce),
 			frame_system::CheckWeight::<runtime::Runtime>::new(),
-			pallet_transaction_payment::ChargeTransactionPayment::<runtime::Runtime>::from(0),
+			pallet_asset_tx_payment::ChargeAssetTxPayment::<runtime::Runtime>::from(0, None),
 		);
 
 		let payload = runtime::SignedPayload::from_raw(
@@ -115,7 +115,7 @@ impl BenchmarkCallSigner<mainnet_runtime::RuntimeCall, sp_core::sr25519::Pair>
 		let signature = payload.using_encoded(|p| acc.sign(p));
 		runtime::UncheckedExtrinsic::new_signed(
 			call,
-			sp_runtime::AccountId32::from(acc.public()).into(),
+			runtime::Address::Id(sp_runtime::AccountId32::from(acc.public())),
 			ternoa_core_primitives::Signature::Sr25519(signature.clone()),
 			extra,
 		)
@@ -148,7 +148,7 @@ impl BenchmarkCallSigner<alphanet_runtime::RuntimeCall, sp_core::sr25519::Pair>
 			)),
 			frame_system::CheckNonce::<runtime::Runtime>::from(nonce),
 			frame_system::CheckWeight::<runtime::Runtime>::new(),
-			pallet_transaction_payment::ChargeTransactionPayment::<runtime::Runtime>::from(0),
+			pallet_asset_tx_payment::ChargeAssetTxPayment::<runtime::Runtime>::from(0, None),
 		);
 
 		let payload = runtime::SignedPayload::from_raw(
@@ -168,7 +168,7 @@ impl BenchmarkCallSigner<alphanet_runtime::RuntimeCall, sp_core::sr25519::Pair>
 		let signature = payload.using_encoded(|p| acc.sign(p));
 		runtime::UncheckedExtrinsic::new_signed(
 			call,
-			sp_runtime::AccountId32::from(acc.public()).into(),
+			runtime::Address::Id(sp_runtime::AccountId32::from(acc.public())),
 			ternoa_core_primitives::Signature::Sr25519(signature.clone()),
 			extra,
 		)
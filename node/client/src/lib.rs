@@ -83,10 +83,16 @@ pub trait RuntimeApiCollection:
 	+ sp_block_builder::BlockBuilder<Block>
 	+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>
 	+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
+	+ pallet_nomination_pools_runtime_api::NominationPoolsApi<Block, AccountId, Balance>
 	+ sp_api::Metadata<Block>
 	+ sp_offchain::OffchainWorkerApi<Block>
 	+ sp_session::SessionKeys<Block>
 	+ sp_authority_discovery::AuthorityDiscoveryApi<Block>
+	+ ternoa_runtime_apis::nft::NftApi<Block, AccountId>
+	+ ternoa_runtime_apis::marketplace::MarketplaceApi<Block, AccountId, Balance>
+	+ ternoa_runtime_apis::auction::AuctionApi<Block, AccountId, Balance, BlockNumber>
+	+ ternoa_runtime_apis::rent::RentApi<Block, AccountId, BlockNumber>
+	+ ternoa_runtime_apis::staking_payout::StakingPayoutApi<Block, AccountId>
 where
 	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
@@ -101,10 +107,16 @@ where
 		+ sp_block_builder::BlockBuilder<Block>
 		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>
 		+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
+		+ pallet_nomination_pools_runtime_api::NominationPoolsApi<Block, AccountId, Balance>
 		+ sp_api::Metadata<Block>
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>
-		+ sp_authority_discovery::AuthorityDiscoveryApi<Block>,
+		+ sp_authority_discovery::AuthorityDiscoveryApi<Block>
+		+ ternoa_runtime_apis::nft::NftApi<Block, AccountId>
+		+ ternoa_runtime_apis::marketplace::MarketplaceApi<Block, AccountId, Balance>
+		+ ternoa_runtime_apis::auction::AuctionApi<Block, AccountId, Balance, BlockNumber>
+		+ ternoa_runtime_apis::rent::RentApi<Block, AccountId, BlockNumber>
+		+ ternoa_runtime_apis::staking_payout::StakingPayoutApi<Block, AccountId>,
 	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
 }
@@ -14,21 +14,27 @@
 // You should have received a copy of the GNU General Public License
 // along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
 
-use super::{get_account_id_from_seed, get_from_seed, MainnetChainSpec as ChainSpec};
+use super::{
+	get_account_id_from_seed, get_from_seed, local_testnet_seeds, network::NetworkConfig,
+	MainnetChainSpec as ChainSpec, Runtime,
+};
 use mainnet_runtime::{
 	constants::currency::CAPS, wasm_binary_unwrap, AuthorityDiscoveryConfig, BabeConfig,
-	BalancesConfig, CouncilConfig, GenesisConfig, GrandpaConfig, ImOnlineConfig, SessionConfig,
-	SessionKeys, StakingConfig, SystemConfig, TechnicalMembershipConfig, BABE_GENESIS_EPOCH_CONFIG,
+	BalancesConfig, CouncilConfig, GenesisConfig, GenesisFeesConfig, GrandpaConfig, ImOnlineConfig,
+	NominationPoolsConfig, SessionConfig, SessionKeys, StakingConfig, SystemConfig,
+	TechnicalMembershipConfig, TransactionStorageConfig, VestingConfig, BABE_GENESIS_EPOCH_CONFIG,
 };
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
-use pallet_staking::Forcing;
+use pallet_staking::{Forcing, StakerStatus};
 use sc_chain_spec::ChainType;
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use sp_consensus_babe::AuthorityId as BabeId;
-use sp_core::sr25519;
+use sp_core::{sr25519, Get};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::{BoundedVec, Perbill};
-use ternoa_core_primitives::AccountId;
+use std::collections::BTreeMap;
+use ternoa_core_primitives::{AccountId, Balance};
+use ternoa_runtime_common::{nomination_pools, technical_collective, transaction_storage};
 
 fn session_keys(
 	grandpa: GrandpaId,
@@ -60,10 +66,25 @@ pub fn authority_keys_from_seed(
 	)
 }
 
+const ENDOWMENT: Balance = 1_000_000 * CAPS;
+const STASH: Balance = 100 * CAPS;
+
 pub struct GenesisInput {
 	pub initial_authorities: Vec<AuthorityKeys>,
 	pub committee_members: Vec<AccountId>,
+	pub council_members: Vec<AccountId>,
 	pub invulnerables: Vec<AccountId>,
+	/// Number of validators to elect.
+	pub validator_count: u32,
+	pub minimum_validator_count: u32,
+	/// Amount bonded by each initial authority, its stash is endowed with it if needed.
+	pub stash: Balance,
+	/// Balances given at genesis.
+	pub endowments: Vec<(AccountId, Balance)>,
+	/// Balances given on top of the endowments, e.g. team and investor allocations.
+	pub allocations: Vec<(AccountId, Balance)>,
+	/// Linear vesting schedules locking part of the balances, as `(who, begin, length, liquid)`.
+	pub vesting: VestingConfig,
 }
 
 fn development_accounts() -> Vec<AccountId> {
@@ -87,18 +108,26 @@ fn development_config_genesis() -> GenesisConfig {
 	let initial_authorities = vec![authority_keys_from_seed("Alice")];
 	let committee_members = vec![sr25519_account_from_seed("Alice")];
 	let invulnerables = vec![initial_authorities[0].0.clone()];
+	let endowments = development_accounts().into_iter().map(|x| (x, ENDOWMENT)).collect();
 
-	let input = GenesisInput { initial_authorities, committee_members, invulnerables };
+	let input = GenesisInput {
+		initial_authorities,
+		committee_members,
+		council_members: vec![],
+		invulnerables,
+		validator_count: 1,
+		minimum_validator_count: 1,
+		stash: STASH,
+		endowments,
+		allocations: vec![],
+		vesting: Default::default(),
+	};
 
 	genesis(input)
 }
 
 /// Development config (single validator Alice)
 pub fn development_config() -> ChainSpec {
-	let mut properties = sc_chain_spec::Properties::new();
-	properties.insert("tokenSymbol".into(), "CAPS".into());
-	properties.insert("tokenDecimals".into(), 18.into());
-
 	ChainSpec::from_genesis(
 		"Ternoa Mainnet Development",
 		"mainnet-dev",
@@ -108,26 +137,138 @@ pub fn development_config() -> ChainSpec {
 		None,
 		Some("ternoa"),
 		None,
-		Some(properties),
+		Some(Runtime::Mainnet.properties()),
+		Default::default(),
+	)
+}
+
+fn local_testnet_genesis(validators: u32) -> GenesisConfig {
+	let seeds = local_testnet_seeds(validators);
+	let initial_authorities: Vec<_> =
+		seeds.iter().map(|seed| authority_keys_from_seed(seed)).collect();
+	let committee_members = initial_authorities
+		.iter()
+		.map(|x| x.1.clone())
+		.take(technical_collective::TechnicalMaxMembers::get() as usize)
+		.collect();
+
+	// The validators beyond the development accounts need funds to pay their fees.
+	let mut endowed_accounts = development_accounts();
+	for keys in &initial_authorities {
+		for account in [&keys.0, &keys.1] {
+			if !endowed_accounts.contains(account) {
+				endowed_accounts.push(account.clone());
+			}
+		}
+	}
+
+	let input = GenesisInput {
+		initial_authorities,
+		committee_members,
+		council_members: vec![],
+		// Nobody is invulnerable so that offences and slashes can be tested.
+		invulnerables: vec![],
+		validator_count: validators,
+		minimum_validator_count: 1,
+		stash: STASH,
+		endowments: endowed_accounts.into_iter().map(|x| (x, ENDOWMENT)).collect(),
+		allocations: vec![],
+		vesting: Default::default(),
+	};
+
+	genesis(input)
+}
+
+/// Local testnet config with the given number of validators, the development accounts first.
+pub fn local_testnet_config(validators: u32) -> ChainSpec {
+	ChainSpec::from_genesis(
+		"Ternoa Mainnet Local Testnet",
+		"mainnet-local",
+		ChainType::Local,
+		move || local_testnet_genesis(validators),
+		vec![],
+		None,
+		Some("ternoa"),
+		None,
+		Some(Runtime::Mainnet.properties()),
 		Default::default(),
 	)
 }
 
+fn network_config_genesis(network: &NetworkConfig) -> GenesisConfig {
+	let input = GenesisInput {
+		initial_authorities: network.authorities(),
+		committee_members: network.committee.clone(),
+		council_members: network.council.clone(),
+		invulnerables: network.invulnerables(),
+		validator_count: network.validator_count(),
+		minimum_validator_count: network.staking.minimum_validator_count,
+		stash: network.bond(),
+		endowments: network.endowments(),
+		allocations: vec![],
+		vesting: Default::default(),
+	};
+
+	GenesisConfig {
+		genesis_fees: GenesisFeesConfig {
+			nft_mint_fee: network.nft_mint_fee(),
+			secret_nft_mint_fee: network.secret_nft_mint_fee(),
+			marketplace_mint_fee: network.marketplace_mint_fee(),
+		},
+		..genesis(input)
+	}
+}
+
+/// Chain spec of the network described by a network file.
+pub fn network_config(network: &NetworkConfig) -> Result<ChainSpec, String> {
+	if network.committee.len() > technical_collective::TechnicalMaxMembers::get() as usize {
+		return Err("Too many technical committee members".into())
+	}
+
+	let genesis_network = network.clone();
+	Ok(ChainSpec::from_genesis(
+		&network.name,
+		&network.id,
+		network.chain_type.clone(),
+		move || network_config_genesis(&genesis_network),
+		network.boot_nodes()?,
+		None,
+		network.protocol_id.as_deref().or(Some("ternoa")),
+		None,
+		Some(Runtime::Mainnet.properties()),
+		Default::default(),
+	))
+}
+
 /// Helper function to create GenesisConfig for dev testo
 pub fn genesis(input: GenesisInput) -> GenesisConfig {
-	let GenesisInput { initial_authorities, committee_members, invulnerables } = input;
-
-	let endowed_accounts: Vec<AccountId> = development_accounts();
+	let GenesisInput {
+		initial_authorities,
+		committee_members,
+		council_members,
+		invulnerables,
+		validator_count,
+		minimum_validator_count,
+		stash,
+		endowments,
+		allocations,
+		vesting,
+	} = input;
 
-	const ENDOWMENT: u128 = 1_000_000 * CAPS;
-	const STASH: u128 = 100 * CAPS;
+	// An account can't appear twice in the balances genesis.
+	let mut balances: BTreeMap<AccountId, Balance> = BTreeMap::new();
+	for (account, amount) in endowments.into_iter().chain(allocations) {
+		*balances.entry(account).or_default() += amount;
+	}
+	for keys in &initial_authorities {
+		let balance = balances.entry(keys.0.clone()).or_default();
+		*balance = (*balance).max(stash);
+	}
 
 	GenesisConfig {
 		// Core
 		system: SystemConfig { code: wasm_binary_unwrap().to_vec() },
-		balances: BalancesConfig {
-			balances: endowed_accounts.iter().cloned().map(|x| (x, ENDOWMENT)).collect(),
-		},
+		balances: BalancesConfig { balances: balances.into_iter().collect() },
 
 		// Consensus
 		session: SessionConfig {
@@ -147,13 +288,11 @@ pub fn genesis(input: GenesisInput) -> GenesisConfig {
 		authority_discovery: AuthorityDiscoveryConfig { keys: vec![] },
 		grandpa: GrandpaConfig { authorities: vec![] },
 		staking: StakingConfig {
-			minimum_validator_count: 1,
-			validator_count: initial_authorities.len() as u32,
+			minimum_validator_count,
+			validator_count,
 			stakers: initial_authorities
 				.iter()
-				.map(|x| {
-					(x.0.clone(), x.1.clone(), STASH, alphanet_runtime::StakerStatus::Validator)
-				})
+				.map(|x| (x.0.clone(), x.1.clone(), stash, StakerStatus::Validator))
 				.collect(),
 			invulnerables,
 			force_era: Forcing::NotForcing,
@@ -162,14 +301,28 @@ pub fn genesis(input: GenesisInput) -> GenesisConfig {
 		},
 		treasury: Default::default(),
 		transaction_payment: Default::default(),
+		indices: Default::default(),
 		technical_committee: Default::default(),
 		technical_membership: TechnicalMembershipConfig {
 			members: BoundedVec::try_from(committee_members).unwrap(),
 			..Default::default()
 		},
-		council: CouncilConfig { members: vec![], ..Default::default() },
-		democracy: Default::default(),
+		council: CouncilConfig { members: council_members, ..Default::default() },
 		phragmen_election: Default::default(),
 		assets: Default::default(),
+		nomination_pools: NominationPoolsConfig {
+			min_create_bond: nomination_pools::MinCreateBond::get(),
+			min_join_bond: nomination_pools::MinJoinBond::get(),
+			max_pools: Some(nomination_pools::MaxPools::get()),
+			max_members_per_pool: Some(nomination_pools::MaxMembersPerPool::get()),
+			max_members: Some(nomination_pools::MaxMembers::get()),
+		},
+		vesting,
+		transaction_storage: TransactionStorageConfig {
+			byte_fee: transaction_storage::ByteFee::get(),
+			entry_fee: transaction_storage::EntryFee::get(),
+			storage_period: transaction_storage::StoragePeriod::get(),
+		},
+		genesis_fees: Default::default(),
 	}
 }
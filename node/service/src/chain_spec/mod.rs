@@ -14,16 +14,20 @@
 // You should have received a copy of the GNU General Public License
 // along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
 
-use sc_chain_spec::{ChainSpecExtension, GenericChainSpec};
+use sc_chain_spec::{ChainSpec, ChainSpecExtension, GenericChainSpec, Properties};
+use sc_executor::read_embedded_version;
+use sc_executor_common::runtime_blob::RuntimeBlob;
 use serde::{Deserialize, Serialize};
-use sp_core::{Pair, Public};
+use sp_core::{bytes, storage::well_known_keys, Pair, Public};
 use sp_runtime::traits::{IdentifyAccount, Verify};
+use std::{fs::File, io::BufReader, path::Path};
 use ternoa_core_primitives::{AccountId, Block, Signature};
 
 #[cfg(feature = "alphanet-native")]
 pub mod alphanet;
 #[cfg(feature = "mainnet-native")]
 pub mod mainnet;
+pub mod network;
 
 type AccountPublic = <Signature as Verify>::Signer;
 
@@ -71,6 +75,123 @@ pub fn mainnet_config() -> Result<MainnetChainSpec, String> {
 	)
 }
 
+/// Chain spec property naming the runtime of the chain.
+pub const RUNTIME_PROPERTY: &str = "runtime";
+
+/// Runtime a chain runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Runtime {
+	Alphanet,
+	Mainnet,
+}
+
+impl Runtime {
+	/// Name of the runtime, used in the [`RUNTIME_PROPERTY`] and as its `spec_name`.
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::Alphanet => "alphanet",
+			Self::Mainnet => "mainnet",
+		}
+	}
+
+	fn from_name(name: &str) -> Option<Self> {
+		[Self::Alphanet, Self::Mainnet]
+			.into_iter()
+			.find(|runtime| runtime.name() == name)
+	}
+
+	/// Properties of the chain specs built for the runtime.
+	pub fn properties(&self) -> Properties {
+		let mut properties = Properties::new();
+		properties.insert("tokenSymbol".into(), "CAPS".into());
+		properties.insert("tokenDecimals".into(), 18.into());
+		properties.insert(RUNTIME_PROPERTY.into(), self.name().into());
+		properties
+	}
+
+	/// Runtime of a chain spec, given by its [`RUNTIME_PROPERTY`] or, when the property is not
+	/// set, by the `spec_name` of its genesis runtime.
+	pub fn of(spec: &dyn ChainSpec) -> Result<Self, String> {
+		if let Some(name) = spec.properties().get(RUNTIME_PROPERTY) {
+			return Self::from_property(spec.id(), name)
+		}
+
+		let storage = spec.as_storage_builder().build_storage()?;
+		let code = storage.top.get(well_known_keys::CODE).ok_or_else(|| {
+			format!("{} has no `{}` property and no genesis runtime", spec.id(), RUNTIME_PROPERTY)
+		})?;
+		Self::from_code(spec.id(), code)
+	}
+
+	/// Runtime of a chain spec file, read before knowing which genesis config the file holds.
+	///
+	/// When the file has both the [`RUNTIME_PROPERTY`] and a genesis runtime, they must agree.
+	pub fn of_json_file(path: &Path) -> Result<Self, String> {
+		let file = File::open(path)
+			.map_err(|e| format!("Error opening spec file `{}`: {}", path.display(), e))?;
+		let json: serde_json::Value = serde_json::from_reader(BufReader::new(file))
+			.map_err(|e| format!("Error parsing spec file `{}`: {}", path.display(), e))?;
+		let id = json["id"].as_str().unwrap_or_default();
+
+		// Raw specs hold the code under its storage key, human ones in the system genesis config.
+		let code = json["genesis"]["raw"]["top"][bytes::to_hex(well_known_keys::CODE, false)]
+			.as_str()
+			.or_else(|| json["genesis"]["runtime"]["system"]["code"].as_str())
+			.map(|code| {
+				bytes::from_hex(code)
+					.map_err(|e| format!("The genesis runtime of {} is not valid hex: {}", id, e))
+			})
+			.transpose()?;
+
+		match (json["properties"].get(RUNTIME_PROPERTY), code) {
+			(Some(name), Some(code)) => {
+				let runtime = Self::from_property(id, name)?;
+				let code_runtime = Self::from_code(id, &code)?;
+				if runtime != code_runtime {
+					return Err(format!(
+						"The `{}` property of {} is `{}` but its genesis runtime is `{}`",
+						RUNTIME_PROPERTY,
+						id,
+						runtime.name(),
+						code_runtime.name(),
+					))
+				}
+				Ok(runtime)
+			},
+			(Some(name), None) => Self::from_property(id, name),
+			(None, Some(code)) => Self::from_code(id, &code),
+			(None, None) =>
+				Err(format!("{} has no `{}` property and no genesis runtime", id, RUNTIME_PROPERTY)),
+		}
+	}
+
+	fn from_property(id: &str, name: &serde_json::Value) -> Result<Self, String> {
+		name.as_str().and_then(Self::from_name).ok_or_else(|| {
+			format!(
+				"Unknown runtime {} in the `{}` property of {}, expected `alphanet` or `mainnet`",
+				name, RUNTIME_PROPERTY, id,
+			)
+		})
+	}
+
+	fn from_code(id: &str, code: &[u8]) -> Result<Self, String> {
+		let version = RuntimeBlob::uncompress_if_needed(code)
+			.and_then(|blob| read_embedded_version(&blob))
+			.map_err(|e| {
+				format!("Failed to read the version of the genesis runtime of {}: {}", id, e)
+			})?
+			.ok_or_else(|| format!("The genesis runtime of {} has no embedded version", id))?;
+
+		Self::from_name(&version.spec_name).ok_or_else(|| {
+			format!(
+				"{} runs the unknown `{}` runtime, expected `alphanet` or `mainnet`",
+				id, version.spec_name,
+			)
+		})
+	}
+}
+
 /// Helper function to generate a crypto pair from seeds
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
 	TPublic::Pair::from_string(&format!("//{}", seed), None)
@@ -85,3 +206,18 @@ where
 {
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
+
+/// Number of validators of the `alphanet-local` and `mainnet-local` chains.
+pub const LOCAL_TESTNET_VALIDATORS: u32 = 3;
+
+/// Seeds of the validators of a local testnet, the development accounts being used first.
+pub fn local_testnet_seeds(validators: u32) -> Vec<String> {
+	const DEVELOPMENT_SEEDS: [&str; 6] = ["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"];
+
+	(0..validators as usize)
+		.map(|i| match DEVELOPMENT_SEEDS.get(i) {
+			Some(seed) => seed.to_string(),
+			None => format!("Validator{}", i + 1),
+		})
+		.collect()
+}
@@ -0,0 +1,240 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Declarative description of a network, read from a TOML file by the `generate-spec`
+//! subcommand.
+//!
+//! ```toml
+//! runtime = "alphanet"
+//! name = "Ternoa Testnet"
+//! id = "ternoa-testnet"
+//! chain_type = "Live"
+//! boot_nodes = ["/dns/boot.example.com/tcp/30333/p2p/12D3KooW..."]
+//! committee = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+//! council = ["5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"]
+//!
+//! [[validators]]
+//! seed = "Alice"
+//!
+//! [[validators]]
+//! stash = "5GNJqTPyNqANBkUVMN1LPPrxXnFouWXoe2wNSmmEoLctxiZY"
+//! controller = "5HpG9w8EBLe5XCrbczpwq5TSXvedjrBGCwqxK1iQ7qUsSWFc"
+//! grandpa = "5FA9nQDVg267DEd8m1ZypXLBnvN7SFxYwV7ndqSYGiN9TTpu"
+//! babe = "5GoNkf6WdbxCFnPdAnYYQyCjAKPJgLNxXwPjwTh6DGg6gN3E"
+//! im_online = "5GoNkf6WdbxCFnPdAnYYQyCjAKPJgLNxXwPjwTh6DGg6gN3E"
+//! authority_discovery = "5GoNkf6WdbxCFnPdAnYYQyCjAKPJgLNxXwPjwTh6DGg6gN3E"
+//!
+//! [[endowments]]
+//! account = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+//! amount = 1_000_000
+//!
+//! [staking]
+//! validator_count = 10
+//! minimum_validator_count = 2
+//! bond = 100
+//!
+//! [fees]
+//! nft_mint = 10
+//! secret_nft_mint = 75
+//! marketplace_mint = 1_000
+//! ```
+//!
+//! Amounts are given in whole CAPS.
+
+use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
+use sc_chain_spec::ChainType;
+use serde::Deserialize;
+use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
+use sp_consensus_babe::AuthorityId as BabeId;
+use sp_core::sr25519;
+use sp_finality_grandpa::AuthorityId as GrandpaId;
+use std::path::Path;
+use ternoa_core_primitives::{AccountId, Balance};
+
+use super::{get_account_id_from_seed, get_from_seed, Runtime};
+
+/// One CAPS, in the smallest unit of the chain.
+const CAPS: Balance = 1_000_000_000_000_000_000;
+
+/// Stash, controller and session keys of a validator.
+pub type AuthorityKeys =
+	(AccountId, AccountId, GrandpaId, BabeId, ImOnlineId, AuthorityDiscoveryId);
+
+/// A genesis validator, given by its keys or by a seed from which they are derived.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Validator {
+	Seed {
+		seed: String,
+	},
+	Keys {
+		stash: AccountId,
+		controller: AccountId,
+		grandpa: GrandpaId,
+		babe: BabeId,
+		im_online: ImOnlineId,
+		authority_discovery: AuthorityDiscoveryId,
+	},
+}
+
+impl Validator {
+	/// Keys of the validator, derived as the development ones when given by a seed.
+	pub fn keys(&self) -> AuthorityKeys {
+		match self.clone() {
+			Self::Seed { seed } => (
+				get_account_id_from_seed::<sr25519::Public>(&format!("{}//stash", seed)),
+				get_account_id_from_seed::<sr25519::Public>(&seed),
+				get_from_seed::<GrandpaId>(&seed),
+				get_from_seed::<BabeId>(&seed),
+				get_from_seed::<ImOnlineId>(&seed),
+				get_from_seed::<AuthorityDiscoveryId>(&seed),
+			),
+			Self::Keys { stash, controller, grandpa, babe, im_online, authority_discovery } =>
+				(stash, controller, grandpa, babe, im_online, authority_discovery),
+		}
+	}
+}
+
+/// Balance given to an account at genesis.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Endowment {
+	pub account: AccountId,
+	/// Amount in CAPS.
+	pub amount: u64,
+}
+
+/// Staking targets of the first eras.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Staking {
+	/// Number of validators elected, the number of genesis validators when not set.
+	pub validator_count: Option<u32>,
+	pub minimum_validator_count: u32,
+	/// Amount bonded by each genesis validator, in CAPS.
+	pub bond: u64,
+	/// Validators which can't be slashed, all the genesis validators when not set.
+	pub invulnerables: Option<Vec<AccountId>>,
+}
+
+impl Default for Staking {
+	fn default() -> Self {
+		Self { validator_count: None, minimum_validator_count: 1, bond: 100, invulnerables: None }
+	}
+}
+
+/// Mint fees of the Ternoa pallets, in CAPS. The runtime defaults are kept when not set.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Fees {
+	pub nft_mint: Option<u64>,
+	pub secret_nft_mint: Option<u64>,
+	pub marketplace_mint: Option<u64>,
+}
+
+/// A network, as described in the TOML file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkConfig {
+	pub runtime: Runtime,
+	pub name: String,
+	pub id: String,
+	#[serde(default = "default_chain_type")]
+	pub chain_type: ChainType,
+	#[serde(default)]
+	pub boot_nodes: Vec<String>,
+	pub protocol_id: Option<String>,
+	pub validators: Vec<Validator>,
+	#[serde(default)]
+	pub endowments: Vec<Endowment>,
+	/// Members of the technical committee.
+	#[serde(default)]
+	pub committee: Vec<AccountId>,
+	#[serde(default)]
+	pub council: Vec<AccountId>,
+	#[serde(default)]
+	pub staking: Staking,
+	#[serde(default)]
+	pub fees: Fees,
+}
+
+fn default_chain_type() -> ChainType {
+	ChainType::Live
+}
+
+impl NetworkConfig {
+	/// Reads and checks a network file.
+	pub fn from_toml_file(path: &Path) -> Result<Self, String> {
+		let content = std::fs::read_to_string(path)
+			.map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+		let config: Self = toml::from_str(&content)
+			.map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+		if config.validators.is_empty() {
+			return Err("At least one validator is needed".into())
+		}
+		if config.staking.minimum_validator_count as usize > config.validators.len() {
+			return Err("The minimum validator count is above the number of validators".into())
+		}
+
+		Ok(config)
+	}
+
+	pub fn authorities(&self) -> Vec<AuthorityKeys> {
+		self.validators.iter().map(Validator::keys).collect()
+	}
+
+	pub fn endowments(&self) -> Vec<(AccountId, Balance)> {
+		self.endowments
+			.iter()
+			.map(|e| (e.account.clone(), e.amount as Balance * CAPS))
+			.collect()
+	}
+
+	pub fn validator_count(&self) -> u32 {
+		self.staking.validator_count.unwrap_or(self.validators.len() as u32)
+	}
+
+	pub fn bond(&self) -> Balance {
+		self.staking.bond as Balance * CAPS
+	}
+
+	pub fn invulnerables(&self) -> Vec<AccountId> {
+		match &self.staking.invulnerables {
+			Some(invulnerables) => invulnerables.clone(),
+			None => self.authorities().into_iter().map(|keys| keys.0).collect(),
+		}
+	}
+
+	pub fn boot_nodes(&self) -> Result<Vec<sc_network::config::MultiaddrWithPeerId>, String> {
+		self.boot_nodes
+			.iter()
+			.map(|addr| addr.parse().map_err(|e| format!("Invalid boot node {}: {}", addr, e)))
+			.collect()
+	}
+
+	pub fn nft_mint_fee(&self) -> Option<Balance> {
+		self.fees.nft_mint.map(|fee| fee as Balance * CAPS)
+	}
+
+	pub fn secret_nft_mint_fee(&self) -> Option<Balance> {
+		self.fees.secret_nft_mint.map(|fee| fee as Balance * CAPS)
+	}
+
+	pub fn marketplace_mint_fee(&self) -> Option<Balance> {
+		self.fees.marketplace_mint.map(|fee| fee as Balance * CAPS)
+	}
+}
@@ -0,0 +1,295 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Development service where BABE and GRANDPA are replaced by a manual sealing engine.
+//!
+//! Blocks still carry a BABE pre-digest so that the runtime (which expects BABE slots) keeps
+//! working, but their production is driven by the transaction pool, a timer or by the
+//! `engine_createBlock` and `engine_finalizeBlock` RPC calls.
+
+use futures::{channel::mpsc, stream, StreamExt};
+use sc_consensus_manual_seal::{
+	consensus::{babe::BabeConsensusDataProvider, timestamp::SlotTimestampProvider},
+	EngineCommand, ManualSealParams,
+};
+use sc_executor::{NativeElseWasmExecutor, NativeExecutionDispatch};
+use sc_service::{config::Configuration, error::Error as ServiceError, TaskManager};
+use sc_telemetry::{Telemetry, TelemetryWorker};
+use sc_transaction_pool_api::TransactionPool as _;
+use sp_api::ConstructRuntimeApi;
+use std::{str::FromStr, sync::Arc, time::Duration};
+use ternoa_client::RuntimeApiCollection;
+use ternoa_core_primitives::{Block, Hash};
+
+use crate::{rpc, FullBackend, FullClient};
+
+/// Block authoring scheme used by the development service.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sealing {
+	/// Seal a block as soon as a transaction enters the pool.
+	Instant,
+	/// Seal blocks only when asked to through the `engine_createBlock` RPC.
+	Manual,
+	/// Seal a block every given number of milliseconds.
+	Interval(u64),
+}
+
+impl FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"instant" => Self::Instant,
+			"manual" => Self::Manual,
+			s => {
+				let millis = s.parse::<u64>().map_err(|_| {
+					format!("Invalid sealing `{}`. Expected `instant`, `manual` or a number of milliseconds", s)
+				})?;
+				if millis == 0 {
+					return Err("The sealing interval must be greater than 0 milliseconds".into())
+				}
+				Self::Interval(millis)
+			},
+		})
+	}
+}
+
+/// Builds a new development service sealing blocks with the given [`Sealing`] scheme.
+///
+/// The `engine_createBlock` and `engine_finalizeBlock` RPC calls are available regardless of the
+/// chosen scheme.
+pub fn new_dev<RuntimeApi, ExecutorDispatch>(
+	config: Configuration,
+	sealing: Sealing,
+) -> Result<TaskManager, ServiceError>
+where
+	RuntimeApi: ConstructRuntimeApi<Block, FullClient<RuntimeApi, ExecutorDispatch>>
+		+ Send
+		+ Sync
+		+ 'static,
+	RuntimeApi::RuntimeApi:
+		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
+	ExecutorDispatch: NativeExecutionDispatch + 'static,
+{
+	let telemetry = config
+		.telemetry_endpoints
+		.clone()
+		.filter(|x| !x.is_empty())
+		.map(|endpoints| -> Result<_, sc_telemetry::Error> {
+			let worker = TelemetryWorker::new(16)?;
+			let telemetry = worker.handle().new_telemetry(endpoints);
+			Ok((worker, telemetry))
+		})
+		.transpose()?;
+
+	let executor = NativeElseWasmExecutor::<ExecutorDispatch>::new(
+		config.wasm_method,
+		config.default_heap_pages,
+		config.max_runtime_instances,
+		config.runtime_cache_size,
+	);
+
+	let (client, backend, keystore_container, mut task_manager) =
+		sc_service::new_full_parts::<Block, RuntimeApi, _>(
+			&config,
+			telemetry.as_ref().map(|(_, telemetry)| telemetry.handle()),
+			executor,
+		)?;
+	let client = Arc::new(client);
+
+	let mut telemetry: Option<Telemetry> = telemetry.map(|(worker, telemetry)| {
+		task_manager.spawn_handle().spawn("telemetry", None, worker.run());
+		telemetry
+	});
+
+	let select_chain = sc_consensus::LongestChain::new(backend.clone());
+
+	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
+		config.transaction_pool.clone(),
+		config.role.is_authority().into(),
+		config.prometheus_registry(),
+		task_manager.spawn_essential_handle(),
+		client.clone(),
+	);
+
+	// There is no GRANDPA here, BABE only wraps the client so that epoch changes are still
+	// tracked. The sealed blocks are imported directly, the import queue is only used for blocks
+	// coming from the network.
+	let babe_config = sc_consensus_babe::configuration(&*client)?;
+	let (block_import, babe_link) =
+		sc_consensus_babe::block_import(babe_config.clone(), client.clone(), client.clone())?;
+
+	let import_queue = sc_consensus_manual_seal::import_queue(
+		Box::new(block_import.clone()),
+		&task_manager.spawn_essential_handle(),
+		config.prometheus_registry(),
+	);
+
+	let (network, system_rpc_tx, tx_handler_controller, network_starter) =
+		sc_service::build_network(sc_service::BuildNetworkParams {
+			config: &config,
+			client: client.clone(),
+			transaction_pool: transaction_pool.clone(),
+			spawn_handle: task_manager.spawn_handle(),
+			import_queue,
+			block_announce_validator_builder: None,
+			warp_sync: None,
+		})?;
+
+	if config.offchain_worker.enabled {
+		sc_service::build_offchain_workers(
+			&config,
+			task_manager.spawn_handle(),
+			client.clone(),
+			network.clone(),
+		);
+	}
+
+	// Commands coming from the `engine_*` RPC calls.
+	let (command_sink, commands_stream) = mpsc::channel::<EngineCommand<Hash>>(1024);
+
+	let rpc_extensions_builder = {
+		let client = client.clone();
+		let pool = transaction_pool.clone();
+		let select_chain = select_chain.clone();
+		let chain_spec = config.chain_spec.cloned_box();
+		let keystore = keystore_container.sync_keystore();
+		let babe_config = babe_link.config().clone();
+		let shared_epoch_changes = babe_link.epoch_changes().clone();
+		let rpc_backend = backend.clone();
+
+		move |deny_unsafe, subscription_executor| -> Result<rpc::RpcExtension, sc_service::Error> {
+			let deps = rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				select_chain: select_chain.clone(),
+				chain_spec: chain_spec.cloned_box(),
+				deny_unsafe,
+				subscription_executor,
+				babe: rpc::BabeDeps {
+					babe_config: babe_config.clone(),
+					shared_epoch_changes: shared_epoch_changes.clone(),
+					keystore: keystore.clone(),
+				},
+				grandpa: None,
+				command_sink: Some(command_sink.clone()),
+			};
+
+			rpc::create_full(deps, rpc_backend.clone()).map_err(Into::into)
+		}
+	};
+
+	let prometheus_registry = config.prometheus_registry().cloned();
+
+	sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+		config,
+		backend,
+		client: client.clone(),
+		keystore: keystore_container.sync_keystore(),
+		network,
+		rpc_builder: Box::new(rpc_extensions_builder),
+		transaction_pool: transaction_pool.clone(),
+		task_manager: &mut task_manager,
+		system_rpc_tx,
+		tx_handler_controller,
+		telemetry: telemetry.as_mut(),
+	})?;
+
+	let proposer = sc_basic_authorship::ProposerFactory::new(
+		task_manager.spawn_handle(),
+		client.clone(),
+		transaction_pool.clone(),
+		prometheus_registry.as_ref(),
+		telemetry.as_ref().map(|x| x.handle()),
+	);
+
+	// Adds the BABE pre-digest expected by the runtime to every sealed block.
+	let consensus_data_provider = BabeConsensusDataProvider::new(
+		client.clone(),
+		keystore_container.sync_keystore(),
+		babe_link.epoch_changes().clone(),
+		babe_config.authorities.clone(),
+	)
+	.map_err(|e| ServiceError::Other(format!("Failed to create the BABE data provider: {}", e)))?;
+
+	// The RPC commands are always accepted, the sealing scheme only adds its own triggers.
+	let commands_stream: stream::BoxStream<'static, EngineCommand<Hash>> = match sealing {
+		Sealing::Manual => commands_stream.boxed(),
+		Sealing::Instant => {
+			let pool_stream = transaction_pool.import_notification_stream().map(|_| {
+				EngineCommand::SealNewBlock {
+					create_empty: false,
+					finalize: true,
+					parent_hash: None,
+					sender: None,
+				}
+			});
+			stream::select(commands_stream, pool_stream).boxed()
+		},
+		Sealing::Interval(millis) => {
+			let timer_stream = stream::unfold((), move |()| async move {
+				futures_timer::Delay::new(Duration::from_millis(millis)).await;
+				Some((
+					EngineCommand::SealNewBlock {
+						create_empty: true,
+						finalize: true,
+						parent_hash: None,
+						sender: None,
+					},
+					(),
+				))
+			});
+			stream::select(commands_stream, timer_stream).boxed()
+		},
+	};
+
+	let client_clone = client.clone();
+	let authorship_future = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+		block_import,
+		env: proposer,
+		client: client.clone(),
+		pool: transaction_pool,
+		commands_stream,
+		select_chain,
+		consensus_data_provider: Some(Box::new(consensus_data_provider)),
+		create_inherent_data_providers: move |parent, ()| {
+			let client_clone = client_clone.clone();
+			async move {
+				let storage_proof = sp_transaction_storage_proof::registration::new_data_provider(
+					&*client_clone,
+					&parent,
+				)?;
+
+				let timestamp = SlotTimestampProvider::new_babe(client_clone)
+					.map_err(|e| format!("{:?}", e))?;
+
+				let slot =
+					sp_consensus_babe::inherents::InherentDataProvider::new(timestamp.slot());
+
+				Ok((slot, timestamp, storage_proof))
+			}
+		},
+	});
+
+	task_manager.spawn_essential_handle().spawn_blocking(
+		"manual-seal",
+		Some("block-authoring"),
+		authorship_future,
+	);
+
+	network_starter.start_network();
+	Ok(task_manager)
+}
@@ -0,0 +1,760 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! SQLite database of the indexer.
+//!
+//! Every row is tied to the block of its event, so that the blocks removed from the chain can be
+//! removed from the database along with everything indexed from them.
+
+use rusqlite::{params, Connection, OptionalExtension, Row, Transaction};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+	path::Path,
+	sync::{Arc, Mutex, MutexGuard},
+};
+use ternoa_core_primitives::{BlockNumber, Hash};
+use ternoa_runtime_apis::{marketplace::MarketplaceId, nft::NFTId};
+
+use super::decode::DecodedEvent;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS meta (
+	key TEXT PRIMARY KEY,
+	value BLOB NOT NULL
+);
+CREATE TABLE IF NOT EXISTS blocks (
+	number INTEGER PRIMARY KEY,
+	hash BLOB NOT NULL,
+	timestamp INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS events (
+	block_number INTEGER NOT NULL,
+	event_index INTEGER NOT NULL,
+	pallet TEXT NOT NULL,
+	name TEXT NOT NULL,
+	nft_id INTEGER,
+	marketplace_id INTEGER,
+	fields TEXT NOT NULL,
+	PRIMARY KEY (block_number, event_index)
+);
+CREATE INDEX IF NOT EXISTS events_by_nft ON events (nft_id, block_number);
+CREATE INDEX IF NOT EXISTS events_by_pallet ON events (pallet, name, block_number);
+CREATE TABLE IF NOT EXISTS nft_owners (
+	nft_id INTEGER NOT NULL,
+	block_number INTEGER NOT NULL,
+	event_index INTEGER NOT NULL,
+	owner TEXT,
+	PRIMARY KEY (nft_id, block_number, event_index)
+);
+CREATE TABLE IF NOT EXISTS sales (
+	block_number INTEGER NOT NULL,
+	event_index INTEGER NOT NULL,
+	timestamp INTEGER NOT NULL,
+	nft_id INTEGER NOT NULL,
+	marketplace_id INTEGER NOT NULL,
+	seller TEXT,
+	buyer TEXT NOT NULL,
+	price TEXT NOT NULL,
+	PRIMARY KEY (block_number, event_index)
+);
+CREATE INDEX IF NOT EXISTS sales_by_marketplace ON sales (marketplace_id, timestamp);
+";
+
+/// Tables holding rows tied to a block.
+const BLOCK_TABLES: [&str; 4] = ["blocks", "events", "nft_owners", "sales"];
+
+/// Key of the genesis hash of the indexed chain in the `meta` table.
+const GENESIS_HASH_KEY: &str = "genesis_hash";
+/// Key of the last [`IndexingError`] in the `meta` table, removed once a block is indexed.
+const LAST_ERROR_KEY: &str = "last_error";
+
+/// An indexed block.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedBlock {
+	pub number: BlockNumber,
+	pub hash: Hash,
+	/// Timestamp of the block, in milliseconds.
+	pub timestamp: u64,
+}
+
+/// Error which stopped the indexing, until it is retried.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexingError {
+	/// Block which could not be indexed, `None` when the error is not tied to a block.
+	pub block_number: Option<BlockNumber>,
+	pub message: String,
+	/// Number of failed attempts in a row.
+	pub attempts: u32,
+}
+
+/// Progress of the indexer.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexerStatus {
+	/// Last indexed block, `None` until the first block is indexed.
+	pub last_block: Option<IndexedBlock>,
+	/// Error of the last attempt, `None` when it succeeded.
+	pub last_error: Option<IndexingError>,
+}
+
+/// An event of one of the indexed pallets.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedEvent {
+	pub block_number: BlockNumber,
+	pub event_index: u32,
+	pub timestamp: u64,
+	pub pallet: String,
+	pub name: String,
+	pub fields: Value,
+}
+
+/// A change of owner of an NFT.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ownership {
+	pub block_number: BlockNumber,
+	pub timestamp: u64,
+	/// The new owner, `None` when the NFT was burnt.
+	pub owner: Option<String>,
+}
+
+/// A sale of an NFT, through a listing or an auction.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sale {
+	pub block_number: BlockNumber,
+	pub event_index: u32,
+	pub timestamp: u64,
+	pub nft_id: NFTId,
+	pub marketplace_id: MarketplaceId,
+	pub seller: Option<String>,
+	pub buyer: String,
+	/// Price paid by the buyer, fees included.
+	pub price: String,
+}
+
+/// Handle to the database of the indexer, shared by the indexer and the RPC methods.
+#[derive(Clone)]
+pub struct Database {
+	connection: Arc<Mutex<Connection>>,
+}
+
+impl Database {
+	/// Opens the database, creating it if needed.
+	pub fn open(path: &Path) -> rusqlite::Result<Self> {
+		let connection = Connection::open(path)?;
+		connection.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))?;
+		connection.execute_batch(SCHEMA)?;
+
+		Ok(Self { connection: Arc::new(Mutex::new(connection)) })
+	}
+
+	fn connection(&self) -> MutexGuard<Connection> {
+		// The connection stays usable even if a thread panicked while holding it.
+		self.connection.lock().unwrap_or_else(|e| e.into_inner())
+	}
+
+	/// Hash of the genesis block of the indexed chain, if any block was indexed.
+	pub fn genesis_hash(&self) -> rusqlite::Result<Option<Hash>> {
+		self.connection()
+			.query_row("SELECT value FROM meta WHERE key = ?1", [GENESIS_HASH_KEY], |row| {
+				row.get::<_, Vec<u8>>(0)
+			})
+			.optional()
+			.map(|hash| hash.map(|hash| Hash::from_slice(&hash)))
+	}
+
+	/// Records the hash of the genesis block of the indexed chain.
+	pub fn set_genesis_hash(&self, hash: Hash) -> rusqlite::Result<()> {
+		self.connection()
+			.execute(
+				"INSERT OR REPLACE INTO meta (key, value) VALUES (?1, ?2)",
+				params![GENESIS_HASH_KEY, hash.as_bytes()],
+			)
+			.map(|_| ())
+	}
+
+	/// Error of the last indexing attempt, if it failed.
+	pub fn last_error(&self) -> rusqlite::Result<Option<IndexingError>> {
+		let error = self
+			.connection()
+			.query_row("SELECT value FROM meta WHERE key = ?1", [LAST_ERROR_KEY], |row| {
+				row.get::<_, Vec<u8>>(0)
+			})
+			.optional()?;
+		error
+			.map(|error| {
+				serde_json::from_slice(&error).map_err(|e| {
+					rusqlite::Error::FromSqlConversionFailure(
+						0,
+						rusqlite::types::Type::Blob,
+						Box::new(e),
+					)
+				})
+			})
+			.transpose()
+	}
+
+	/// Records the error of an indexing attempt, until a block is indexed.
+	pub fn set_last_error(&self, error: &IndexingError) -> rusqlite::Result<()> {
+		let error = serde_json::to_vec(error)
+			.map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+		self.connection()
+			.execute(
+				"INSERT OR REPLACE INTO meta (key, value) VALUES (?1, ?2)",
+				params![LAST_ERROR_KEY, error],
+			)
+			.map(|_| ())
+	}
+
+	/// Last indexed block and last error.
+	pub fn status(&self) -> rusqlite::Result<IndexerStatus> {
+		Ok(IndexerStatus { last_block: self.last_block()?, last_error: self.last_error()? })
+	}
+
+	/// Last indexed block.
+	pub fn last_block(&self) -> rusqlite::Result<Option<IndexedBlock>> {
+		self.connection()
+			.query_row(
+				"SELECT number, hash, timestamp FROM blocks ORDER BY number DESC LIMIT 1",
+				[],
+				|row| {
+					Ok(IndexedBlock {
+						number: row.get(0)?,
+						hash: Hash::from_slice(&row.get::<_, Vec<u8>>(1)?),
+						timestamp: row.get(2)?,
+					})
+				},
+			)
+			.optional()
+	}
+
+	/// Hash of an indexed block.
+	pub fn block_hash(&self, number: BlockNumber) -> rusqlite::Result<Option<Hash>> {
+		self.connection()
+			.query_row("SELECT hash FROM blocks WHERE number = ?1", [number], |row| {
+				row.get::<_, Vec<u8>>(0)
+			})
+			.optional()
+			.map(|hash| hash.map(|hash| Hash::from_slice(&hash)))
+	}
+
+	/// Removes everything indexed from the blocks after `number`.
+	pub fn revert_to(&self, number: BlockNumber) -> rusqlite::Result<()> {
+		let mut connection = self.connection();
+		let tx = connection.transaction()?;
+		for table in BLOCK_TABLES {
+			let column = if table == "blocks" { "number" } else { "block_number" };
+			tx.execute(&format!("DELETE FROM {} WHERE {} > ?1", table, column), [number])?;
+		}
+		tx.commit()
+	}
+
+	/// Indexes a block and its events, all at once, and clears the last error.
+	pub fn index_block(
+		&self,
+		block: &IndexedBlock,
+		events: &[(u32, DecodedEvent)],
+	) -> rusqlite::Result<()> {
+		let mut connection = self.connection();
+		let tx = connection.transaction()?;
+		tx.execute(
+			"INSERT INTO blocks (number, hash, timestamp) VALUES (?1, ?2, ?3)",
+			params![block.number, block.hash.as_bytes(), block.timestamp],
+		)?;
+		for (index, event) in events {
+			index_event(&tx, block, *index, event)?;
+		}
+		tx.execute("DELETE FROM meta WHERE key = ?1", [LAST_ERROR_KEY])?;
+		tx.commit()
+	}
+
+	/// Owners of an NFT, from its creation.
+	pub fn nft_owners(&self, nft_id: NFTId) -> rusqlite::Result<Vec<Ownership>> {
+		let connection = self.connection();
+		let mut statement = connection.prepare(
+			"SELECT o.block_number, b.timestamp, o.owner FROM nft_owners o
+			JOIN blocks b ON b.number = o.block_number
+			WHERE o.nft_id = ?1 ORDER BY o.block_number, o.event_index",
+		)?;
+		let owners = statement
+			.query_map([nft_id], |row| {
+				Ok(Ownership {
+					block_number: row.get(0)?,
+					timestamp: row.get(1)?,
+					owner: row.get(2)?,
+				})
+			})?
+			.collect();
+		owners
+	}
+
+	/// Events concerning an NFT, after the given `(block number, event index)`.
+	pub fn nft_events(
+		&self,
+		nft_id: NFTId,
+		start_after: Option<(BlockNumber, u32)>,
+		limit: u32,
+	) -> rusqlite::Result<Vec<IndexedEvent>> {
+		let (block_number, event_index) =
+			(start_after.map(|(number, _)| number), start_after.map(|(_, index)| index));
+		let connection = self.connection();
+		let mut statement = connection.prepare(
+			"SELECT e.block_number, e.event_index, b.timestamp, e.pallet, e.name, e.fields
+			FROM events e JOIN blocks b ON b.number = e.block_number
+			WHERE e.nft_id = ?1 AND (?2 IS NULL OR (e.block_number, e.event_index) > (?2, ?3))
+			ORDER BY e.block_number, e.event_index LIMIT ?4",
+		)?;
+		let events = statement
+			.query_map(params![nft_id, block_number, event_index, limit], event_from_row)?
+			.collect();
+		events
+	}
+
+	/// Events of a pallet, optionally of a single kind, after the given
+	/// `(block number, event index)`.
+	pub fn events(
+		&self,
+		pallet: &str,
+		name: Option<&str>,
+		start_after: Option<(BlockNumber, u32)>,
+		limit: u32,
+	) -> rusqlite::Result<Vec<IndexedEvent>> {
+		let (block_number, event_index) =
+			(start_after.map(|(number, _)| number), start_after.map(|(_, index)| index));
+		let connection = self.connection();
+		let mut statement = connection.prepare(
+			"SELECT e.block_number, e.event_index, b.timestamp, e.pallet, e.name, e.fields
+			FROM events e JOIN blocks b ON b.number = e.block_number
+			WHERE e.pallet = ?1 AND (?2 IS NULL OR e.name = ?2)
+				AND (?3 IS NULL OR (e.block_number, e.event_index) > (?3, ?4))
+			ORDER BY e.block_number, e.event_index LIMIT ?5",
+		)?;
+		let events = statement
+			.query_map(params![pallet, name, block_number, event_index, limit], event_from_row)?
+			.collect();
+		events
+	}
+
+	/// Sales of a marketplace between two timestamps, in milliseconds, `to` being excluded, after
+	/// the given `(block number, event index)`.
+	pub fn marketplace_sales(
+		&self,
+		marketplace_id: MarketplaceId,
+		from: Option<u64>,
+		to: Option<u64>,
+		start_after: Option<(BlockNumber, u32)>,
+		limit: u32,
+	) -> rusqlite::Result<Vec<Sale>> {
+		let (block_number, event_index) =
+			(start_after.map(|(number, _)| number), start_after.map(|(_, index)| index));
+		let connection = self.connection();
+		let mut statement = connection.prepare(
+			"SELECT block_number, event_index, timestamp, nft_id, marketplace_id, seller, buyer,
+			price FROM sales
+			WHERE marketplace_id = ?1 AND (?2 IS NULL OR timestamp >= ?2)
+				AND (?3 IS NULL OR timestamp < ?3)
+				AND (?4 IS NULL OR (block_number, event_index) > (?4, ?5))
+			ORDER BY block_number, event_index LIMIT ?6",
+		)?;
+		let sales = statement
+			.query_map(
+				params![marketplace_id, from, to, block_number, event_index, limit],
+				|row| {
+					Ok(Sale {
+						block_number: row.get(0)?,
+						event_index: row.get(1)?,
+						timestamp: row.get(2)?,
+						nft_id: row.get(3)?,
+						marketplace_id: row.get(4)?,
+						seller: row.get(5)?,
+						buyer: row.get(6)?,
+						price: row.get(7)?,
+					})
+				},
+			)?
+			.collect();
+		sales
+	}
+}
+
+fn event_from_row(row: &Row) -> rusqlite::Result<IndexedEvent> {
+	let fields: String = row.get(5)?;
+	Ok(IndexedEvent {
+		block_number: row.get(0)?,
+		event_index: row.get(1)?,
+		timestamp: row.get(2)?,
+		pallet: row.get(3)?,
+		name: row.get(4)?,
+		fields: serde_json::from_str(&fields).map_err(|e| {
+			rusqlite::Error::FromSqlConversionFailure(5, rusqlite::types::Type::Text, Box::new(e))
+		})?,
+	})
+}
+
+fn u32_field(fields: &Value, name: &str) -> Option<u32> {
+	fields.get(name)?.as_u64()?.try_into().ok()
+}
+
+fn str_field<'a>(fields: &'a Value, name: &str) -> Option<&'a str> {
+	fields.get(name)?.as_str()
+}
+
+/// Stores an event, and the ownership changes and sales it stands for.
+fn index_event(
+	tx: &Transaction,
+	block: &IndexedBlock,
+	index: u32,
+	event: &DecodedEvent,
+) -> rusqlite::Result<()> {
+	let fields = &event.fields;
+	let nft_id = u32_field(fields, "nft_id");
+	let marketplace_id = u32_field(fields, "marketplace_id");
+
+	tx.execute(
+		"INSERT INTO events
+		(block_number, event_index, pallet, name, nft_id, marketplace_id, fields)
+		VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+		params![
+			block.number,
+			index,
+			event.pallet,
+			event.name,
+			nft_id,
+			marketplace_id,
+			fields.to_string()
+		],
+	)?;
+
+	let nft_id = match nft_id {
+		Some(nft_id) => nft_id,
+		None => return Ok(()),
+	};
+	let set_owner = |owner: Option<&str>| {
+		tx.execute(
+			"INSERT INTO nft_owners (nft_id, block_number, event_index, owner)
+			VALUES (?1, ?2, ?3, ?4)",
+			params![nft_id, block.number, index, owner],
+		)
+		.map(|_| ())
+	};
+	let add_sale =
+		|marketplace_id: MarketplaceId, seller: Option<String>, buyer: &str, price: &str| {
+			tx.execute(
+				"INSERT INTO sales (block_number, event_index, timestamp, nft_id, marketplace_id,
+				seller, buyer, price) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+				params![
+					block.number,
+					index,
+					block.timestamp,
+					nft_id,
+					marketplace_id,
+					seller,
+					buyer,
+					price
+				],
+			)
+			.map(|_| ())
+		};
+
+	match (event.pallet.as_str(), event.name.as_str()) {
+		("NFT", "NFTCreated") => set_owner(str_field(fields, "owner"))?,
+		("NFT", "NFTTransferred") => set_owner(str_field(fields, "recipient"))?,
+		("NFT", "NFTBurned") => set_owner(None)?,
+		("Marketplace", "NFTSold") => {
+			let buyer = str_field(fields, "buyer");
+			let price = str_field(fields, "listed_price");
+			if let (Some(marketplace_id), Some(buyer), Some(price)) = (marketplace_id, buyer, price)
+			{
+				// The NFT is sold by the account that listed it, its owner.
+				let seller = tx
+					.query_row(
+						"SELECT owner FROM nft_owners WHERE nft_id = ?1
+						ORDER BY block_number DESC, event_index DESC LIMIT 1",
+						[nft_id],
+						|row| row.get(0),
+					)
+					.optional()?
+					.flatten();
+				add_sale(marketplace_id, seller, buyer, price)?;
+				set_owner(Some(buyer))?;
+			}
+		},
+		("Auction", "AuctionCompleted") => {
+			let buyer = str_field(fields, "new_owner");
+			let price = str_field(fields, "paid_amount");
+			// Auctions ending without bids leave the NFT to its owner.
+			if let (Some(buyer), Some(price)) = (buyer, price) {
+				let auction = tx
+					.query_row(
+						"SELECT marketplace_id, fields FROM events
+						WHERE nft_id = ?1 AND pallet = 'Auction' AND name = 'AuctionCreated'
+						ORDER BY block_number DESC, event_index DESC LIMIT 1",
+						[nft_id],
+						|row| {
+							Ok((row.get::<_, Option<MarketplaceId>>(0)?, row.get::<_, String>(1)?))
+						},
+					)
+					.optional()?;
+				if let Some((Some(marketplace_id), auction)) = auction {
+					let seller = serde_json::from_str::<Value>(&auction)
+						.ok()
+						.and_then(|auction| str_field(&auction, "creator").map(Into::into));
+					add_sale(marketplace_id, seller, buyer, price)?;
+				}
+				set_owner(Some(buyer))?;
+			}
+		},
+		_ => (),
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	fn database() -> Database {
+		Database::open(Path::new(":memory:")).unwrap()
+	}
+
+	fn block(number: BlockNumber) -> IndexedBlock {
+		IndexedBlock {
+			number,
+			hash: Hash::repeat_byte(number as u8),
+			timestamp: number as u64 * 6_000,
+		}
+	}
+
+	fn event(pallet: &str, name: &str, fields: Value) -> (u32, DecodedEvent) {
+		(0, DecodedEvent { pallet: pallet.into(), name: name.into(), fields })
+	}
+
+	/// Blocks in which NFT 0 is created by alice, given to bob, sold to charlie on marketplace 1,
+	/// sold by auction to dave on marketplace 2 and burnt.
+	fn nft_life() -> Vec<(IndexedBlock, (u32, DecodedEvent))> {
+		vec![
+			(block(1), event("NFT", "NFTCreated", json!({ "nft_id": 0, "owner": "alice" }))),
+			(
+				block(2),
+				event(
+					"NFT",
+					"NFTTransferred",
+					json!({ "nft_id": 0, "sender": "alice", "recipient": "bob" }),
+				),
+			),
+			(
+				block(3),
+				event(
+					"Marketplace",
+					"NFTSold",
+					json!({
+						"nft_id": 0,
+						"marketplace_id": 1,
+						"buyer": "charlie",
+						"listed_price": "100",
+					}),
+				),
+			),
+			(
+				block(4),
+				event(
+					"Auction",
+					"AuctionCreated",
+					json!({ "nft_id": 0, "marketplace_id": 2, "creator": "charlie" }),
+				),
+			),
+			(
+				block(5),
+				event(
+					"Auction",
+					"AuctionCompleted",
+					json!({ "nft_id": 0, "new_owner": "dave", "paid_amount": "150" }),
+				),
+			),
+			(block(6), event("NFT", "NFTBurned", json!({ "nft_id": 0 }))),
+		]
+	}
+
+	fn index(database: &Database, blocks: &[(IndexedBlock, (u32, DecodedEvent))]) {
+		for (block, event) in blocks {
+			database.index_block(block, &[event.clone()]).unwrap();
+		}
+	}
+
+	fn owners(database: &Database) -> Vec<(BlockNumber, Option<String>)> {
+		let owners = database.nft_owners(0).unwrap();
+		owners
+			.into_iter()
+			.map(|ownership| (ownership.block_number, ownership.owner))
+			.collect()
+	}
+
+	fn sales(
+		database: &Database,
+		marketplace_id: MarketplaceId,
+	) -> Vec<(BlockNumber, Option<String>, String, String)> {
+		let sales = database.marketplace_sales(marketplace_id, None, None, None, 10).unwrap();
+		sales
+			.into_iter()
+			.map(|sale| (sale.block_number, sale.seller, sale.buyer, sale.price))
+			.collect()
+	}
+
+	fn some(account: &str) -> Option<String> {
+		Some(account.into())
+	}
+
+	#[test]
+	fn owners_follow_the_events() {
+		let database = database();
+		index(&database, &nft_life());
+
+		assert_eq!(
+			owners(&database),
+			vec![
+				(1, some("alice")),
+				(2, some("bob")),
+				(3, some("charlie")),
+				(5, some("dave")),
+				(6, None)
+			]
+		);
+	}
+
+	#[test]
+	fn sales_are_recorded_with_their_seller() {
+		let database = database();
+		index(&database, &nft_life());
+
+		assert_eq!(sales(&database, 1), vec![(3, some("bob"), "charlie".into(), "100".into())]);
+		assert_eq!(sales(&database, 2), vec![(5, some("charlie"), "dave".into(), "150".into())]);
+		assert_eq!(
+			database.marketplace_sales(1, None, None, None, 10).unwrap()[0].timestamp,
+			18_000
+		);
+	}
+
+	#[test]
+	fn auctions_without_bids_leave_the_owner() {
+		let database = database();
+		let mut blocks = nft_life();
+		blocks.truncate(4);
+		blocks.push((
+			block(5),
+			event(
+				"Auction",
+				"AuctionCompleted",
+				json!({ "nft_id": 0, "new_owner": null, "paid_amount": null }),
+			),
+		));
+		index(&database, &blocks);
+
+		assert_eq!(owners(&database).last(), Some(&(3, some("charlie"))));
+		assert!(sales(&database, 2).is_empty());
+	}
+
+	#[test]
+	fn revert_to_removes_the_later_blocks() {
+		let database = database();
+		let blocks = nft_life();
+		index(&database, &blocks);
+
+		database.revert_to(2).unwrap();
+		assert_eq!(database.last_block().unwrap().map(|block| block.number), Some(2));
+		assert_eq!(database.block_hash(3).unwrap(), None);
+		assert_eq!(owners(&database), vec![(1, some("alice")), (2, some("bob"))]);
+		assert!(sales(&database, 1).is_empty());
+		assert_eq!(database.nft_events(0, None, 10).unwrap().len(), 2);
+
+		// The removed blocks can be indexed again.
+		index(&database, &blocks[2..]);
+		assert_eq!(owners(&database).len(), 5);
+		assert_eq!(sales(&database, 1).len(), 1);
+	}
+
+	#[test]
+	fn last_error_is_cleared_by_the_next_block() {
+		let database = database();
+		let error = IndexingError { block_number: Some(1), message: "failed".into(), attempts: 2 };
+		database.set_last_error(&error).unwrap();
+		assert_eq!(database.status().unwrap().last_error, Some(error));
+
+		index(&database, &nft_life()[..1]);
+		let status = database.status().unwrap();
+		assert_eq!(status.last_block.map(|block| block.number), Some(1));
+		assert_eq!(status.last_error, None);
+	}
+
+	/// The fields read by [`index_event`] are the ones of the events of the runtime.
+	#[cfg(feature = "mainnet-native")]
+	#[test]
+	fn indexed_fields_are_in_the_runtime_metadata() {
+		use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+		use parity_scale_codec::{Decode, Encode};
+		use scale_info::TypeDef;
+
+		const INDEXED_FIELDS: [(&str, &str, &[&str]); 6] = [
+			("NFT", "NFTCreated", &["nft_id", "owner"]),
+			("NFT", "NFTTransferred", &["nft_id", "recipient"]),
+			("NFT", "NFTBurned", &["nft_id"]),
+			("Marketplace", "NFTSold", &["nft_id", "marketplace_id", "buyer", "listed_price"]),
+			("Auction", "AuctionCreated", &["nft_id", "marketplace_id", "creator"]),
+			("Auction", "AuctionCompleted", &["nft_id", "new_owner", "paid_amount"]),
+		];
+
+		let metadata = mainnet_runtime::Runtime::metadata().encode();
+		let metadata = match RuntimeMetadataPrefixed::decode(&mut &metadata[..]).unwrap().1 {
+			RuntimeMetadata::V14(metadata) => metadata,
+			_ => panic!("The runtime metadata is not V14"),
+		};
+
+		for (pallet, event, fields) in INDEXED_FIELDS {
+			let events = metadata
+				.pallets
+				.iter()
+				.find(|p| p.name == pallet)
+				.and_then(|p| p.event.as_ref())
+				.and_then(|events| metadata.types.resolve(events.ty.id()))
+				.unwrap_or_else(|| panic!("{} has no events", pallet));
+			let variant = match events.type_def() {
+				TypeDef::Variant(variants) =>
+					variants.variants().iter().find(|v| v.name() == event),
+				_ => None,
+			}
+			.unwrap_or_else(|| panic!("{} has no {} event", pallet, event));
+
+			for field in fields {
+				assert!(
+					variant
+						.fields()
+						.iter()
+						.any(|f| f.name().map(|name| name.as_str()) == Some(*field)),
+					"{}::{} has no `{}` field",
+					pallet,
+					event,
+					field,
+				);
+			}
+		}
+	}
+}
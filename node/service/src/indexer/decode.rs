@@ -0,0 +1,309 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Decoding of the events of a block following the types of the runtime metadata.
+//!
+//! Events are decoded with the metadata of the runtime of their block, the indexer doesn't
+//! depend on the native runtimes and keeps working across runtime upgrades changing the events.
+
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed, StorageEntryType};
+use parity_scale_codec::{Compact, Decode};
+use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde_json::{Map, Value};
+use sp_core::{
+	bytes,
+	crypto::{AccountId32, Ss58Codec},
+};
+
+/// An event of one of the indexed pallets.
+#[derive(Debug, Clone)]
+pub struct DecodedEvent {
+	/// Name of the pallet in the runtime.
+	pub pallet: String,
+	/// Name of the event.
+	pub name: String,
+	/// Fields of the event, by name.
+	pub fields: Value,
+}
+
+/// Decodes the `System::Events` storage value of the blocks of a runtime version.
+///
+/// Values are turned into JSON as follows: structs into objects or arrays, enums into
+/// `{ "Variant": value }` or `"Variant"` when the variant holds nothing, options into the value
+/// or `null`, accounts into SS58 addresses, bytes into hex strings and 128-bit numbers into
+/// decimal strings.
+pub struct EventDecoder {
+	types: PortableRegistry,
+	events_ty: u32,
+}
+
+impl EventDecoder {
+	/// Creates a decoder from the SCALE encoded metadata of a runtime.
+	pub fn new(metadata: &[u8]) -> Result<Self, String> {
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+			.map_err(|e| format!("Invalid metadata: {}", e))?;
+		let metadata = match metadata.1 {
+			RuntimeMetadata::V14(metadata) => metadata,
+			_ => return Err("Only the V14 metadata is supported".into()),
+		};
+
+		let events_ty = metadata
+			.pallets
+			.iter()
+			.find(|pallet| pallet.name == "System")
+			.and_then(|pallet| pallet.storage.as_ref())
+			.and_then(|storage| storage.entries.iter().find(|entry| entry.name == "Events"))
+			.and_then(|entry| match &entry.ty {
+				StorageEntryType::Plain(ty) => Some(ty.id()),
+				_ => None,
+			})
+			.ok_or("The metadata has no `System::Events` storage")?;
+
+		Ok(Self { types: metadata.types, events_ty })
+	}
+
+	/// Decodes the events of a block, keeping the ones of the given pallets along with their
+	/// index in the block.
+	pub fn decode_events(
+		&self,
+		mut data: &[u8],
+		pallets: &[&str],
+	) -> Result<Vec<(u32, DecodedEvent)>, String> {
+		let records = match self.decode(self.events_ty, &mut data)? {
+			Value::Array(records) => records,
+			_ => return Err("`System::Events` is not a sequence".into()),
+		};
+
+		Ok(records
+			.into_iter()
+			.enumerate()
+			.filter_map(|(index, mut record)| {
+				let (pallet, event) = single_entry(record["event"].take())?;
+				if !pallets.contains(&pallet.as_str()) {
+					return None
+				}
+
+				let event = match event {
+					Value::String(name) => DecodedEvent { pallet, name, fields: Value::Null },
+					event => {
+						let (name, fields) = single_entry(event)?;
+						DecodedEvent { pallet, name, fields }
+					},
+				};
+				Some((index as u32, event))
+			})
+			.collect())
+	}
+
+	fn decode(&self, ty_id: u32, input: &mut &[u8]) -> Result<Value, String> {
+		let ty = self.types.resolve(ty_id).ok_or_else(|| format!("Unknown type {}", ty_id))?;
+
+		Ok(match ty.type_def() {
+			// Accounts are given in their SS58 form, as by the other RPC methods.
+			TypeDef::Composite(_)
+				if ty.path().segments().last().map(|s| s.as_str()) == Some("AccountId32") =>
+			{
+				let account = <[u8; 32]>::decode(input).map_err(|e| e.to_string())?;
+				AccountId32::from(account).to_ss58check().into()
+			},
+			TypeDef::Composite(composite) => self.decode_fields(composite.fields(), input)?,
+			TypeDef::Variant(variants) => {
+				let index = u8::decode(input).map_err(|e| e.to_string())?;
+				let variant =
+					variants.variants().iter().find(|v| v.index() == index).ok_or_else(|| {
+						format!("Unknown variant {} of {}", index, ty.path().segments().join("::"))
+					})?;
+				let fields = self.decode_fields(variant.fields(), input)?;
+
+				if ty.path().segments() == ["Option"] {
+					fields
+				} else if variant.fields().is_empty() {
+					Value::String(variant.name().clone())
+				} else {
+					Value::Object(Map::from_iter([(variant.name().clone(), fields)]))
+				}
+			},
+			TypeDef::Sequence(sequence) => {
+				let len = Compact::<u32>::decode(input).map_err(|e| e.to_string())?.0;
+				self.decode_items(sequence.type_param().id(), len as usize, input)?
+			},
+			TypeDef::Array(array) =>
+				self.decode_items(array.type_param().id(), array.len() as usize, input)?,
+			TypeDef::Tuple(tuple) => match tuple.fields() {
+				[] => Value::Null,
+				fields => Value::Array(
+					fields
+						.iter()
+						.map(|ty| self.decode(ty.id(), input))
+						.collect::<Result<_, _>>()?,
+				),
+			},
+			TypeDef::Primitive(primitive) => decode_primitive(primitive, input)?,
+			TypeDef::Compact(compact) => {
+				let value = Compact::<u128>::decode(input).map_err(|e| e.to_string())?.0;
+				match self.types.resolve(compact.type_param().id()).map(|ty| ty.type_def()) {
+					Some(TypeDef::Primitive(TypeDefPrimitive::U128)) => value.to_string().into(),
+					_ => (value as u64).into(),
+				}
+			},
+			TypeDef::BitSequence(_) => return Err("Bit sequences are not supported".into()),
+		})
+	}
+
+	fn decode_fields(
+		&self,
+		fields: &[Field<PortableForm>],
+		input: &mut &[u8],
+	) -> Result<Value, String> {
+		match fields {
+			[] => Ok(Value::Null),
+			[field] if field.name().is_none() => self.decode(field.ty().id(), input),
+			fields if fields.iter().all(|field| field.name().is_some()) => fields
+				.iter()
+				.map(|field| {
+					let name = field.name().cloned().unwrap_or_default();
+					Ok((name, self.decode(field.ty().id(), input)?))
+				})
+				.collect::<Result<Map<_, _>, String>>()
+				.map(Value::Object),
+			fields => fields
+				.iter()
+				.map(|field| self.decode(field.ty().id(), input))
+				.collect::<Result<Vec<_>, _>>()
+				.map(Value::Array),
+		}
+	}
+
+	fn decode_items(&self, ty_id: u32, len: usize, input: &mut &[u8]) -> Result<Value, String> {
+		let is_byte = matches!(
+			self.types.resolve(ty_id).map(|ty| ty.type_def()),
+			Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+		);
+		if is_byte {
+			if input.len() < len {
+				return Err("Not enough data to decode the bytes".into())
+			}
+			let (bytes, rest) = input.split_at(len);
+			*input = rest;
+			return Ok(bytes::to_hex(bytes, false).into())
+		}
+
+		(0..len)
+			.map(|_| self.decode(ty_id, input))
+			.collect::<Result<Vec<_>, _>>()
+			.map(Value::Array)
+	}
+}
+
+fn decode_primitive(primitive: &TypeDefPrimitive, input: &mut &[u8]) -> Result<Value, String> {
+	fn decode<T: Decode>(input: &mut &[u8]) -> Result<T, String> {
+		T::decode(input).map_err(|e| e.to_string())
+	}
+
+	Ok(match primitive {
+		TypeDefPrimitive::Bool => decode::<bool>(input)?.into(),
+		TypeDefPrimitive::Char =>
+			char::from_u32(decode::<u32>(input)?).ok_or("Invalid char")?.to_string().into(),
+		TypeDefPrimitive::Str => decode::<String>(input)?.into(),
+		TypeDefPrimitive::U8 => decode::<u8>(input)?.into(),
+		TypeDefPrimitive::U16 => decode::<u16>(input)?.into(),
+		TypeDefPrimitive::U32 => decode::<u32>(input)?.into(),
+		TypeDefPrimitive::U64 => decode::<u64>(input)?.into(),
+		TypeDefPrimitive::U128 => decode::<u128>(input)?.to_string().into(),
+		TypeDefPrimitive::I8 => decode::<i8>(input)?.into(),
+		TypeDefPrimitive::I16 => decode::<i16>(input)?.into(),
+		TypeDefPrimitive::I32 => decode::<i32>(input)?.into(),
+		TypeDefPrimitive::I64 => decode::<i64>(input)?.into(),
+		TypeDefPrimitive::I128 => decode::<i128>(input)?.to_string().into(),
+		TypeDefPrimitive::U256 | TypeDefPrimitive::I256 =>
+			bytes::to_hex(&decode::<[u8; 32]>(input)?, false).into(),
+	})
+}
+
+/// Splits a `{ "key": value }` object.
+fn single_entry(value: Value) -> Option<(String, Value)> {
+	match value {
+		Value::Object(map) if map.len() == 1 => map.into_iter().next(),
+		_ => None,
+	}
+}
+
+#[cfg(all(test, feature = "mainnet-native"))]
+mod tests {
+	use super::*;
+	use frame_system::{EventRecord, Phase};
+	use mainnet_runtime::{Runtime, RuntimeEvent};
+	use parity_scale_codec::Encode;
+	use serde_json::json;
+	use sp_core::H256;
+
+	fn events(events: Vec<RuntimeEvent>) -> Vec<u8> {
+		events
+			.into_iter()
+			.map(|event| EventRecord::<_, H256> {
+				phase: Phase::ApplyExtrinsic(1),
+				event,
+				topics: vec![],
+			})
+			.collect::<Vec<_>>()
+			.encode()
+	}
+
+	fn block_events() -> Vec<u8> {
+		events(vec![
+			RuntimeEvent::System(frame_system::Event::ExtrinsicSuccess {
+				dispatch_info: Default::default(),
+			}),
+			RuntimeEvent::Balances(pallet_balances::Event::Transfer {
+				from: AccountId32::new([1; 32]),
+				to: AccountId32::new([2; 32]),
+				amount: 5_000_000_000_000_000_000,
+			}),
+		])
+	}
+
+	#[test]
+	fn events_are_decoded_with_the_runtime_metadata() {
+		let decoder = EventDecoder::new(&Runtime::metadata().encode()).unwrap();
+		let events = decoder.decode_events(&block_events(), &["Balances"]).unwrap();
+
+		assert_eq!(events.len(), 1);
+		let (index, event) = &events[0];
+		assert_eq!(*index, 1);
+		assert_eq!((event.pallet.as_str(), event.name.as_str()), ("Balances", "Transfer"));
+		assert_eq!(
+			event.fields,
+			json!({
+				"from": AccountId32::new([1; 32]).to_ss58check(),
+				"to": AccountId32::new([2; 32]).to_ss58check(),
+				"amount": "5000000000000000000",
+			})
+		);
+	}
+
+	#[test]
+	fn events_of_other_pallets_are_skipped() {
+		let decoder = EventDecoder::new(&Runtime::metadata().encode()).unwrap();
+		assert!(decoder.decode_events(&block_events(), &["NFT"]).unwrap().is_empty());
+	}
+
+	#[test]
+	fn truncated_events_are_an_error() {
+		let decoder = EventDecoder::new(&Runtime::metadata().encode()).unwrap();
+		let data = block_events();
+		assert!(decoder.decode_events(&data[..data.len() - 1], &["Balances"]).is_err());
+	}
+}
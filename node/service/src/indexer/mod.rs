@@ -0,0 +1,255 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Event indexer, enabled with `--indexer-db`.
+//!
+//! Follows the finalized blocks and stores the events of the NFT, marketplace, auction, rent and
+//! bridge pallets in a SQLite database, along with the owners of the NFTs and the sales of the
+//! marketplaces, so that their history can be queried through the `indexer_*` RPC methods.
+//!
+//! Each block is indexed in a single transaction and the hashes of the indexed blocks are kept.
+//! When the node starts, or when the indexed blocks are no longer part of the finalized chain
+//! (after a `revert` for instance), the blocks that are not part of the chain are removed from
+//! the database before indexing resumes. Indexing a block requires its state, nodes building the
+//! database of an existing chain should run with `--state-pruning archive`.
+//!
+//! A block which can't be indexed is retried, with a delay doubling up to [`MAX_RETRY_DELAY`],
+//! and never skipped since the owners and the sales follow from the blocks before. The error is
+//! kept in the database and shown by `indexer_status` until the block is indexed.
+
+mod db;
+mod decode;
+
+pub use db::{Database, IndexedBlock, IndexedEvent, IndexerStatus, IndexingError, Ownership, Sale};
+
+use futures::StreamExt;
+use parity_scale_codec::Decode;
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use sp_api::{CallApiAt, Metadata, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{storage::StorageKey, twox_128};
+use sp_runtime::generic::BlockId;
+use std::{marker::PhantomData, sync::Arc, time::Duration};
+use ternoa_core_primitives::{Block, BlockNumber};
+
+use decode::EventDecoder;
+
+const LOG_TARGET: &str = "indexer";
+
+/// Pallets whose events are indexed, by their name in the runtimes.
+const INDEXED_PALLETS: [&str; 5] = ["NFT", "Marketplace", "Auction", "Rent", "Bridge"];
+
+/// Delay before the first retry of a failed attempt, doubled at each new failure.
+const MIN_RETRY_DELAY: Duration = Duration::from_secs(6);
+/// Longest delay between two attempts.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(600);
+
+/// Indexes the finalized blocks into a [`Database`].
+pub struct Indexer<C, B> {
+	client: Arc<C>,
+	database: Database,
+	/// Decoder of the events of the last indexed runtime version.
+	decoder: Option<(u32, EventDecoder)>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> Indexer<C, B>
+where
+	B: Backend<Block>,
+	C: ProvideRuntimeApi<Block>
+		+ CallApiAt<Block>
+		+ HeaderBackend<Block>
+		+ BlockchainEvents<Block>
+		+ StorageProvider<Block, B>,
+	C::Api: Metadata<Block>,
+{
+	pub fn new(client: Arc<C>, database: Database) -> Self {
+		Self { client, database, decoder: None, _marker: Default::default() }
+	}
+
+	/// Indexes the finalized blocks as they come, retrying the ones that fail. Stops only when
+	/// the database indexes another chain.
+	pub async fn run(mut self) {
+		let mut finality_notifications = self.client.finality_notification_stream();
+
+		if let Err(e) = self.check_genesis() {
+			log::error!(target: LOG_TARGET, "{}", e);
+			self.record_error(IndexingError { block_number: None, message: e, attempts: 1 });
+			return
+		}
+
+		let mut attempts = 0;
+		loop {
+			if let Err((block_number, message)) = self.sync() {
+				attempts += 1;
+				let delay = retry_delay(attempts);
+				log::warn!(
+					target: LOG_TARGET,
+					"Indexing failed, attempt {} again in {}s: {}",
+					attempts,
+					delay.as_secs(),
+					message,
+				);
+				self.record_error(IndexingError { block_number, message, attempts });
+				futures_timer::Delay::new(delay).await;
+				continue
+			}
+			attempts = 0;
+
+			// Catching up with the last finalized block indexes the blocks finalized meanwhile.
+			if finality_notifications.next().await.is_none() {
+				return
+			}
+		}
+	}
+
+	fn record_error(&self, error: IndexingError) {
+		if let Err(e) = self.database.set_last_error(&error) {
+			log::error!(target: LOG_TARGET, "Failed to record the indexing error: {}", e);
+		}
+	}
+
+	/// Makes sure that the database indexes the chain of the node.
+	fn check_genesis(&self) -> Result<(), String> {
+		let genesis_hash = self.client.info().genesis_hash;
+		match self.database.genesis_hash().map_err(|e| e.to_string())? {
+			Some(hash) if hash != genesis_hash => Err(format!(
+				"The indexer database indexes the chain of genesis {:?}, not {:?}",
+				hash, genesis_hash,
+			)),
+			Some(_) => Ok(()),
+			None => self.database.set_genesis_hash(genesis_hash).map_err(|e| e.to_string()),
+		}
+	}
+
+	/// Indexes the blocks up to the last finalized one. Errors come with the block which failed,
+	/// if any.
+	fn sync(&mut self) -> Result<(), (Option<BlockNumber>, String)> {
+		let finalized = self.client.info().finalized_number;
+		let last = self.database.last_block().map_err(|e| (None, e.to_string()))?;
+
+		let next = match last {
+			Some(last) => {
+				let from = last.number.min(finalized);
+				self.revert_stale_blocks(from, last.number).map_err(|e| (None, e))? + 1
+			},
+			// The genesis block has no events.
+			None => 1,
+		};
+
+		for number in next..=finalized {
+			self.index_block(number).map_err(|e| (Some(number), e))?;
+		}
+
+		Ok(())
+	}
+
+	/// Removes the indexed blocks that are not part of the finalized chain, starting from `from`,
+	/// and returns the last indexed block that is.
+	fn revert_stale_blocks(
+		&self,
+		from: BlockNumber,
+		last: BlockNumber,
+	) -> Result<BlockNumber, String> {
+		let mut number = from;
+		while number > 0 {
+			let indexed = self.database.block_hash(number).map_err(|e| e.to_string())?;
+			let canonical = self.client.hash(number).map_err(|e| e.to_string())?;
+			if indexed.is_some() && indexed == canonical {
+				break
+			}
+			number -= 1;
+		}
+
+		if number < last {
+			log::info!(
+				target: LOG_TARGET,
+				"Removing the indexed blocks after #{}, they are not part of the finalized chain",
+				number,
+			);
+			self.database.revert_to(number).map_err(|e| e.to_string())?;
+		}
+
+		Ok(number)
+	}
+
+	fn index_block(&mut self, number: BlockNumber) -> Result<(), String> {
+		let hash = self
+			.client
+			.hash(number)
+			.map_err(|e| e.to_string())?
+			.ok_or_else(|| format!("Block #{} not found", number))?;
+		let at = BlockId::Hash(hash);
+
+		let version = self.client.runtime_version_at(&at).map_err(|e| e.to_string())?.spec_version;
+		let decoder = match self.decoder.take() {
+			Some((decoder_version, decoder)) if decoder_version == version => decoder,
+			_ => {
+				let metadata =
+					self.client.runtime_api().metadata(&at).map_err(|e| e.to_string())?;
+				EventDecoder::new(&metadata)?
+			},
+		};
+		let decoder = &self.decoder.insert((version, decoder)).1;
+
+		let storage = |pallet: &[u8], item: &[u8]| {
+			let key = StorageKey([twox_128(pallet), twox_128(item)].concat());
+			self.client.storage(&at, &key).map_err(|e| {
+				format!(
+					"Failed to read the state of block #{}, the node must keep the state of the \
+					blocks to index (see `--state-pruning`): {}",
+					number, e,
+				)
+			})
+		};
+
+		let timestamp = match storage(b"Timestamp", b"Now")? {
+			Some(data) => u64::decode(&mut &data.0[..]).map_err(|e| e.to_string())?,
+			None => 0,
+		};
+		let events = match storage(b"System", b"Events")? {
+			Some(data) => decoder
+				.decode_events(&data.0, &INDEXED_PALLETS)
+				.map_err(|e| format!("Failed to decode the events of block #{}: {}", number, e))?,
+			None => vec![],
+		};
+
+		self.database
+			.index_block(&IndexedBlock { number, hash, timestamp }, &events)
+			.map_err(|e| e.to_string())
+	}
+}
+
+/// Delay before the given attempt.
+fn retry_delay(attempts: u32) -> Duration {
+	MIN_RETRY_DELAY
+		.saturating_mul(1 << attempts.saturating_sub(1).min(16))
+		.min(MAX_RETRY_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn retry_delay_doubles_up_to_the_max() {
+		assert_eq!(retry_delay(1), MIN_RETRY_DELAY);
+		assert_eq!(retry_delay(2), MIN_RETRY_DELAY * 2);
+		assert_eq!(retry_delay(3), MIN_RETRY_DELAY * 4);
+		assert_eq!(retry_delay(10), MAX_RETRY_DELAY);
+		assert_eq!(retry_delay(u32::MAX), MAX_RETRY_DELAY);
+	}
+}
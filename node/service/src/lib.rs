@@ -15,6 +15,8 @@
 // along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
 
 pub mod chain_spec;
+mod dev;
+mod indexer;
 mod rpc;
 
 use futures::StreamExt;
@@ -30,12 +32,14 @@ use sc_service::{
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sp_api::ConstructRuntimeApi;
 use sp_runtime::traits::Block as BlockT;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use ternoa_client::RuntimeApiCollection;
 use ternoa_core_primitives::Block;
 
 use rpc::RpcExtension;
 
+pub use dev::{new_dev, Sealing};
+
 #[cfg(feature = "alphanet-native")]
 pub use ternoa_client::AlphanetExecutorDispatch;
 
@@ -67,23 +71,16 @@ pub type TransactionPool<RuntimeApi, Executor> =
 
 /// Can be called for a `Configuration` to identify which network the configuration targets.
 pub trait IdentifyVariant {
-	/// Returns `true` if this is a configuration for the `Alphanet` network.
-	fn is_alphanet(&self) -> bool;
-
-	/// Returns `true` if this is a configuration for the `Mainnet` network.
-	fn is_mainnet(&self) -> bool;
+	/// Returns the runtime of the network, see [`chain_spec::Runtime::of`].
+	fn runtime(&self) -> Result<chain_spec::Runtime, String>;
 
 	/// Returns `true` if this is a configuration for a dev network.
 	fn is_dev(&self) -> bool;
 }
 
 impl IdentifyVariant for Box<dyn ChainSpec> {
-	fn is_alphanet(&self) -> bool {
-		self.id().starts_with("alphanet") || self.id().starts_with("alpha")
-	}
-
-	fn is_mainnet(&self) -> bool {
-		self.id().starts_with("mainnet") || self.id().starts_with("main")
+	fn runtime(&self) -> Result<chain_spec::Runtime, String> {
+		chain_spec::Runtime::of(&**self)
 	}
 
 	fn is_dev(&self) -> bool {
@@ -307,18 +304,20 @@ where
 					select_chain: select_chain.clone(),
 					chain_spec: chain_spec.cloned_box(),
 					deny_unsafe,
+					subscription_executor: subscription_executor.clone(),
 					babe: rpc::BabeDeps {
 						babe_config: babe_config.clone(),
 						shared_epoch_changes: shared_epoch_changes.clone(),
 						keystore: keystore.clone(),
 					},
-					grandpa: rpc::GrandpaDeps {
+					grandpa: Some(rpc::GrandpaDeps {
 						shared_voter_state: shared_voter_state.clone(),
 						shared_authority_set: shared_authority_set.clone(),
 						justification_stream: justification_stream.clone(),
 						subscription_executor,
 						finality_provider: finality_proof_provider.clone(),
-					},
+					}),
+					command_sink: None,
 				};
 
 				rpc::create_full(deps, rpc_backend.clone()).map_err(Into::into)
@@ -363,8 +362,11 @@ where
 }
 
 /// Creates a full service from the configuration.
+///
+/// The event indexer is run, and its RPC methods served, when `indexer_db` is given.
 pub fn new_full_base<RuntimeApi, ExecutorDispatch>(
 	mut config: Configuration,
+	indexer_db: Option<PathBuf>,
 	with_startup_data: impl FnOnce(
 		&sc_consensus_babe::BabeBlockImport<
 			Block,
@@ -396,6 +398,31 @@ where
 	} = new_partial::<RuntimeApi, ExecutorDispatch>(&config)?;
 
 	let shared_voter_state = rpc_setup;
+
+	let indexer_database = indexer_db
+		.map(|path| {
+			indexer::Database::open(&path).map_err(|e| {
+				ServiceError::Other(format!(
+					"Failed to open the indexer database {}: {}",
+					path.display(),
+					e
+				))
+			})
+		})
+		.transpose()?;
+
+	let rpc_extensions_builder = {
+		let indexer_database = indexer_database.clone();
+
+		move |deny_unsafe, subscription_executor| -> Result<RpcExtension, sc_service::Error> {
+			let mut io = rpc_extensions_builder(deny_unsafe, subscription_executor)?;
+			if let Some(database) = &indexer_database {
+				rpc::extend_with_indexer(&mut io, database.clone())?;
+			}
+			Ok(io)
+		}
+	};
+
 	let auth_disc_publish_non_global_ips = config.network.allow_non_globals_in_dht;
 
 	let grandpa_protocol_name = sc_finality_grandpa::protocol_standard_name(
@@ -459,6 +486,14 @@ where
 
 	(with_startup_data)(&block_import, &babe_link);
 
+	if let Some(database) = indexer_database {
+		task_manager.spawn_handle().spawn_blocking(
+			"event-indexer",
+			Some("indexer"),
+			indexer::Indexer::new(client.clone(), database).run(),
+		);
+	}
+
 	if let sc_service::config::Role::Authority { .. } = &role {
 		let proposer = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
@@ -600,6 +635,7 @@ where
 /// Builds a new service for a full client.
 pub fn new_full<RuntimeApi, ExecutorDispatch>(
 	config: Configuration,
+	indexer_db: Option<PathBuf>,
 ) -> Result<TaskManager, ServiceError>
 where
 	RuntimeApi: ConstructRuntimeApi<Block, FullClient<RuntimeApi, ExecutorDispatch>>
@@ -610,6 +646,6 @@ where
 		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
 	ExecutorDispatch: NativeExecutionDispatch + 'static,
 {
-	new_full_base(config, |_, _| ())
+	new_full_base(config, indexer_db, |_, _| ())
 		.map(|NewFullBase::<RuntimeApi, ExecutorDispatch> { task_manager, .. }| task_manager)
 }
@@ -16,10 +16,16 @@
 
 use std::sync::Arc;
 
-use jsonrpsee::RpcModule;
-use sc_client_api::AuxStore;
+use futures::channel::mpsc;
+use jsonrpsee::{
+	core::Error as JsonRpseeError,
+	types::error::{CallError, ErrorObject},
+	RpcModule,
+};
+use sc_client_api::{AuxStore, BlockchainEvents};
 use sc_consensus_babe::{BabeConfiguration, Epoch};
 use sc_consensus_epochs::SharedEpochChanges;
+use sc_consensus_manual_seal::EngineCommand;
 use sc_finality_grandpa::{
 	FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
 };
@@ -29,9 +35,32 @@ use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_keystore::SyncCryptoStorePtr;
 use ternoa_core_primitives::{AccountId, Balance, Block, BlockNumber, Hash, Index};
 
+mod auction;
+mod indexer;
+mod marketplace;
+mod nft;
+mod rent;
+mod staking_payout;
+
 /// A type representing all RPC extensions.
 pub type RpcExtension = RpcModule<()>;
 
+/// Error code returned when a runtime API call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+/// Number of items returned by paginated calls when no limit is given.
+const DEFAULT_PAGE_SIZE: u32 = 100;
+
+/// Converts a runtime API error into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
 /// Extra dependencies for BABE.
 pub struct BabeDeps {
 	/// BABE protocol config.
@@ -68,10 +97,14 @@ pub struct FullDeps<C, P, SC, B> {
 	pub chain_spec: Box<dyn sc_chain_spec::ChainSpec>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Executor to drive the subscriptions of the Ternoa RPC handlers.
+	pub subscription_executor: SubscriptionTaskExecutor,
 	/// BABE specific dependencies.
 	pub babe: BabeDeps,
-	/// GRANDPA specific dependencies.
-	pub grandpa: GrandpaDeps<B>,
+	/// GRANDPA specific dependencies. `None` when GRANDPA is not running (manual sealing).
+	pub grandpa: Option<GrandpaDeps<B>>,
+	/// Channel used to send commands to the manual sealing engine, if any.
+	pub command_sink: Option<mpsc::Sender<EngineCommand<Hash>>>,
 }
 
 /// Instantiate all Full RPC extensions.
@@ -83,6 +116,7 @@ where
 	C: ProvideRuntimeApi<Block>
 		+ HeaderBackend<Block>
 		+ AuxStore
+		+ BlockchainEvents<Block>
 		+ HeaderMetadata<Block, Error = BlockChainError>
 		+ Sync
 		+ Send
@@ -91,33 +125,52 @@ where
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: sp_consensus_babe::BabeApi<Block>,
 	C::Api: sp_block_builder::BlockBuilder<Block>,
+	C::Api: ternoa_runtime_apis::nft::NftApi<Block, AccountId>,
+	C::Api: ternoa_runtime_apis::marketplace::MarketplaceApi<Block, AccountId, Balance>,
+	C::Api: ternoa_runtime_apis::auction::AuctionApi<Block, AccountId, Balance, BlockNumber>,
+	C::Api: ternoa_runtime_apis::rent::RentApi<Block, AccountId, BlockNumber>,
+	C::Api: ternoa_runtime_apis::staking_payout::StakingPayoutApi<Block, AccountId>,
 	P: sc_transaction_pool_api::TransactionPool + Sync + Send + 'static,
 	SC: sp_consensus::SelectChain<Block> + 'static,
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::backend::StateBackend<sp_runtime::traits::HashFor<Block>>,
 {
+	use auction::{Auction, AuctionApiServer};
+	use marketplace::{Marketplace, MarketplaceApiServer};
+	use nft::{Nft, NftApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+	use rent::{Rent, RentApiServer};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
+	use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer};
 	use sc_finality_grandpa_rpc::{Grandpa, GrandpaApiServer};
 	use sc_sync_state_rpc::{SyncState, SyncStateApiServer};
+	use staking_payout::{StakingPayout, StakingPayoutApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 	use substrate_state_trie_migration_rpc::{StateMigration, StateMigrationApiServer};
 
 	let mut io = RpcModule::new(());
-	let FullDeps { client, pool, select_chain, chain_spec, deny_unsafe, babe, grandpa } = deps;
+	let FullDeps {
+		client,
+		pool,
+		select_chain,
+		chain_spec,
+		deny_unsafe,
+		subscription_executor,
+		babe,
+		grandpa,
+		command_sink,
+	} = deps;
 
 	let BabeDeps { keystore, babe_config, shared_epoch_changes } = babe;
-	let GrandpaDeps {
-		shared_voter_state,
-		shared_authority_set,
-		justification_stream,
-		subscription_executor,
-		finality_provider,
-	} = grandpa;
 
 	io.merge(StateMigration::new(client.clone(), backend, deny_unsafe).into_rpc())?;
 	io.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
 	io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	io.merge(Nft::new(client.clone()).into_rpc())?;
+	io.merge(Marketplace::new(client.clone()).into_rpc())?;
+	io.merge(Auction::new(client.clone()).into_rpc())?;
+	io.merge(Rent::new(client.clone(), subscription_executor).into_rpc())?;
+	io.merge(StakingPayout::new(client.clone()).into_rpc())?;
 	io.merge(
 		Babe::new(
 			client.clone(),
@@ -129,19 +182,47 @@ where
 		)
 		.into_rpc(),
 	)?;
-	io.merge(
-		Grandpa::new(
-			subscription_executor,
-			shared_authority_set.clone(),
+
+	if let Some(grandpa) = grandpa {
+		let GrandpaDeps {
 			shared_voter_state,
+			shared_authority_set,
 			justification_stream,
+			subscription_executor,
 			finality_provider,
-		)
-		.into_rpc(),
-	)?;
-	io.merge(
-		SyncState::new(chain_spec, client, shared_authority_set, shared_epoch_changes)?.into_rpc(),
-	)?;
+		} = grandpa;
+
+		io.merge(
+			Grandpa::new(
+				subscription_executor,
+				shared_authority_set.clone(),
+				shared_voter_state,
+				justification_stream,
+				finality_provider,
+			)
+			.into_rpc(),
+		)?;
+		io.merge(
+			SyncState::new(chain_spec, client.clone(), shared_authority_set, shared_epoch_changes)?
+				.into_rpc(),
+		)?;
+	}
+
+	if let Some(command_sink) = command_sink {
+		// `engine_createBlock` and `engine_finalizeBlock`.
+		io.merge(ManualSeal::new(command_sink).into_rpc())?;
+	}
 
 	Ok(io)
 }
+
+/// Adds the `indexer_*` RPC methods, served from the database of the event indexer.
+pub fn extend_with_indexer(
+	io: &mut RpcExtension,
+	database: crate::indexer::Database,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+	use indexer::{Indexer, IndexerApiServer};
+
+	io.merge(Indexer::new(database).into_rpc())?;
+	Ok(())
+}
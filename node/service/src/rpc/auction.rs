@@ -0,0 +1,127 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! `auction_*` RPC methods, backed by the `AuctionApi` runtime API.
+
+use std::{fmt::Display, marker::PhantomData, str::FromStr, sync::Arc};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use parity_scale_codec::Codec;
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use ternoa_runtime_apis::{
+	auction::{AuctionApi as AuctionRuntimeApi, AuctionState},
+	marketplace::{MarketplaceId, SaleFees},
+	nft::{NFTId, Page},
+};
+
+use super::{runtime_error_into_rpc_err, DEFAULT_PAGE_SIZE};
+
+#[rpc(server)]
+pub trait AuctionApi<BlockHash, AccountId, Balance, BlockNumber> {
+	/// Returns the auction of an NFT, with its bidders and highest bid.
+	#[method(name = "auction_getAuction")]
+	fn auction(
+		&self,
+		nft_id: NFTId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<AuctionState<AccountId, Balance, BlockNumber>>>;
+
+	/// Returns a page of the auctions held on a marketplace. Pass the `next` NFT of a page as
+	/// `start_after` to get the next one, the last page has no `next`. A page can be short, or
+	/// empty, before the last one.
+	#[method(name = "auction_getAuctions")]
+	fn auctions(
+		&self,
+		marketplace_id: MarketplaceId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<AuctionState<AccountId, Balance, BlockNumber>>>;
+
+	/// Dry-runs the fee computation of `complete_auction` at the current highest bid, without
+	/// submitting anything.
+	#[method(name = "auction_completeAuctionFees")]
+	fn complete_auction_fees(
+		&self,
+		nft_id: NFTId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<SaleFees<Balance>>>;
+}
+
+/// Provides the `auction_*` RPC methods.
+pub struct Auction<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Auction<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber>
+	AuctionApiServer<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber> for Auction<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AuctionRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+	AccountId: Codec + Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + Display + FromStr + Serialize + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn auction(
+		&self,
+		nft_id: NFTId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<AuctionState<AccountId, Balance, BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.auction(&at, nft_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn auctions(
+		&self,
+		marketplace_id: MarketplaceId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Page<AuctionState<AccountId, Balance, BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+		self.client
+			.runtime_api()
+			.auctions(&at, marketplace_id, start_after, limit)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn complete_auction_fees(
+		&self,
+		nft_id: NFTId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<SaleFees<Balance>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.complete_auction_fees(&at, nft_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -0,0 +1,146 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! `indexer_*` RPC methods, backed by the database of the event indexer.
+
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use ternoa_core_primitives::BlockNumber;
+use ternoa_runtime_apis::{marketplace::MarketplaceId, nft::NFTId};
+
+use super::DEFAULT_PAGE_SIZE;
+use crate::indexer::{Database, IndexedEvent, IndexerStatus, Ownership, Sale};
+
+/// Error code returned when the indexer database can't be read.
+const DATABASE_ERROR: i32 = 2;
+
+/// Converts a database error into an RPC error.
+fn database_error_into_rpc_err(err: rusqlite::Error) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(DATABASE_ERROR, "Indexer error", Some(err.to_string())))
+		.into()
+}
+
+#[rpc(server)]
+pub trait IndexerApi {
+	/// Returns the last indexed block, `null` until the first block is indexed, and the error of
+	/// the last attempt, `null` when it succeeded. A block which fails is retried until it is
+	/// indexed, the error tells which one and how many times it failed.
+	#[method(name = "indexer_status")]
+	fn status(&self) -> RpcResult<IndexerStatus>;
+
+	/// Returns the successive owners of an NFT, from its creation. The owner is `null` once the
+	/// NFT is burnt.
+	#[method(name = "indexer_getNftOwners")]
+	fn nft_owners(&self, nft_id: NFTId) -> RpcResult<Vec<Ownership>>;
+
+	/// Returns a page of the events concerning an NFT. Pass the `[blockNumber, eventIndex]` of
+	/// the last event of a page as `start_after` to get the next one.
+	#[method(name = "indexer_getNftEvents")]
+	fn nft_events(
+		&self,
+		nft_id: NFTId,
+		start_after: Option<(BlockNumber, u32)>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<IndexedEvent>>;
+
+	/// Returns a page of the sales of a marketplace made between the `from` and `to`
+	/// timestamps, in milliseconds, `to` being excluded. Pass the `[blockNumber, eventIndex]` of
+	/// the last sale of a page as `start_after` to get the next one.
+	#[method(name = "indexer_getMarketplaceSales")]
+	fn marketplace_sales(
+		&self,
+		marketplace_id: MarketplaceId,
+		from: Option<u64>,
+		to: Option<u64>,
+		start_after: Option<(BlockNumber, u32)>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<Sale>>;
+
+	/// Returns a page of the events of an indexed pallet (`NFT`, `Marketplace`, `Auction`, `Rent`
+	/// or `Bridge`), only the ones named `name` if given. Pass the `[blockNumber, eventIndex]` of
+	/// the last event of a page as `start_after` to get the next one.
+	#[method(name = "indexer_getEvents")]
+	fn events(
+		&self,
+		pallet: String,
+		name: Option<String>,
+		start_after: Option<(BlockNumber, u32)>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<IndexedEvent>>;
+}
+
+/// Provides the `indexer_*` RPC methods.
+pub struct Indexer {
+	database: Database,
+}
+
+impl Indexer {
+	pub fn new(database: Database) -> Self {
+		Self { database }
+	}
+}
+
+impl IndexerApiServer for Indexer {
+	fn status(&self) -> RpcResult<IndexerStatus> {
+		self.database.status().map_err(database_error_into_rpc_err)
+	}
+
+	fn nft_owners(&self, nft_id: NFTId) -> RpcResult<Vec<Ownership>> {
+		self.database.nft_owners(nft_id).map_err(database_error_into_rpc_err)
+	}
+
+	fn nft_events(
+		&self,
+		nft_id: NFTId,
+		start_after: Option<(BlockNumber, u32)>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<IndexedEvent>> {
+		let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+		self.database
+			.nft_events(nft_id, start_after, limit)
+			.map_err(database_error_into_rpc_err)
+	}
+
+	fn marketplace_sales(
+		&self,
+		marketplace_id: MarketplaceId,
+		from: Option<u64>,
+		to: Option<u64>,
+		start_after: Option<(BlockNumber, u32)>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<Sale>> {
+		let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+		self.database
+			.marketplace_sales(marketplace_id, from, to, start_after, limit)
+			.map_err(database_error_into_rpc_err)
+	}
+
+	fn events(
+		&self,
+		pallet: String,
+		name: Option<String>,
+		start_after: Option<(BlockNumber, u32)>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<IndexedEvent>> {
+		let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+		self.database
+			.events(&pallet, name.as_deref(), start_after, limit)
+			.map_err(database_error_into_rpc_err)
+	}
+}
@@ -0,0 +1,125 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! `marketplace_*` RPC methods, backed by the `MarketplaceApi` runtime API.
+
+use std::{fmt::Display, marker::PhantomData, str::FromStr, sync::Arc};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use parity_scale_codec::Codec;
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use ternoa_runtime_apis::{
+	marketplace::{Listing, MarketplaceApi as MarketplaceRuntimeApi, MarketplaceId, SaleFees},
+	nft::{NFTId, Page},
+};
+
+use super::{runtime_error_into_rpc_err, DEFAULT_PAGE_SIZE};
+
+#[rpc(server)]
+pub trait MarketplaceApi<BlockHash, AccountId, Balance> {
+	/// Returns a page of the NFTs listed on a marketplace, with the price split between the
+	/// marketplace, the creator and the seller. Pass the `next` NFT of a page as `start_after` to
+	/// get the next one, the last page has no `next`. A page can be short, or empty, before the
+	/// last one.
+	#[method(name = "marketplace_getListings")]
+	fn listings(
+		&self,
+		marketplace_id: MarketplaceId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<Listing<AccountId, Balance>>>;
+
+	/// Returns the listing of an NFT.
+	#[method(name = "marketplace_getListing")]
+	fn listing(
+		&self,
+		nft_id: NFTId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Listing<AccountId, Balance>>>;
+
+	/// Dry-runs the fee computation of `buy_nft` for an NFT, without submitting anything.
+	#[method(name = "marketplace_buyNftFees")]
+	fn buy_nft_fees(
+		&self,
+		nft_id: NFTId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<SaleFees<Balance>>>;
+}
+
+/// Provides the `marketplace_*` RPC methods.
+pub struct Marketplace<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Marketplace<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, Balance> MarketplaceApiServer<<Block as BlockT>::Hash, AccountId, Balance>
+	for Marketplace<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: MarketplaceRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec + Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + Display + FromStr + Serialize + Send + Sync + 'static,
+{
+	fn listings(
+		&self,
+		marketplace_id: MarketplaceId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Page<Listing<AccountId, Balance>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+		self.client
+			.runtime_api()
+			.listings(&at, marketplace_id, start_after, limit)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn listing(
+		&self,
+		nft_id: NFTId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<Listing<AccountId, Balance>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.listing(&at, nft_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn buy_nft_fees(
+		&self,
+		nft_id: NFTId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<SaleFees<Balance>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.buy_nft_fees(&at, nft_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -0,0 +1,156 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! `nft_*` RPC methods, backed by the `NftApi` runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use parity_scale_codec::Codec;
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use ternoa_runtime_apis::nft::{
+	CollectionId, CollectionInfo, NFTId, NftApi as NftRuntimeApi, NftInfo, NftStatus, Page,
+};
+
+use super::{runtime_error_into_rpc_err, DEFAULT_PAGE_SIZE};
+
+#[rpc(server)]
+pub trait NftApi<BlockHash, AccountId> {
+	/// Returns the NFT with the given id.
+	#[method(name = "nft_getNft")]
+	fn nft(&self, nft_id: NFTId, at: Option<BlockHash>) -> RpcResult<Option<NftInfo<AccountId>>>;
+
+	/// Returns a page of the NFTs owned by `owner`. Pass the `next` NFT of a page as
+	/// `start_after` to get the next one, the last page has no `next`. A page can be short, or
+	/// empty, before the last one.
+	#[method(name = "nft_getNftsByOwner")]
+	fn nfts_by_owner(
+		&self,
+		owner: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<NftInfo<AccountId>>>;
+
+	/// Returns the collection with the given id.
+	#[method(name = "nft_getCollection")]
+	fn collection(
+		&self,
+		collection_id: CollectionId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<CollectionInfo<AccountId>>>;
+
+	/// Returns a page of the NFTs belonging to a collection.
+	#[method(name = "nft_getCollectionNfts")]
+	fn collection_nfts(
+		&self,
+		collection_id: CollectionId,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<NftInfo<AccountId>>>;
+
+	/// Returns the secret, capsule, delegation and other state flags of an NFT.
+	#[method(name = "nft_getNftStatus")]
+	fn nft_status(
+		&self,
+		nft_id: NFTId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<NftStatus<AccountId>>>;
+}
+
+/// Provides the `nft_*` RPC methods.
+pub struct Nft<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Nft<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId> NftApiServer<<Block as BlockT>::Hash, AccountId> for Nft<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: NftRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn nft(&self, nft_id: NFTId, at: Option<Block::Hash>) -> RpcResult<Option<NftInfo<AccountId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().nft(&at, nft_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn nfts_by_owner(
+		&self,
+		owner: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Page<NftInfo<AccountId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+		self.client
+			.runtime_api()
+			.nfts_by_owner(&at, owner, start_after, limit)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn collection(
+		&self,
+		collection_id: CollectionId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<CollectionInfo<AccountId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.collection(&at, collection_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn collection_nfts(
+		&self,
+		collection_id: CollectionId,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<NftInfo<AccountId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let offset = offset.unwrap_or_default();
+		let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+		self.client
+			.runtime_api()
+			.collection_nfts(&at, collection_id, offset, limit)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn nft_status(
+		&self,
+		nft_id: NFTId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<NftStatus<AccountId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.nft_status(&at, nft_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -0,0 +1,344 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! `rent_*` RPC methods, backed by the `RentApi` runtime API.
+
+use std::{collections::BTreeSet, marker::PhantomData, sync::Arc};
+
+use futures::{stream, FutureExt, StreamExt};
+use jsonrpsee::{
+	core::RpcResult, proc_macros::rpc, types::error::SubscriptionClosed, PendingSubscription,
+};
+use parity_scale_codec::Codec;
+use sc_client_api::BlockchainEvents;
+use sc_rpc::SubscriptionTaskExecutor;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use ternoa_runtime_apis::{
+	nft::{NFTId, Page, MAX_PAGE_SIZE},
+	rent::{ContractEvent, ContractEventKind, RentApi as RentRuntimeApi, RentContractInfo},
+};
+
+use super::{runtime_error_into_rpc_err, DEFAULT_PAGE_SIZE};
+
+const LOG_TARGET: &str = "rpc::rent";
+
+/// Selects the contract events sent to a `rent_subscribeContractEvents` subscriber.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContractEventFilter<AccountId> {
+	/// Events of the contracts where the account is the renter or the rentee.
+	Account(AccountId),
+	/// Events of the contracts of a single NFT.
+	Nft(NFTId),
+}
+
+/// A contract event along with the finalized block it was deposited in.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractEventNotification<BlockHash, AccountId> {
+	pub block_hash: BlockHash,
+	#[serde(flatten)]
+	pub event: ContractEvent<AccountId>,
+}
+
+#[rpc(server)]
+pub trait RentApi<BlockHash, AccountId, BlockNumber> {
+	/// Returns the rent contract of an NFT.
+	#[method(name = "rent_getContract")]
+	fn contract(
+		&self,
+		nft_id: NFTId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<RentContractInfo<AccountId, BlockNumber>>>;
+
+	/// Returns a page of the contracts offered by `renter`. Pass the `next` NFT of a page as
+	/// `start_after` to get the next one, the last page has no `next`. A page can be short, or
+	/// empty, before the last one.
+	#[method(name = "rent_getContractsByRenter")]
+	fn contracts_by_renter(
+		&self,
+		renter: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<RentContractInfo<AccountId, BlockNumber>>>;
+
+	/// Returns a page of the contracts rented by `rentee`. Pass the `next` NFT of a page as
+	/// `start_after` to get the next one, the last page has no `next`. A page can be short, or
+	/// empty, before the last one.
+	#[method(name = "rent_getContractsByRentee")]
+	fn contracts_by_rentee(
+		&self,
+		rentee: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<RentContractInfo<AccountId, BlockNumber>>>;
+
+	/// Returns a page of the running contracts ending at or before the block `until`, in storage
+	/// order. Pages are fetched the same way as with `rent_getContractsByRenter`.
+	#[method(name = "rent_getUpcomingExpirations")]
+	fn upcoming_expirations(
+		&self,
+		until: BlockNumber,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<RentContractInfo<AccountId, BlockNumber>>>;
+
+	/// Streams the creation, acceptance, revocation and expiry of the contracts matching
+	/// `filter`, as their blocks get finalized.
+	#[subscription(
+		name = "rent_subscribeContractEvents" => "rent_contractEvent",
+		unsubscribe = "rent_unsubscribeContractEvents",
+		item = ContractEventNotification<BlockHash, AccountId>,
+	)]
+	fn subscribe_contract_events(&self, filter: ContractEventFilter<AccountId>);
+}
+
+/// Keeps track of the NFTs whose contract involves the account of an
+/// [`ContractEventFilter::Account`] filter, as some events only carry the NFT id.
+struct EventMatcher<AccountId> {
+	filter: ContractEventFilter<AccountId>,
+	tracked_nfts: BTreeSet<NFTId>,
+}
+
+impl<AccountId: PartialEq> EventMatcher<AccountId> {
+	fn matches(&mut self, event: &ContractEvent<AccountId>) -> bool {
+		let account = match &self.filter {
+			ContractEventFilter::Nft(nft_id) => return event.nft_id == *nft_id,
+			ContractEventFilter::Account(account) => account,
+		};
+
+		let involved = match &event.kind {
+			ContractEventKind::Created { renter } => renter == account,
+			ContractEventKind::Accepted { rentee } => rentee == account,
+			ContractEventKind::Revoked { revoked_by } => revoked_by == account,
+			_ => false,
+		};
+		if involved {
+			self.tracked_nfts.insert(event.nft_id);
+		}
+		let matched = involved || self.tracked_nfts.contains(&event.nft_id);
+
+		if matches!(
+			event.kind,
+			ContractEventKind::Ended { .. } |
+				ContractEventKind::Expired |
+				ContractEventKind::Canceled
+		) {
+			self.tracked_nfts.remove(&event.nft_id);
+		}
+
+		matched
+	}
+}
+
+/// Provides the `rent_*` RPC methods.
+pub struct Rent<C, Block> {
+	client: Arc<C>,
+	executor: SubscriptionTaskExecutor,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Rent<C, Block> {
+	pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+		Self { client, executor, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> Rent<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+{
+	/// Collects the NFTs of all the contracts where `account` is the renter or the rentee at the
+	/// block `at`.
+	fn nfts_of<AccountId, BlockNumber>(
+		&self,
+		account: &AccountId,
+		at: Block::Hash,
+	) -> Result<BTreeSet<NFTId>, sp_api::ApiError>
+	where
+		C::Api: RentRuntimeApi<Block, AccountId, BlockNumber>,
+		AccountId: Codec + Clone,
+		BlockNumber: Codec,
+	{
+		let at = BlockId::hash(at);
+		let api = self.client.runtime_api();
+		let mut nfts = BTreeSet::new();
+
+		for by_rentee in [false, true] {
+			let mut start_after = None;
+			loop {
+				let page = if by_rentee {
+					api.contracts_by_rentee(&at, account.clone(), start_after, MAX_PAGE_SIZE)?
+				} else {
+					api.contracts_by_renter(&at, account.clone(), start_after, MAX_PAGE_SIZE)?
+				};
+
+				nfts.extend(page.items.iter().map(|contract| contract.nft_id));
+				match page.next {
+					Some(next) => start_after = Some(next),
+					None => break,
+				}
+			}
+		}
+
+		Ok(nfts)
+	}
+}
+
+impl<C, Block, AccountId, BlockNumber>
+	RentApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber> for Rent<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block>
+		+ HeaderBackend<Block>
+		+ BlockchainEvents<Block>
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: RentRuntimeApi<Block, AccountId, BlockNumber>,
+	AccountId: Codec + Clone + PartialEq + Serialize + DeserializeOwned + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn contract(
+		&self,
+		nft_id: NFTId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<RentContractInfo<AccountId, BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.contract(&at, nft_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn contracts_by_renter(
+		&self,
+		renter: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Page<RentContractInfo<AccountId, BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+		self.client
+			.runtime_api()
+			.contracts_by_renter(&at, renter, start_after, limit)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn contracts_by_rentee(
+		&self,
+		rentee: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Page<RentContractInfo<AccountId, BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+		self.client
+			.runtime_api()
+			.contracts_by_rentee(&at, rentee, start_after, limit)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn upcoming_expirations(
+		&self,
+		until: BlockNumber,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Page<RentContractInfo<AccountId, BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+		self.client
+			.runtime_api()
+			.upcoming_expirations(&at, until, start_after, limit)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn subscribe_contract_events(
+		&self,
+		pending: PendingSubscription,
+		filter: ContractEventFilter<AccountId>,
+	) {
+		// Subscribed first so that no block gets finalized between the one the contracts are read
+		// at and the first notification.
+		let finality_notifications = self.client.finality_notification_stream();
+		let finalized_hash = self.client.info().finalized_hash;
+		let tracked_nfts = match &filter {
+			ContractEventFilter::Account(account) =>
+				match self.nfts_of::<AccountId, BlockNumber>(account, finalized_hash) {
+					Ok(nfts) => nfts,
+					Err(e) => {
+						log::warn!(target: LOG_TARGET, "Failed to read the contracts: {:?}", e);
+						let _ = pending.reject(runtime_error_into_rpc_err(e));
+						return
+					},
+				},
+			ContractEventFilter::Nft(_) => BTreeSet::new(),
+		};
+		let mut matcher = EventMatcher { filter, tracked_nfts };
+
+		let client = self.client.clone();
+		let stream = finality_notifications.flat_map(move |notification| {
+			// A single notification can finalize several blocks at once.
+			let hashes = notification.tree_route.iter().cloned().chain(Some(notification.hash));
+			let mut notifications = Vec::new();
+			for block_hash in hashes {
+				let at = BlockId::hash(block_hash);
+				let events = match client.runtime_api().contract_events(&at) {
+					Ok(events) => events,
+					Err(e) => {
+						log::warn!(
+							target: LOG_TARGET,
+							"Failed to read the contract events of {:?}: {:?}",
+							block_hash,
+							e
+						);
+						notifications.push(Err(format!("{:?}", e)));
+						break
+					},
+				};
+				notifications.extend(
+					events
+						.into_iter()
+						.filter(|event| matcher.matches(event))
+						.map(|event| Ok(ContractEventNotification { block_hash, event })),
+				);
+			}
+			stream::iter(notifications)
+		});
+
+		let fut = async move {
+			if let Some(mut sink) = pending.accept() {
+				// The subscription is closed with the error of the first block whose events
+				// could not be read, skipping it would silently drop its events.
+				if let SubscriptionClosed::Failed(e) = sink.pipe_from_try_stream(stream).await {
+					let _ = sink.close(e);
+				}
+			}
+		};
+
+		self.executor.spawn("ternoa-rpc-subscription", Some("rpc"), fut.boxed());
+	}
+}
@@ -0,0 +1,110 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+//! `stakingPayout_*` RPC methods, backed by the `StakingPayoutApi` runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use parity_scale_codec::Codec;
+use serde::de::DeserializeOwned;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use ternoa_runtime_apis::staking_payout::{
+	NominatorPayouts, StakingPayoutApi as StakingPayoutRuntimeApi, ValidatorPayouts,
+};
+
+use super::runtime_error_into_rpc_err;
+
+#[rpc(server)]
+pub trait StakingPayoutApi<BlockHash, AccountId> {
+	/// Returns the eras for which a validator and its nominators are waiting for the automatic
+	/// payout, and the eras already paid out.
+	#[method(name = "stakingPayout_getValidatorPayouts")]
+	fn validator_payouts(
+		&self,
+		validator: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<ValidatorPayouts>;
+
+	/// Returns the eras of the staking history for which a nominator is still waiting for a
+	/// payout, and the eras fully paid out.
+	///
+	/// Only the validators currently nominated are looked at.
+	#[method(name = "stakingPayout_getNominatorPayouts")]
+	fn nominator_payouts(
+		&self,
+		nominator: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<NominatorPayouts>;
+
+	/// Returns the number of automatic payouts waiting to be processed.
+	#[method(name = "stakingPayout_getPendingPayouts")]
+	fn pending_payouts(&self, at: Option<BlockHash>) -> RpcResult<u32>;
+}
+
+/// Provides the `stakingPayout_*` RPC methods.
+pub struct StakingPayout<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> StakingPayout<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId> StakingPayoutApiServer<<Block as BlockT>::Hash, AccountId>
+	for StakingPayout<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: StakingPayoutRuntimeApi<Block, AccountId>,
+	AccountId: Codec + DeserializeOwned + Send + Sync + 'static,
+{
+	fn validator_payouts(
+		&self,
+		validator: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<ValidatorPayouts> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.validator_payouts(&at, validator)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn nominator_payouts(
+		&self,
+		nominator: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<NominatorPayouts> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.nominator_payouts(&at, nominator)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn pending_payouts(&self, at: Option<Block::Hash>) -> RpcResult<u32> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.pending_payouts(&at)
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -0,0 +1,54 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::Hooks;
+use sp_runtime::traits::One;
+
+benchmarks! {
+	set_split {
+		let origin = T::SplitOrigin::successful_origin();
+		let split = FeeSplit {
+			author: Perbill::from_percent(10),
+			treasury: Perbill::from_percent(20),
+			staking: Perbill::from_percent(30),
+			burn: Perbill::from_percent(40),
+		};
+	}: _<T::RuntimeOrigin>(origin, split.clone())
+	verify {
+		assert_eq!(Split::<T>::get(), split);
+	}
+
+	on_finalize {
+		let amount: BalanceOf<T> = 1u32.into();
+		BlockRoutedFees::<T>::put(RoutedFees {
+			author: amount,
+			treasury: amount,
+			staking: amount,
+			burnt: amount,
+		});
+	}: {
+		Pallet::<T>::on_finalize(T::BlockNumber::one());
+	}
+	verify {
+		assert!(!BlockRoutedFees::<T>::exists());
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,231 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Fee Router
+//!
+//! Splits the transaction fees and tips between the block author, the treasury, the staking
+//! rewards pot and burn, following a [`FeeSplit`] stored on-chain and changeable by governance.
+//!
+//! The pallet implements [`OnUnbalanced`] so that it can be given to the `CurrencyAdapter` of
+//! `pallet_transaction_payment`. The amounts routed during a block are summed up and reported
+//! by a single [`Event::FeesRouted`] at the end of the block.
+//!
+//! The fees are routed while the transactions are charged, so the cost of the routing is part of
+//! the extrinsic base weight rather than of the weights of this pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, Imbalance, OnUnbalanced},
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	Perbill,
+};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+/// Share of the fees given to each destination. The shares must add up to 100%.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct FeeSplit {
+	pub author: Perbill,
+	pub treasury: Perbill,
+	pub staking: Perbill,
+	pub burn: Perbill,
+}
+
+impl FeeSplit {
+	/// Returns true if the shares add up to 100%.
+	pub fn is_valid(&self) -> bool {
+		let total = [self.author, self.treasury, self.staking, self.burn]
+			.iter()
+			.map(|share| share.deconstruct() as u64)
+			.sum::<u64>();
+		total == Perbill::one().deconstruct() as u64
+	}
+}
+
+/// Amounts routed to each destination.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RoutedFees<Balance> {
+	pub author: Balance,
+	pub treasury: Balance,
+	pub staking: Balance,
+	pub burnt: Balance,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Currency in which the fees are paid.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Origin allowed to change the fee split.
+		type SplitOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Fee split used until governance sets one.
+		type DefaultSplit: Get<FeeSplit>;
+
+		/// Receives the block author share.
+		type Author: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Receives the treasury share.
+		type Treasury: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Receives the staking rewards pot share.
+		type StakingPot: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::type_value]
+	pub fn DefaultSplitOnEmpty<T: Config>() -> FeeSplit {
+		T::DefaultSplit::get()
+	}
+
+	/// Current fee split.
+	#[pallet::storage]
+	#[pallet::getter(fn split)]
+	pub type Split<T: Config> = StorageValue<_, FeeSplit, ValueQuery, DefaultSplitOnEmpty<T>>;
+
+	/// Amounts routed since the beginning of the block.
+	#[pallet::storage]
+	pub type BlockRoutedFees<T: Config> = StorageValue<_, RoutedFees<BalanceOf<T>>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The fee split was changed.
+		SplitChanged { split: FeeSplit },
+		/// Fees were routed during the block.
+		FeesRouted {
+			author: BalanceOf<T>,
+			treasury: BalanceOf<T>,
+			staking: BalanceOf<T>,
+			burnt: BalanceOf<T>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The shares of the split do not add up to 100%.
+		InvalidSplit,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			// Accounts for `on_finalize`, which has no weight of its own.
+			T::WeightInfo::on_finalize()
+		}
+
+		fn on_finalize(_n: T::BlockNumber) {
+			let routed = BlockRoutedFees::<T>::take();
+			if routed != RoutedFees::default() {
+				let RoutedFees { author, treasury, staking, burnt } = routed;
+				Self::deposit_event(Event::FeesRouted { author, treasury, staking, burnt });
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Changes how the next fees are split.
+		#[pallet::weight(T::WeightInfo::set_split())]
+		pub fn set_split(origin: OriginFor<T>, split: FeeSplit) -> DispatchResult {
+			T::SplitOrigin::ensure_origin(origin)?;
+			ensure!(split.is_valid(), Error::<T>::InvalidSplit);
+
+			Split::<T>::put(split.clone());
+			Self::deposit_event(Event::SplitChanged { split });
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Splits the amount between the destinations following the current split.
+	fn route(amount: NegativeImbalanceOf<T>) {
+		let total = amount.peek();
+		if total.is_zero() {
+			return
+		}
+
+		let split = Split::<T>::get();
+		let (author, rest) = amount.split(split.author * total);
+		let (treasury, rest) = rest.split(split.treasury * total);
+		// Rounding leftovers are burnt.
+		let (staking, burnt) = rest.split(split.staking * total);
+
+		BlockRoutedFees::<T>::mutate(|routed| {
+			routed.author.saturating_accrue(author.peek());
+			routed.treasury.saturating_accrue(treasury.peek());
+			routed.staking.saturating_accrue(staking.peek());
+			routed.burnt.saturating_accrue(burnt.peek());
+		});
+
+		T::Author::on_unbalanced(author);
+		T::Treasury::on_unbalanced(treasury);
+		T::StakingPot::on_unbalanced(staking);
+		// Dropping the imbalance reduces the total issuance.
+		drop(burnt);
+	}
+}
+
+impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for Pallet<T> {
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalanceOf<T>>) {
+		if let Some(mut amount) = fees_then_tips.next() {
+			if let Some(tips) = fees_then_tips.next() {
+				tips.merge_into(&mut amount);
+			}
+			Self::route(amount);
+		}
+	}
+
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T>) {
+		Self::route(amount);
+	}
+}
@@ -0,0 +1,160 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{ExistenceRequirement, WithdrawReasons},
+};
+use sp_runtime::DispatchError::BadOrigin;
+
+fn split(author: u32, treasury: u32, staking: u32, burn: u32) -> FeeSplit {
+	FeeSplit {
+		author: Perbill::from_percent(author),
+		treasury: Perbill::from_percent(treasury),
+		staking: Perbill::from_percent(staking),
+		burn: Perbill::from_percent(burn),
+	}
+}
+
+/// Withdraws fees and tips from Alice, as the transaction payment pallet does.
+fn pay(fee: u64, tip: u64) {
+	let withdraw = |amount| {
+		Balances::withdraw(&ALICE, amount, WithdrawReasons::FEE, ExistenceRequirement::KeepAlive)
+			.unwrap()
+	};
+	FeeRouter::on_unbalanceds(vec![withdraw(fee), withdraw(tip)].into_iter());
+}
+
+fn balances() -> (u64, u64, u64) {
+	(
+		Balances::free_balance(AUTHOR),
+		Balances::free_balance(TREASURY),
+		Balances::free_balance(STAKING_POT),
+	)
+}
+
+mod set_split {
+	use super::*;
+
+	#[test]
+	fn set_split() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(FeeRouter::split(), DefaultSplit::get());
+			assert_ok!(FeeRouter::set_split(RuntimeOrigin::root(), split(10, 20, 30, 40)));
+
+			assert_eq!(FeeRouter::split(), split(10, 20, 30, 40));
+			System::assert_last_event(RuntimeEvent::FeeRouter(Event::SplitChanged {
+				split: split(10, 20, 30, 40),
+			}));
+		})
+	}
+
+	#[test]
+	fn bad_origin() {
+		new_test_ext().execute_with(|| {
+			let err = FeeRouter::set_split(RuntimeOrigin::signed(ALICE), split(10, 20, 30, 40));
+			assert_noop!(err, BadOrigin);
+		})
+	}
+
+	#[test]
+	fn invalid_split() {
+		new_test_ext().execute_with(|| {
+			for invalid in [split(0, 0, 0, 0), split(10, 20, 30, 39), split(50, 50, 50, 0)] {
+				let err = FeeRouter::set_split(RuntimeOrigin::root(), invalid);
+				assert_noop!(err, Error::<Test>::InvalidSplit);
+			}
+		})
+	}
+}
+
+mod route {
+	use super::*;
+
+	#[test]
+	fn default_split_goes_to_staking_pot() {
+		new_test_ext().execute_with(|| {
+			pay(1_000, 100);
+			assert_eq!(balances(), (0, 0, 1_100));
+		})
+	}
+
+	#[test]
+	fn split_is_respected() {
+		let splits = [
+			split(100, 0, 0, 0),
+			split(0, 100, 0, 0),
+			split(0, 0, 100, 0),
+			split(0, 0, 0, 100),
+			split(25, 25, 25, 25),
+			split(10, 40, 30, 20),
+			split(33, 33, 33, 1),
+		];
+		let payments = [(0, 0), (1, 0), (7, 3), (1_000, 0), (999_999, 1), (1 << 60, 1 << 50)];
+
+		for fee_split in splits {
+			for (fee, tip) in payments {
+				new_test_ext().execute_with(|| {
+					assert_ok!(FeeRouter::set_split(RuntimeOrigin::root(), fee_split.clone()));
+					let issuance = Balances::total_issuance();
+
+					pay(fee, tip);
+
+					let total = fee + tip;
+					let (author, treasury, staking) = balances();
+					assert_eq!(author, fee_split.author * total);
+					assert_eq!(treasury, fee_split.treasury * total);
+					assert_eq!(staking, fee_split.staking * total);
+
+					let burnt = total -
+						(fee_split.author * total) -
+						(fee_split.treasury * total) -
+						(fee_split.staking * total);
+					assert!(burnt >= fee_split.burn * total);
+					assert_eq!(Balances::total_issuance(), issuance - burnt);
+				})
+			}
+		}
+	}
+
+	#[test]
+	fn fees_routed_event() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(FeeRouter::set_split(RuntimeOrigin::root(), split(10, 20, 30, 40)));
+			pay(1_000, 0);
+			pay(900, 100);
+			FeeRouter::on_finalize(1);
+
+			System::assert_last_event(RuntimeEvent::FeeRouter(Event::FeesRouted {
+				author: 200,
+				treasury: 400,
+				staking: 600,
+				burnt: 800,
+			}));
+			assert_eq!(BlockRoutedFees::<Test>::get(), RoutedFees::default());
+		})
+	}
+
+	#[test]
+	fn no_event_without_fees() {
+		new_test_ext().execute_with(|| {
+			FeeRouter::on_finalize(1);
+			assert!(System::events().is_empty());
+		})
+	}
+}
@@ -0,0 +1,84 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_system::RawOrigin;
+use sp_std::vec;
+
+fn remark_call<T: Config>() -> Box<<T as Config>::RuntimeCall>
+where
+	<T as Config>::RuntimeCall: From<frame_system::Call<T>>,
+{
+	Box::new(frame_system::Call::<T>::remark { remark: vec![] }.into())
+}
+
+/// Adds `s` tasks to the agenda of the block the next mandate is dispatched at.
+fn fill_agenda<T: Config>(s: u32) -> Result<(), &'static str>
+where
+	<T as Config>::RuntimeCall: From<frame_system::Call<T>>,
+{
+	let dispatch_at = frame_system::Pallet::<T>::block_number().saturating_add(T::Delay::get());
+	for i in 0..s {
+		T::Scheduler::schedule_named(
+			(b"bench", i).encode(),
+			DispatchTime::At(dispatch_at),
+			None,
+			HARD_DEADLINE,
+			frame_system::RawOrigin::Root.into(),
+			(*remark_call::<T>()).into(),
+		)
+		.map_err(|_| "the agenda can't be filled")?;
+	}
+	Ok(())
+}
+
+benchmarks! {
+	where_clause { where <T as Config>::RuntimeCall: From<frame_system::Call<T>> }
+
+	propose {
+		let s in 0 .. T::MaxScheduledPerBlock::get() - 1;
+		fill_agenda::<T>(s)?;
+		let origin = T::ExternalOrigin::successful_origin();
+		let call = remark_call::<T>();
+	}: _<T::RuntimeOrigin>(origin, call)
+	verify {
+		assert!(PendingMandates::<T>::contains_key(0));
+	}
+
+	enact {
+		let origin = T::ExternalOrigin::successful_origin();
+		Pallet::<T>::propose(origin, remark_call::<T>())?;
+	}: _(RawOrigin::Root, 0, remark_call::<T>())
+	verify {
+		assert!(!PendingMandates::<T>::contains_key(0));
+	}
+
+	veto {
+		let s in 0 .. T::MaxScheduledPerBlock::get() - 1;
+		fill_agenda::<T>(s)?;
+		let origin = T::ExternalOrigin::successful_origin();
+		Pallet::<T>::propose(origin, remark_call::<T>())?;
+		let origin = T::VetoOrigin::successful_origin();
+	}: _<T::RuntimeOrigin>(origin, 0)
+	verify {
+		assert!(!PendingMandates::<T>::contains_key(0));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
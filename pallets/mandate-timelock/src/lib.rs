@@ -0,0 +1,262 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Mandate Timelock
+//!
+//! Lets the technical committee dispatch calls with the root origin, like `ternoa_mandate`, but
+//! only after [`Config::Delay`]. The mandates are scheduled through the scheduler and, until they
+//! are dispatched, they can be vetoed by [`Config::VetoOrigin`], which is meant to be the council
+//! or a referendum.
+//!
+//! Calls matched by [`Config::EmergencyCalls`] skip the delay and are dispatched right away.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
+use frame_support::{
+	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+	pallet_prelude::*,
+	traits::{
+		schedule::{DispatchTime, Named as ScheduleNamed, HARD_DEADLINE},
+		Contains,
+	},
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::traits::{Hash as HashT, Saturating};
+use sp_std::prelude::*;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// Index of a mandate.
+pub type MandateIndex = u32;
+
+/// Prefix of the names of the scheduled mandates.
+const MANDATE_ID: [u8; 8] = *b"mandate_";
+
+/// A mandate waiting for its delay to pass.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingMandate<Hash, BlockNumber> {
+	/// Hash of the call to dispatch.
+	pub call_hash: Hash,
+	/// Block at which the call is dispatched.
+	pub dispatch_at: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The overarching call type.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<Call<Self>>;
+
+		/// The origin the scheduler dispatches with.
+		type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+
+		/// Scheduler of the delayed mandates.
+		type Scheduler: ScheduleNamed<
+			Self::BlockNumber,
+			<Self as Config>::RuntimeCall,
+			Self::PalletsOrigin,
+		>;
+
+		/// Origin allowed to issue mandates.
+		type ExternalOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Origin allowed to veto the pending mandates.
+		type VetoOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Calls dispatched without delay.
+		type EmergencyCalls: Contains<<Self as Config>::RuntimeCall>;
+
+		/// Number of blocks between a mandate and its dispatch.
+		#[pallet::constant]
+		type Delay: Get<Self::BlockNumber>;
+
+		/// Maximum number of tasks in the agenda of a block of [`Config::Scheduler`]. The agenda
+		/// the mandates are added to or removed from is weighed as full.
+		#[pallet::constant]
+		type MaxScheduledPerBlock: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Number of mandates issued so far, used as the index of the next one.
+	#[pallet::storage]
+	#[pallet::getter(fn mandate_count)]
+	pub type MandateCount<T: Config> = StorageValue<_, MandateIndex, ValueQuery>;
+
+	/// Mandates waiting for their delay to pass.
+	#[pallet::storage]
+	pub type PendingMandates<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		MandateIndex,
+		PendingMandate<T::Hash, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A mandate was scheduled.
+		MandateScheduled { index: MandateIndex, call_hash: T::Hash, dispatch_at: T::BlockNumber },
+		/// A mandate was dispatched once its delay passed.
+		MandateDispatched { index: MandateIndex, result: DispatchResult },
+		/// A pending mandate was vetoed.
+		MandateVetoed { index: MandateIndex },
+		/// An emergency mandate was dispatched right away.
+		EmergencyMandateDispatched { result: DispatchResult },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The mandate could not be scheduled.
+		ScheduleFailed,
+		/// There is no pending mandate with this index.
+		UnknownMandate,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Dispatches the call with the root origin once the delay passed, or right away if it is
+		/// an emergency call.
+		#[pallet::weight({
+			let info = call.get_dispatch_info();
+			let propose = T::WeightInfo::propose(T::MaxScheduledPerBlock::get());
+			(propose.saturating_add(info.weight), info.class)
+		})]
+		pub fn propose(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			T::ExternalOrigin::ensure_origin(origin)?;
+
+			if T::EmergencyCalls::contains(&call) {
+				let call_weight = call.get_dispatch_info().weight;
+				let result = call.dispatch(frame_system::RawOrigin::Root.into());
+				let actual_weight = match &result {
+					Ok(post_info) => post_info.actual_weight,
+					Err(e) => e.post_info.actual_weight,
+				}
+				.unwrap_or(call_weight);
+				Self::deposit_event(Event::EmergencyMandateDispatched {
+					result: result.map(|_| ()).map_err(|e| e.error),
+				});
+				// The emergency path does less than `enact`.
+				return Ok(Some(T::WeightInfo::enact().saturating_add(actual_weight)).into())
+			}
+
+			let index = MandateCount::<T>::get();
+			let call_hash = T::Hashing::hash_of(&call);
+			let dispatch_at =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::Delay::get());
+
+			T::Scheduler::schedule_named(
+				Self::task_name(index),
+				DispatchTime::At(dispatch_at),
+				None,
+				HARD_DEADLINE,
+				frame_system::RawOrigin::Root.into(),
+				Call::<T>::enact { index, call }.into(),
+			)
+			.map_err(|_| Error::<T>::ScheduleFailed)?;
+
+			MandateCount::<T>::put(index.saturating_add(1));
+			PendingMandates::<T>::insert(index, PendingMandate { call_hash, dispatch_at });
+			Self::deposit_event(Event::MandateScheduled { index, call_hash, dispatch_at });
+
+			// Only the emergency calls are dispatched here.
+			Ok(Some(T::WeightInfo::propose(T::MaxScheduledPerBlock::get())).into())
+		}
+
+		/// Dispatches a pending mandate, called by the scheduler once the delay passed.
+		#[pallet::weight({
+			let info = call.get_dispatch_info();
+			(T::WeightInfo::enact().saturating_add(info.weight), info.class)
+		})]
+		pub fn enact(
+			origin: OriginFor<T>,
+			index: MandateIndex,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(PendingMandates::<T>::take(index).is_some(), Error::<T>::UnknownMandate);
+
+			let call_weight = call.get_dispatch_info().weight;
+			let result = call.dispatch(frame_system::RawOrigin::Root.into());
+			let actual_weight = match &result {
+				Ok(post_info) => post_info.actual_weight,
+				Err(e) => e.post_info.actual_weight,
+			}
+			.unwrap_or(call_weight);
+			Self::deposit_event(Event::MandateDispatched {
+				index,
+				result: result.map(|_| ()).map_err(|e| e.error),
+			});
+
+			Ok(Some(T::WeightInfo::enact().saturating_add(actual_weight)).into())
+		}
+
+		/// Cancels a pending mandate.
+		#[pallet::weight(T::WeightInfo::veto(T::MaxScheduledPerBlock::get()))]
+		pub fn veto(origin: OriginFor<T>, index: MandateIndex) -> DispatchResult {
+			T::VetoOrigin::ensure_origin(origin)?;
+			ensure!(PendingMandates::<T>::take(index).is_some(), Error::<T>::UnknownMandate);
+
+			T::Scheduler::cancel_named(Self::task_name(index))
+				.map_err(|_| Error::<T>::UnknownMandate)?;
+			Self::deposit_event(Event::MandateVetoed { index });
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Name of the scheduled task of a mandate.
+	fn task_name(index: MandateIndex) -> Vec<u8> {
+		(MANDATE_ID, index).encode()
+	}
+
+	/// Mandates waiting for their delay to pass, by index.
+	pub fn pending_mandates() -> Vec<(MandateIndex, PendingMandate<T::Hash, T::BlockNumber>)> {
+		let mut mandates: Vec<_> = PendingMandates::<T>::iter().collect();
+		mandates.sort_unstable_by_key(|(index, _)| *index);
+		mandates
+	}
+}
@@ -0,0 +1,190 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{traits::BlakeTwo256, DispatchError::BadOrigin};
+
+fn remark_call() -> Box<RuntimeCall> {
+	Box::new(RuntimeCall::System(frame_system::Call::remark_with_event { remark: vec![1] }))
+}
+
+fn remarked() -> bool {
+	System::events().iter().any(|record| {
+		matches!(record.event, RuntimeEvent::System(frame_system::Event::Remarked { .. }))
+	})
+}
+
+mod propose {
+	use super::*;
+
+	#[test]
+	fn propose() {
+		new_test_ext().execute_with(|| {
+			let call_hash = BlakeTwo256::hash_of(&remark_call());
+			assert_ok!(MandateTimelock::propose(RuntimeOrigin::signed(COMMITTEE), remark_call()));
+
+			let dispatch_at = 1 + DELAY;
+			assert_eq!(MandateTimelock::mandate_count(), 1);
+			assert_eq!(
+				MandateTimelock::pending_mandates(),
+				vec![(0, PendingMandate { call_hash, dispatch_at })]
+			);
+			System::assert_last_event(RuntimeEvent::MandateTimelock(Event::MandateScheduled {
+				index: 0,
+				call_hash,
+				dispatch_at,
+			}));
+		})
+	}
+
+	#[test]
+	fn dispatched_after_delay() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(MandateTimelock::propose(RuntimeOrigin::signed(COMMITTEE), remark_call()));
+
+			run_to_block(DELAY);
+			assert!(!remarked());
+
+			run_to_block(1 + DELAY);
+			assert!(remarked());
+			assert!(MandateTimelock::pending_mandates().is_empty());
+			System::assert_has_event(RuntimeEvent::MandateTimelock(Event::MandateDispatched {
+				index: 0,
+				result: Ok(()),
+			}));
+		})
+	}
+
+	#[test]
+	fn emergency_call_skips_delay() {
+		new_test_ext().execute_with(|| {
+			let call =
+				Box::new(RuntimeCall::System(frame_system::Call::set_heap_pages { pages: 64 }));
+			assert_ok!(MandateTimelock::propose(RuntimeOrigin::signed(COMMITTEE), call));
+
+			assert_eq!(MandateTimelock::mandate_count(), 0);
+			assert_eq!(
+				sp_io::storage::get(sp_core::storage::well_known_keys::HEAP_PAGES),
+				Some(64u64.encode())
+			);
+			System::assert_last_event(RuntimeEvent::MandateTimelock(
+				Event::EmergencyMandateDispatched { result: Ok(()) },
+			));
+		})
+	}
+
+	#[test]
+	fn bad_origin() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				MandateTimelock::propose(RuntimeOrigin::signed(ALICE), remark_call()),
+				BadOrigin
+			);
+			assert_noop!(
+				MandateTimelock::propose(RuntimeOrigin::signed(COUNCIL), remark_call()),
+				BadOrigin
+			);
+		})
+	}
+}
+
+mod enact {
+	use super::*;
+
+	#[test]
+	fn bad_origin() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(MandateTimelock::propose(RuntimeOrigin::signed(COMMITTEE), remark_call()));
+
+			assert_noop!(
+				MandateTimelock::enact(RuntimeOrigin::signed(COMMITTEE), 0, remark_call()),
+				BadOrigin
+			);
+		})
+	}
+
+	#[test]
+	fn unknown_mandate() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				MandateTimelock::enact(RuntimeOrigin::root(), 0, remark_call()),
+				Error::<Test>::UnknownMandate
+			);
+		})
+	}
+}
+
+mod veto {
+	use super::*;
+
+	#[test]
+	fn veto() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(MandateTimelock::propose(RuntimeOrigin::signed(COMMITTEE), remark_call()));
+			assert_ok!(MandateTimelock::veto(RuntimeOrigin::signed(COUNCIL), 0));
+
+			assert!(MandateTimelock::pending_mandates().is_empty());
+			System::assert_last_event(RuntimeEvent::MandateTimelock(Event::MandateVetoed {
+				index: 0,
+			}));
+
+			run_to_block(1 + DELAY);
+			assert!(!remarked());
+		})
+	}
+
+	#[test]
+	fn root_can_veto() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(MandateTimelock::propose(RuntimeOrigin::signed(COMMITTEE), remark_call()));
+			assert_ok!(MandateTimelock::veto(RuntimeOrigin::root(), 0));
+		})
+	}
+
+	#[test]
+	fn bad_origin() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(MandateTimelock::propose(RuntimeOrigin::signed(COMMITTEE), remark_call()));
+
+			assert_noop!(MandateTimelock::veto(RuntimeOrigin::signed(COMMITTEE), 0), BadOrigin);
+		})
+	}
+
+	#[test]
+	fn unknown_mandate() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				MandateTimelock::veto(RuntimeOrigin::root(), 0),
+				Error::<Test>::UnknownMandate
+			);
+		})
+	}
+
+	#[test]
+	fn dispatched_mandate_cannot_be_vetoed() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(MandateTimelock::propose(RuntimeOrigin::signed(COMMITTEE), remark_call()));
+			run_to_block(1 + DELAY);
+
+			assert_noop!(
+				MandateTimelock::veto(RuntimeOrigin::root(), 0),
+				Error::<Test>::UnknownMandate
+			);
+		})
+	}
+}
@@ -0,0 +1,55 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::weights::{constants::RocksDbWeight, Weight};
+
+/// Weight functions needed for `ternoa_mandate_timelock`.
+pub trait WeightInfo {
+	fn propose(s: u32) -> Weight;
+	fn enact() -> Weight;
+	fn veto(s: u32) -> Weight;
+}
+
+// For tests
+impl WeightInfo for () {
+	// Storage: MandateTimelock MandateCount (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: MandateTimelock PendingMandates (r:0 w:1)
+	fn propose(s: u32) -> Weight {
+		Weight::from_ref_time(69_480_000 as u64)
+			.saturating_add(Weight::from_ref_time(132_000 as u64).saturating_mul(s as u64))
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	// Storage: MandateTimelock PendingMandates (r:1 w:1)
+	fn enact() -> Weight {
+		Weight::from_ref_time(44_664_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: MandateTimelock PendingMandates (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn veto(s: u32) -> Weight {
+		Weight::from_ref_time(68_252_000 as u64)
+			.saturating_add(Weight::from_ref_time(590_000 as u64).saturating_mul(s as u64))
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+}
@@ -0,0 +1,54 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+
+benchmarks! {
+	set_payout_mode {
+		let origin = T::ExternalOrigin::successful_origin();
+	}: _<T::RuntimeOrigin>(origin, PayoutMode::Curve)
+	verify {
+		assert_eq!(Mode::<T>::get(), PayoutMode::Curve);
+	}
+
+	queue_era {
+		let v in 0 .. 1_000;
+		let era = 0;
+		T::Staking::add_era_validators(era, v);
+	}: {
+		Pallet::<T>::queue_era(era);
+	}
+	verify {
+		assert_eq!(Pallet::<T>::pending_payouts(), v);
+	}
+
+	// The payout of a validator that is not in the era fails and is queued again, the most the
+	// pallet does around a payout.
+	process_payout {
+		let validator: T::AccountId = account("validator", 0, 0);
+		PendingPayouts::<T>::insert((validator.clone(), 0), 0);
+	}: {
+		Pallet::<T>::process_payouts(Weight::MAX);
+	}
+	verify {
+		assert_eq!(PendingPayouts::<T>::get((validator, 0)), Some(1));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,443 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Staking Payout
+//!
+//! Offers a second way to compute the era payouts next to the pre-funded pot of
+//! `ternoa_staking_rewards`.
+//!
+//! In [`PayoutMode::Pot`] mode, which is the default, the payout is entirely delegated to
+//! [`Config::PotPayout`]. In [`PayoutMode::Curve`] mode, the payout is computed from
+//! [`Config::RewardCurve`], based on the staked ratio and the total issuance, and is minted by
+//! the staking pallet. The pot pays the shortfall whenever the curve yields less to the stakers
+//! than the pot would, so switching to the curve never lowers their rewards.
+//!
+//! The pallet implements [`EraPayout`] and is meant to be used as
+//! `pallet_staking::Config::EraPayout`.
+//!
+//! The stakers are also paid out automatically. Once an era has ended, a payout is queued for
+//! every validator that earned reward points during it and was not paid out yet, and the queue is
+//! processed by `on_idle` within the weight left in the blocks. Stakers can still call
+//! `payout_stakers` themselves, the queued payouts that were claimed in the meantime are simply
+//! dropped. A payout that fails without having been claimed is queued again, up to
+//! [`Config::MaxPayoutAttempts`] times. The eras that ended before the pallet was deployed can be
+//! queued with [`Pallet::queue_era`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
+use frame_support::{pallet_prelude::*, traits::Currency};
+use frame_system::pallet_prelude::*;
+use pallet_staking::{inflation::compute_total_payout, EraPayout};
+use sp_runtime::{
+	curve::PiecewiseLinear,
+	traits::{Saturating, Zero},
+};
+use sp_staking::EraIndex;
+use sp_std::{ops::Range, prelude::*};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// How the era payouts are computed.
+#[derive(
+	Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub enum PayoutMode {
+	/// The payouts are taken from the staking rewards pot.
+	#[default]
+	Pot,
+	/// The payouts are minted following the reward curve.
+	Curve,
+}
+
+/// Staking operations needed to pay the stakers out automatically.
+pub trait StakingPayouts<AccountId> {
+	/// Index of the active era, if any.
+	fn active_era() -> Option<EraIndex>;
+
+	/// Validators that earned reward points during the era.
+	fn era_validators(era: EraIndex) -> Vec<AccountId>;
+
+	/// Pays out the validator and its nominators for the era.
+	fn payout_stakers(validator: &AccountId, era: EraIndex) -> DispatchResult;
+
+	/// Eras for which the validator was already paid out.
+	fn claimed_eras(validator: &AccountId) -> Vec<EraIndex>;
+
+	/// Validators currently nominated by the nominator.
+	fn nomination_targets(nominator: &AccountId) -> Vec<AccountId>;
+
+	/// Whether the nominator is rewarded for the era through the validator.
+	fn is_rewarded(nominator: &AccountId, validator: &AccountId, era: EraIndex) -> bool;
+
+	/// Number of ended eras whose rewards can still be claimed.
+	fn history_depth() -> EraIndex;
+
+	/// Worst case weight of [`Self::payout_stakers`].
+	fn payout_weight() -> Weight;
+
+	/// Adds `count` bonded validators to the reward points of the era and returns them.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add_era_validators(era: EraIndex, count: u32) -> Vec<AccountId>;
+}
+
+impl<T: pallet_staking::Config> StakingPayouts<T::AccountId> for pallet_staking::Pallet<T> {
+	fn active_era() -> Option<EraIndex> {
+		pallet_staking::ActiveEra::<T>::get().map(|info| info.index)
+	}
+
+	fn era_validators(era: EraIndex) -> Vec<T::AccountId> {
+		pallet_staking::ErasRewardPoints::<T>::get(era).individual.into_keys().collect()
+	}
+
+	fn payout_stakers(validator: &T::AccountId, era: EraIndex) -> DispatchResult {
+		// Anyone can trigger a payout, the validator is used as the origin.
+		let origin = frame_system::RawOrigin::Signed(validator.clone()).into();
+		pallet_staking::Pallet::<T>::payout_stakers(origin, validator.clone(), era)
+			.map(|_| ())
+			.map_err(|e| e.error)
+	}
+
+	fn claimed_eras(validator: &T::AccountId) -> Vec<EraIndex> {
+		pallet_staking::Bonded::<T>::get(validator)
+			.and_then(pallet_staking::Ledger::<T>::get)
+			.map(|ledger| ledger.claimed_rewards.into_iter().collect())
+			.unwrap_or_default()
+	}
+
+	fn nomination_targets(nominator: &T::AccountId) -> Vec<T::AccountId> {
+		pallet_staking::Nominators::<T>::get(nominator)
+			.map(|nominations| nominations.targets.into_inner())
+			.unwrap_or_default()
+	}
+
+	fn is_rewarded(nominator: &T::AccountId, validator: &T::AccountId, era: EraIndex) -> bool {
+		// Only the nominators kept in the clipped exposures are rewarded.
+		pallet_staking::ErasStakersClipped::<T>::get(era, validator)
+			.others
+			.iter()
+			.any(|other| &other.who == nominator)
+	}
+
+	fn history_depth() -> EraIndex {
+		T::HistoryDepth::get()
+	}
+
+	fn payout_weight() -> Weight {
+		use pallet_staking::WeightInfo as _;
+		<T as pallet_staking::Config>::WeightInfo::payout_stakers_alive_staked(
+			T::MaxNominatorRewardedPerValidator::get(),
+		)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add_era_validators(era: EraIndex, count: u32) -> Vec<T::AccountId> {
+		use pallet_staking::{testing_utils::create_stash_controller, RewardDestination};
+
+		let validators: Vec<_> = (0..count)
+			.map(|n| {
+				create_stash_controller::<T>(n, 100, RewardDestination::Staked)
+					.expect("The stash can be bonded")
+					.0
+			})
+			.collect();
+		pallet_staking::ErasRewardPoints::<T>::mutate(era, |points| {
+			for validator in &validators {
+				points.individual.insert(validator.clone(), 1);
+				points.total += 1;
+			}
+		});
+
+		validators
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Currency of the stakers.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Origin allowed to change the payout mode.
+		type ExternalOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Yearly inflation curve used in [`PayoutMode::Curve`] mode.
+		type RewardCurve: Get<&'static PiecewiseLinear<'static>>;
+
+		/// Payout from the pre-funded pot, used in [`PayoutMode::Pot`] mode and to pay the
+		/// shortfall of the curve in [`PayoutMode::Curve`] mode.
+		type PotPayout: EraPayout<BalanceOf<Self>>;
+
+		/// Staking pallet whose stakers are paid out automatically.
+		type Staking: StakingPayouts<Self::AccountId>;
+
+		/// Maximum number of queued payouts processed in a block.
+		#[pallet::constant]
+		type MaxPayoutsPerBlock: Get<u32>;
+
+		/// Number of times a payout is tried before it is dropped.
+		#[pallet::constant]
+		type MaxPayoutAttempts: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Current payout mode.
+	#[pallet::storage]
+	#[pallet::getter(fn payout_mode)]
+	pub type Mode<T: Config> = StorageValue<_, PayoutMode, ValueQuery>;
+
+	/// Last era whose payouts were queued.
+	#[pallet::storage]
+	#[pallet::getter(fn last_queued_era)]
+	pub type LastQueuedEra<T: Config> = StorageValue<_, EraIndex, OptionQuery>;
+
+	/// Payouts waiting to be processed, by validator and era, with the number of failed attempts.
+	#[pallet::storage]
+	pub type PendingPayouts<T: Config> =
+		CountedStorageMap<_, Blake2_128Concat, (T::AccountId, EraIndex), u32, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The payout mode was changed.
+		PayoutModeChanged { mode: PayoutMode },
+		/// The payouts of the validators of an era that were not paid out yet were queued.
+		PayoutsQueued { era: EraIndex, validators: u32 },
+		/// A validator and its nominators were paid out for an era.
+		PayoutProcessed { era: EraIndex, validator: T::AccountId },
+		/// The payout of a validator failed and was queued again.
+		PayoutRequeued { era: EraIndex, validator: T::AccountId, error: DispatchError },
+		/// The payout of a validator failed and was dropped, because it was claimed in the
+		/// meantime or failed too many times.
+		PayoutFailed { era: EraIndex, validator: T::AccountId, error: DispatchError },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			Self::queue_payouts()
+		}
+
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::process_payouts(remaining_weight)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Changes how the next era payouts are computed.
+		#[pallet::weight(T::WeightInfo::set_payout_mode())]
+		pub fn set_payout_mode(origin: OriginFor<T>, mode: PayoutMode) -> DispatchResult {
+			T::ExternalOrigin::ensure_origin(origin)?;
+
+			Mode::<T>::put(mode);
+			Self::deposit_event(Event::PayoutModeChanged { mode });
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Queues the payouts of the last ended era, if it was not queued yet.
+	fn queue_payouts() -> Weight {
+		let weight = T::DbWeight::get().reads(2);
+		let era = match T::Staking::active_era().and_then(|active| active.checked_sub(1)) {
+			Some(era) => era,
+			None => return weight,
+		};
+		if LastQueuedEra::<T>::get().map_or(false, |last| last >= era) {
+			return weight
+		}
+
+		LastQueuedEra::<T>::put(era);
+
+		weight
+			.saturating_add(Self::queue_era(era))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	/// Queues the payouts of the validators of the era that were not paid out yet, and returns
+	/// the weight used.
+	///
+	/// Queuing an era twice is harmless, the payouts already queued stay as they are.
+	pub fn queue_era(era: EraIndex) -> Weight {
+		let validators = T::Staking::era_validators(era);
+
+		let mut queued = 0;
+		for validator in &validators {
+			// Validators paid out by hand would only make the payout fail.
+			if T::Staking::claimed_eras(validator).contains(&era) {
+				continue
+			}
+			let key = (validator.clone(), era);
+			if !PendingPayouts::<T>::contains_key(&key) {
+				PendingPayouts::<T>::insert(key, 0);
+			}
+			queued += 1;
+		}
+
+		Self::deposit_event(Event::PayoutsQueued { era, validators: queued });
+
+		T::WeightInfo::queue_era(validators.len() as u32)
+	}
+
+	/// Ended eras whose rewards can still be claimed, oldest first.
+	pub fn history_eras() -> Range<EraIndex> {
+		let active = T::Staking::active_era().unwrap_or_default();
+		active.saturating_sub(T::Staking::history_depth())..active
+	}
+
+	/// Processes queued payouts as long as they fit in the remaining weight.
+	fn process_payouts(remaining_weight: Weight) -> Weight {
+		let payout_weight =
+			T::Staking::payout_weight().saturating_add(T::WeightInfo::process_payout());
+
+		let mut used_weight = Weight::zero();
+		let mut queue = PendingPayouts::<T>::drain();
+		for _ in 0..T::MaxPayoutsPerBlock::get() {
+			if !used_weight.saturating_add(payout_weight).all_lte(remaining_weight) {
+				break
+			}
+
+			let ((validator, era), failures) = match queue.next() {
+				Some(payout) => payout,
+				None => break,
+			};
+			used_weight.saturating_accrue(payout_weight);
+
+			let error = match T::Staking::payout_stakers(&validator, era) {
+				Ok(()) => {
+					Self::deposit_event(Event::PayoutProcessed { era, validator });
+					continue
+				},
+				Err(error) => error,
+			};
+
+			// The drained iterator goes on after the key, a payout queued again waits for the
+			// next block.
+			let failures = failures.saturating_add(1);
+			let claimed = T::Staking::claimed_eras(&validator).contains(&era);
+			if !claimed && failures < T::MaxPayoutAttempts::get() {
+				PendingPayouts::<T>::insert((validator.clone(), era), failures);
+				Self::deposit_event(Event::PayoutRequeued { era, validator, error });
+			} else {
+				Self::deposit_event(Event::PayoutFailed { era, validator, error });
+			}
+		}
+
+		used_weight
+	}
+
+	/// Eras for which the payout of the validator is still queued, oldest first.
+	pub fn pending_eras(validator: &T::AccountId) -> Vec<EraIndex> {
+		Self::history_eras()
+			.filter(|era| PendingPayouts::<T>::contains_key((validator, *era)))
+			.collect()
+	}
+
+	/// Eras of the staking history for which the nominator is rewarded, split between the ones
+	/// with a validator left to pay out and the ones fully paid out, oldest first.
+	///
+	/// Only the validators the nominator currently nominates are looked at, the eras it was
+	/// rewarded for through other validators are left out.
+	pub fn nominator_eras(nominator: &T::AccountId) -> (Vec<EraIndex>, Vec<EraIndex>) {
+		let targets: Vec<_> = T::Staking::nomination_targets(nominator)
+			.into_iter()
+			.map(|validator| {
+				let claimed = T::Staking::claimed_eras(&validator);
+				(validator, claimed)
+			})
+			.collect();
+
+		let mut pending = Vec::new();
+		let mut paid = Vec::new();
+		for era in Self::history_eras() {
+			let mut rewarded = targets
+				.iter()
+				.filter(|(validator, _)| T::Staking::is_rewarded(nominator, validator, era))
+				.peekable();
+			if rewarded.peek().is_none() {
+				continue
+			}
+
+			if rewarded.all(|(_, claimed)| claimed.contains(&era)) {
+				paid.push(era);
+			} else {
+				pending.push(era);
+			}
+		}
+
+		(pending, paid)
+	}
+
+	/// Number of payouts still queued.
+	pub fn pending_payouts() -> u32 {
+		PendingPayouts::<T>::count()
+	}
+}
+impl<T: Config> EraPayout<BalanceOf<T>> for Pallet<T> {
+	fn era_payout(
+		total_staked: BalanceOf<T>,
+		total_issuance: BalanceOf<T>,
+		era_duration_millis: u64,
+	) -> (BalanceOf<T>, BalanceOf<T>) {
+		if Mode::<T>::get() == PayoutMode::Curve {
+			let (validator_payout, max_payout) = compute_total_payout(
+				T::RewardCurve::get(),
+				total_staked,
+				total_issuance,
+				era_duration_millis,
+			);
+
+			let (pot_payout, _) =
+				T::PotPayout::era_payout(total_staked, total_issuance, era_duration_millis);
+
+			// The pot pays the shortfall, the remainder is the one of the curve.
+			let shortfall = pot_payout.saturating_sub(validator_payout);
+			return (
+				validator_payout.saturating_add(shortfall),
+				max_payout.saturating_sub(validator_payout),
+			)
+		}
+
+		T::PotPayout::era_payout(total_staked, total_issuance, era_duration_millis)
+	}
+}
@@ -0,0 +1,255 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use crate as ternoa_staking_payout;
+
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, ConstU64},
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use std::{cell::RefCell, collections::BTreeMap};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub const ALICE: u64 = 1;
+
+pub const VALIDATOR_A: u64 = 10;
+pub const VALIDATOR_B: u64 = 11;
+pub const VALIDATOR_C: u64 = 12;
+
+pub const NOMINATOR: u64 = 20;
+
+/// Number of ended eras whose rewards can still be claimed.
+pub const HISTORY_DEPTH: EraIndex = 4;
+
+/// Payout returned by the pot, whatever the inputs.
+pub const POT_PAYOUT: (u64, u64) = (1_000, 0);
+
+/// Weight of a single payout of the mocked staking pallet.
+pub const PAYOUT_WEIGHT: u64 = 100;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		StakingPayout: ternoa_staking_payout,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type RuntimeCall = RuntimeCall;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+
+pallet_staking_reward_curve::build! {
+	const REWARD_CURVE: PiecewiseLinear<'static> = curve!(
+		min_inflation: 0_025_000,
+		max_inflation: 0_100_000,
+		ideal_stake: 0_500_000,
+		falloff: 0_050_000,
+		max_piece_count: 40,
+		test_precision: 0_005_000,
+	);
+}
+
+parameter_types! {
+	pub const RewardCurve: &'static PiecewiseLinear<'static> = &REWARD_CURVE;
+}
+
+pub struct PotPayout;
+impl EraPayout<u64> for PotPayout {
+	fn era_payout(_: u64, _: u64, _: u64) -> (u64, u64) {
+		POT_PAYOUT
+	}
+}
+
+/// State of the mocked staking pallet.
+#[derive(Default)]
+pub struct MockStaking {
+	pub active_era: Option<EraIndex>,
+	pub era_validators: BTreeMap<EraIndex, Vec<u64>>,
+	pub claimed: Vec<(u64, EraIndex)>,
+	/// Nominator, validator and era of the rewarded nominations.
+	pub nominations: Vec<(u64, u64, EraIndex)>,
+	/// Validators currently nominated, by nominator.
+	pub targets: BTreeMap<u64, Vec<u64>>,
+}
+
+thread_local! {
+	pub static STAKING: RefCell<MockStaking> = RefCell::new(MockStaking::default());
+}
+
+pub struct Staking;
+impl Staking {
+	pub fn start_era(era: EraIndex, validators: Vec<u64>) {
+		STAKING.with(|s| {
+			let mut s = s.borrow_mut();
+			s.active_era = Some(era + 1);
+			s.era_validators.insert(era, validators);
+		});
+	}
+
+	pub fn nominate(nominator: u64, validator: u64, era: EraIndex) {
+		STAKING.with(|s| {
+			let mut s = s.borrow_mut();
+			s.nominations.push((nominator, validator, era));
+			let targets = s.targets.entry(nominator).or_default();
+			if !targets.contains(&validator) {
+				targets.push(validator);
+			}
+		});
+	}
+
+	pub fn stop_nominating(nominator: u64, validator: u64) {
+		STAKING.with(|s| {
+			if let Some(targets) = s.borrow_mut().targets.get_mut(&nominator) {
+				targets.retain(|target| *target != validator);
+			}
+		});
+	}
+}
+
+impl StakingPayouts<u64> for Staking {
+	fn active_era() -> Option<EraIndex> {
+		STAKING.with(|s| s.borrow().active_era)
+	}
+
+	fn era_validators(era: EraIndex) -> Vec<u64> {
+		STAKING.with(|s| s.borrow().era_validators.get(&era).cloned().unwrap_or_default())
+	}
+
+	fn payout_stakers(validator: &u64, era: EraIndex) -> DispatchResult {
+		STAKING.with(|s| {
+			let mut s = s.borrow_mut();
+			if !s.era_validators.get(&era).map_or(false, |v| v.contains(validator)) {
+				return Err(DispatchError::Other("NotValidator"))
+			}
+			if s.claimed.contains(&(*validator, era)) {
+				return Err(DispatchError::Other("AlreadyClaimed"))
+			}
+			s.claimed.push((*validator, era));
+			Ok(())
+		})
+	}
+
+	fn claimed_eras(validator: &u64) -> Vec<EraIndex> {
+		STAKING.with(|s| {
+			s.borrow()
+				.claimed
+				.iter()
+				.filter(|(v, _)| v == validator)
+				.map(|(_, e)| *e)
+				.collect()
+		})
+	}
+
+	fn nomination_targets(nominator: &u64) -> Vec<u64> {
+		STAKING.with(|s| s.borrow().targets.get(nominator).cloned().unwrap_or_default())
+	}
+
+	fn is_rewarded(nominator: &u64, validator: &u64, era: EraIndex) -> bool {
+		STAKING.with(|s| s.borrow().nominations.contains(&(*nominator, *validator, era)))
+	}
+
+	fn history_depth() -> EraIndex {
+		HISTORY_DEPTH
+	}
+
+	fn payout_weight() -> Weight {
+		Weight::from_ref_time(PAYOUT_WEIGHT)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add_era_validators(era: EraIndex, count: u32) -> Vec<u64> {
+		let validators: Vec<u64> = (0..count as u64).map(|n| 1_000 + n).collect();
+		STAKING.with(|s| {
+			s.borrow_mut()
+				.era_validators
+				.entry(era)
+				.or_default()
+				.extend(validators.iter().copied())
+		});
+		validators
+	}
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type ExternalOrigin = EnsureRoot<u64>;
+	type RewardCurve = RewardCurve;
+	type PotPayout = PotPayout;
+	type Staking = Staking;
+	type MaxPayoutsPerBlock = ConstU32<3>;
+	type MaxPayoutAttempts = ConstU32<2>;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	STAKING.with(|s| *s.borrow_mut() = MockStaking::default());
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,390 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError::BadOrigin;
+
+/// Six hours, in milliseconds.
+const ERA_DURATION: u64 = 6 * 60 * 60 * 1_000;
+const TOTAL_ISSUANCE: u64 = 1_000_000_000_000;
+
+/// Weight of `payouts` automatic payouts.
+fn payouts_weight(payouts: u64) -> Weight {
+	Weight::from_ref_time(PAYOUT_WEIGHT)
+		.saturating_add(<() as WeightInfo>::process_payout())
+		.saturating_mul(payouts)
+}
+
+mod set_payout_mode {
+	use super::*;
+
+	#[test]
+	fn set_payout_mode() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(StakingPayout::payout_mode(), PayoutMode::Pot);
+			assert_ok!(StakingPayout::set_payout_mode(RuntimeOrigin::root(), PayoutMode::Curve));
+
+			assert_eq!(StakingPayout::payout_mode(), PayoutMode::Curve);
+			System::assert_last_event(RuntimeEvent::StakingPayout(Event::PayoutModeChanged {
+				mode: PayoutMode::Curve,
+			}));
+		})
+	}
+
+	#[test]
+	fn bad_origin() {
+		new_test_ext().execute_with(|| {
+			let err =
+				StakingPayout::set_payout_mode(RuntimeOrigin::signed(ALICE), PayoutMode::Curve);
+			assert_noop!(err, BadOrigin);
+		})
+	}
+}
+
+mod era_payout {
+	use super::*;
+
+	#[test]
+	fn pot_mode() {
+		new_test_ext().execute_with(|| {
+			let payout =
+				StakingPayout::era_payout(TOTAL_ISSUANCE / 2, TOTAL_ISSUANCE, ERA_DURATION);
+			assert_eq!(payout, POT_PAYOUT);
+		})
+	}
+
+	#[test]
+	fn curve_mode() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(StakingPayout::set_payout_mode(RuntimeOrigin::root(), PayoutMode::Curve));
+
+			let (validators, remainder) =
+				StakingPayout::era_payout(TOTAL_ISSUANCE / 2, TOTAL_ISSUANCE, ERA_DURATION);
+			let expected = compute_total_payout(
+				&REWARD_CURVE,
+				TOTAL_ISSUANCE / 2,
+				TOTAL_ISSUANCE,
+				ERA_DURATION,
+			);
+			assert_eq!(validators, expected.0);
+			assert_eq!(validators + remainder, expected.1);
+			assert!(validators > 0);
+		})
+	}
+
+	#[test]
+	fn curve_mode_falls_back_to_pot() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(StakingPayout::set_payout_mode(RuntimeOrigin::root(), PayoutMode::Curve));
+
+			// Nothing to mint when there is no issuance.
+			let payout = StakingPayout::era_payout(0, 0, ERA_DURATION);
+			assert_eq!(payout, POT_PAYOUT);
+		})
+	}
+
+	#[test]
+	fn pot_pays_the_curve_shortfall() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(StakingPayout::set_payout_mode(RuntimeOrigin::root(), PayoutMode::Curve));
+
+			// The curve yields less than the pot with a small issuance.
+			let issuance = 5_000_000;
+			let (curve_payout, max_payout) =
+				compute_total_payout(&REWARD_CURVE, issuance / 2, issuance, ERA_DURATION);
+			assert!(curve_payout > 0 && curve_payout < POT_PAYOUT.0);
+
+			let (validators, remainder) =
+				StakingPayout::era_payout(issuance / 2, issuance, ERA_DURATION);
+			assert_eq!(validators, POT_PAYOUT.0);
+			assert_eq!(remainder, max_payout - curve_payout);
+		})
+	}
+}
+
+mod queue_payouts {
+	use super::*;
+
+	#[test]
+	fn queue_payouts() {
+		new_test_ext().execute_with(|| {
+			Staking::start_era(1, vec![VALIDATOR_A, VALIDATOR_B]);
+			StakingPayout::on_initialize(1);
+
+			assert_eq!(StakingPayout::last_queued_era(), Some(1));
+			assert_eq!(StakingPayout::pending_payouts(), 2);
+			assert_eq!(StakingPayout::pending_eras(&VALIDATOR_A), vec![1]);
+			assert_eq!(StakingPayout::pending_eras(&VALIDATOR_B), vec![1]);
+			System::assert_last_event(RuntimeEvent::StakingPayout(Event::PayoutsQueued {
+				era: 1,
+				validators: 2,
+			}));
+		})
+	}
+
+	#[test]
+	fn era_is_queued_once() {
+		new_test_ext().execute_with(|| {
+			Staking::start_era(1, vec![VALIDATOR_A]);
+			StakingPayout::on_initialize(1);
+			StakingPayout::on_idle(1, payouts_weight(1));
+			StakingPayout::on_initialize(2);
+
+			assert_eq!(StakingPayout::pending_payouts(), 0);
+		})
+	}
+
+	#[test]
+	fn claimed_validators_are_not_queued() {
+		new_test_ext().execute_with(|| {
+			Staking::start_era(1, vec![VALIDATOR_A, VALIDATOR_B]);
+			assert_ok!(Staking::payout_stakers(&VALIDATOR_A, 1));
+			StakingPayout::on_initialize(1);
+
+			assert_eq!(StakingPayout::pending_eras(&VALIDATOR_A), Vec::<EraIndex>::new());
+			assert_eq!(StakingPayout::pending_eras(&VALIDATOR_B), vec![1]);
+			System::assert_last_event(RuntimeEvent::StakingPayout(Event::PayoutsQueued {
+				era: 1,
+				validators: 1,
+			}));
+
+			StakingPayout::on_idle(1, payouts_weight(10));
+			System::assert_last_event(RuntimeEvent::StakingPayout(Event::PayoutProcessed {
+				era: 1,
+				validator: VALIDATOR_B,
+			}));
+		})
+	}
+
+	#[test]
+	fn past_eras_can_be_queued() {
+		new_test_ext().execute_with(|| {
+			Staking::start_era(1, vec![VALIDATOR_A]);
+			Staking::start_era(2, vec![VALIDATOR_A, VALIDATOR_B]);
+			assert_ok!(Staking::payout_stakers(&VALIDATOR_B, 2));
+			StakingPayout::on_initialize(1);
+			assert_eq!(StakingPayout::pending_eras(&VALIDATOR_A), vec![2]);
+
+			for era in StakingPayout::history_eras() {
+				StakingPayout::queue_era(era);
+			}
+
+			assert_eq!(StakingPayout::pending_eras(&VALIDATOR_A), vec![1, 2]);
+			assert_eq!(StakingPayout::pending_eras(&VALIDATOR_B), Vec::<EraIndex>::new());
+			assert_eq!(StakingPayout::pending_payouts(), 2);
+		})
+	}
+
+	#[test]
+	fn history_eras() {
+		new_test_ext().execute_with(|| {
+			assert!(StakingPayout::history_eras().is_empty());
+
+			Staking::start_era(2, vec![]);
+			assert_eq!(StakingPayout::history_eras(), 0..3);
+
+			Staking::start_era(9, vec![]);
+			assert_eq!(StakingPayout::history_eras(), 6..10);
+		})
+	}
+
+	#[test]
+	fn nothing_to_queue_during_first_era() {
+		new_test_ext().execute_with(|| {
+			StakingPayout::on_initialize(1);
+			STAKING.with(|s| s.borrow_mut().active_era = Some(0));
+			StakingPayout::on_initialize(2);
+
+			assert_eq!(StakingPayout::last_queued_era(), None);
+			assert_eq!(StakingPayout::pending_payouts(), 0);
+		})
+	}
+}
+
+mod process_payouts {
+	use super::*;
+
+	#[test]
+	fn process_payouts() {
+		new_test_ext().execute_with(|| {
+			Staking::start_era(1, vec![VALIDATOR_A, VALIDATOR_B]);
+			StakingPayout::on_initialize(1);
+
+			let weight = StakingPayout::on_idle(1, payouts_weight(10));
+
+			assert_eq!(weight, payouts_weight(2));
+			assert_eq!(StakingPayout::pending_payouts(), 0);
+			assert_eq!(Staking::claimed_eras(&VALIDATOR_A), vec![1]);
+			assert_eq!(Staking::claimed_eras(&VALIDATOR_B), vec![1]);
+			System::assert_has_event(RuntimeEvent::StakingPayout(Event::PayoutProcessed {
+				era: 1,
+				validator: VALIDATOR_A,
+			}));
+		})
+	}
+
+	#[test]
+	fn within_remaining_weight() {
+		new_test_ext().execute_with(|| {
+			Staking::start_era(1, vec![VALIDATOR_A, VALIDATOR_B]);
+			StakingPayout::on_initialize(1);
+
+			let remaining = payouts_weight(2).saturating_sub(Weight::from_ref_time(1));
+			let weight = StakingPayout::on_idle(1, remaining);
+			assert_eq!(weight, payouts_weight(1));
+			assert_eq!(StakingPayout::pending_payouts(), 1);
+
+			let remaining = payouts_weight(1).saturating_sub(Weight::from_ref_time(1));
+			let weight = StakingPayout::on_idle(2, remaining);
+			assert_eq!(weight, Weight::zero());
+			assert_eq!(StakingPayout::pending_payouts(), 1);
+		})
+	}
+
+	#[test]
+	fn within_max_payouts_per_block() {
+		new_test_ext().execute_with(|| {
+			Staking::start_era(1, vec![VALIDATOR_A, VALIDATOR_B, VALIDATOR_C, 13, 14]);
+			StakingPayout::on_initialize(1);
+
+			StakingPayout::on_idle(1, payouts_weight(10));
+			assert_eq!(StakingPayout::pending_payouts(), 2);
+
+			StakingPayout::on_idle(2, payouts_weight(10));
+			assert_eq!(StakingPayout::pending_payouts(), 0);
+		})
+	}
+
+	#[test]
+	fn already_claimed() {
+		new_test_ext().execute_with(|| {
+			Staking::start_era(1, vec![VALIDATOR_A]);
+			StakingPayout::on_initialize(1);
+			assert_ok!(Staking::payout_stakers(&VALIDATOR_A, 1));
+
+			StakingPayout::on_idle(1, payouts_weight(1));
+
+			assert_eq!(StakingPayout::pending_payouts(), 0);
+			System::assert_last_event(RuntimeEvent::StakingPayout(Event::PayoutFailed {
+				era: 1,
+				validator: VALIDATOR_A,
+				error: DispatchError::Other("AlreadyClaimed"),
+			}));
+		})
+	}
+
+	#[test]
+	fn failed_payout_is_requeued() {
+		new_test_ext().execute_with(|| {
+			Staking::start_era(1, vec![VALIDATOR_A]);
+			StakingPayout::on_initialize(1);
+			STAKING.with(|s| s.borrow_mut().era_validators.insert(1, vec![]));
+
+			// The payout is tried once per block.
+			StakingPayout::on_idle(1, payouts_weight(10));
+			assert_eq!(StakingPayout::pending_eras(&VALIDATOR_A), vec![1]);
+			System::assert_last_event(RuntimeEvent::StakingPayout(Event::PayoutRequeued {
+				era: 1,
+				validator: VALIDATOR_A,
+				error: DispatchError::Other("NotValidator"),
+			}));
+
+			// And dropped after `MaxPayoutAttempts` failures.
+			StakingPayout::on_idle(2, payouts_weight(10));
+			assert_eq!(StakingPayout::pending_payouts(), 0);
+			System::assert_last_event(RuntimeEvent::StakingPayout(Event::PayoutFailed {
+				era: 1,
+				validator: VALIDATOR_A,
+				error: DispatchError::Other("NotValidator"),
+			}));
+		})
+	}
+
+	#[test]
+	fn requeued_payout_can_succeed() {
+		new_test_ext().execute_with(|| {
+			Staking::start_era(1, vec![VALIDATOR_A]);
+			StakingPayout::on_initialize(1);
+			STAKING.with(|s| s.borrow_mut().era_validators.insert(1, vec![]));
+			StakingPayout::on_idle(1, payouts_weight(10));
+
+			STAKING.with(|s| s.borrow_mut().era_validators.insert(1, vec![VALIDATOR_A]));
+			StakingPayout::on_idle(2, payouts_weight(10));
+
+			assert_eq!(StakingPayout::pending_payouts(), 0);
+			assert_eq!(Staking::claimed_eras(&VALIDATOR_A), vec![1]);
+			System::assert_last_event(RuntimeEvent::StakingPayout(Event::PayoutProcessed {
+				era: 1,
+				validator: VALIDATOR_A,
+			}));
+		})
+	}
+}
+
+mod nominator_eras {
+	use super::*;
+
+	#[test]
+	fn nominator_eras() {
+		new_test_ext().execute_with(|| {
+			Staking::start_era(1, vec![VALIDATOR_A, VALIDATOR_B]);
+			Staking::start_era(2, vec![VALIDATOR_A, VALIDATOR_B]);
+			Staking::start_era(3, vec![VALIDATOR_A]);
+			Staking::nominate(NOMINATOR, VALIDATOR_A, 1);
+			Staking::nominate(NOMINATOR, VALIDATOR_A, 2);
+			Staking::nominate(NOMINATOR, VALIDATOR_B, 2);
+			Staking::nominate(NOMINATOR, VALIDATOR_A, 3);
+
+			assert_ok!(Staking::payout_stakers(&VALIDATOR_A, 1));
+			assert_ok!(Staking::payout_stakers(&VALIDATOR_A, 2));
+
+			// Era 2 is only paid out through one of the validators.
+			let (pending, paid) = StakingPayout::nominator_eras(&NOMINATOR);
+			assert_eq!(pending, vec![2, 3]);
+			assert_eq!(paid, vec![1]);
+		})
+	}
+
+	#[test]
+	fn out_of_history() {
+		new_test_ext().execute_with(|| {
+			Staking::start_era(1, vec![VALIDATOR_A]);
+			Staking::nominate(NOMINATOR, VALIDATOR_A, 1);
+			Staking::start_era(1 + HISTORY_DEPTH, vec![]);
+
+			let (pending, paid) = StakingPayout::nominator_eras(&NOMINATOR);
+			assert!(pending.is_empty());
+			assert!(paid.is_empty());
+		})
+	}
+
+	#[test]
+	fn only_current_targets() {
+		new_test_ext().execute_with(|| {
+			Staking::start_era(1, vec![VALIDATOR_A]);
+			Staking::start_era(2, vec![VALIDATOR_B]);
+			Staking::nominate(NOMINATOR, VALIDATOR_A, 1);
+			Staking::nominate(NOMINATOR, VALIDATOR_B, 2);
+			Staking::stop_nominating(NOMINATOR, VALIDATOR_B);
+
+			let (pending, paid) = StakingPayout::nominator_eras(&NOMINATOR);
+			assert_eq!(pending, vec![1]);
+			assert!(paid.is_empty());
+		})
+	}
+}
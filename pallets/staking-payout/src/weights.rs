@@ -0,0 +1,56 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::weights::{constants::RocksDbWeight, Weight};
+
+/// Weight functions needed for `ternoa_staking_payout`.
+pub trait WeightInfo {
+	fn set_payout_mode() -> Weight;
+	fn queue_era(v: u32) -> Weight;
+	fn process_payout() -> Weight;
+}
+
+// For tests
+impl WeightInfo for () {
+	// Storage: StakingPayout Mode (r:0 w:1)
+	fn set_payout_mode() -> Weight {
+		Weight::from_ref_time(44_664_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Staking ErasRewardPoints (r:1 w:0)
+	// Storage: Staking Bonded (r:1 w:0)
+	// Storage: Staking Ledger (r:1 w:0)
+	// Storage: StakingPayout PendingPayouts (r:1 w:1)
+	// Storage: StakingPayout CounterForPendingPayouts (r:1 w:1)
+	fn queue_era(v: u32) -> Weight {
+		Weight::from_ref_time(16_432_000 as u64)
+			.saturating_add(Weight::from_ref_time(21_740_000 as u64).saturating_mul(v as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((4 as u64).saturating_mul(v as u64)))
+			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(v as u64)))
+	}
+	// Storage: StakingPayout PendingPayouts (r:2 w:2)
+	// Storage: StakingPayout CounterForPendingPayouts (r:1 w:1)
+	// Storage: Staking Bonded (r:1 w:0)
+	// Storage: Staking Ledger (r:1 w:0)
+	fn process_payout() -> Weight {
+		Weight::from_ref_time(38_915_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+}
@@ -0,0 +1,51 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use sp_std::vec;
+
+/// Returns a name of the maximum allowed length, which no runtime pallet uses.
+fn name<T: Config>(byte: u8) -> BoundedVec<u8, T::MaxNameLen> {
+	vec![byte; T::MaxNameLen::get() as usize].try_into().unwrap()
+}
+
+benchmarks! {
+	pause {
+		let origin = T::PauseOrigin::successful_origin();
+		let pallet_name = name::<T>(b'p');
+		let call_name = Some(name::<T>(b'c'));
+		let full_name = (pallet_name.clone(), call_name.clone());
+	}: _<T::RuntimeOrigin>(origin, pallet_name, call_name)
+	verify {
+		assert!(PausedCalls::<T>::contains_key(full_name));
+	}
+
+	unpause {
+		let origin = T::PauseOrigin::successful_origin();
+		let pallet_name = name::<T>(b'p');
+		let call_name = Some(name::<T>(b'c'));
+		let full_name = (pallet_name.clone(), call_name.clone());
+		PausedCalls::<T>::insert(&full_name, ());
+	}: _<T::RuntimeOrigin>(origin, pallet_name, call_name)
+	verify {
+		assert!(!PausedCalls::<T>::contains_key(full_name));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,187 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Tx Pause
+//!
+//! Lets governance disable a whole pallet or a single call of a pallet without a runtime upgrade,
+//! for instance while a bug found in a pallet is being fixed.
+//!
+//! Calls are identified by the pallet name used in `construct_runtime!` and by the call name, as
+//! given by [`GetCallMetadata`]. The pallet implements [`Contains`] over the runtime calls so that
+//! it can be used directly as `frame_system::Config::BaseCallFilter`. Calls dispatched with the
+//! root origin are not filtered.
+//!
+//! Pallets and calls matched by [`Config::UnpausableCalls`] can never be paused, this is meant to
+//! protect the consensus critical and governance pallets, so that governance can always undo a
+//! pause.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::{CallMetadata, Contains, GetCallMetadata},
+};
+use frame_system::pallet_prelude::*;
+use sp_std::prelude::*;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// Name of a pallet as used in `construct_runtime!`.
+pub type PalletNameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLen>;
+/// Name of a call of a pallet.
+pub type CallNameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLen>;
+/// A whole pallet when the call name is `None`, a single call of a pallet otherwise.
+pub type FullNameOf<T> = (PalletNameOf<T>, Option<CallNameOf<T>>);
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The overarching call type.
+		type RuntimeCall: Parameter + GetCallMetadata;
+
+		/// Origin allowed to pause and unpause pallets and calls.
+		type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Pallets and calls that can never be paused.
+		type UnpausableCalls: Contains<FullNameOf<Self>>;
+
+		/// Maximum length of a pallet or a call name.
+		#[pallet::constant]
+		type MaxNameLen: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Paused pallets and calls.
+	#[pallet::storage]
+	#[pallet::getter(fn paused_calls)]
+	pub type PausedCalls<T: Config> =
+		StorageMap<_, Blake2_128Concat, FullNameOf<T>, (), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A pallet or a call was paused.
+		CallPaused { pallet_name: PalletNameOf<T>, call_name: Option<CallNameOf<T>> },
+		/// A pallet or a call was unpaused.
+		CallUnpaused { pallet_name: PalletNameOf<T>, call_name: Option<CallNameOf<T>> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The pallet or the call is already paused.
+		IsPaused,
+		/// The pallet or the call is not paused.
+		IsUnpaused,
+		/// The pallet or the call cannot be paused.
+		IsUnpausable,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pauses a whole pallet when `call_name` is `None`, a single call of it otherwise.
+		#[pallet::weight(T::WeightInfo::pause())]
+		pub fn pause(
+			origin: OriginFor<T>,
+			pallet_name: PalletNameOf<T>,
+			call_name: Option<CallNameOf<T>>,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			let full_name = (pallet_name, call_name);
+			ensure!(!T::UnpausableCalls::contains(&full_name), Error::<T>::IsUnpausable);
+			ensure!(!PausedCalls::<T>::contains_key(&full_name), Error::<T>::IsPaused);
+
+			PausedCalls::<T>::insert(&full_name, ());
+
+			let (pallet_name, call_name) = full_name;
+			Self::deposit_event(Event::CallPaused { pallet_name, call_name });
+
+			Ok(())
+		}
+
+		/// Unpauses a pallet or a call previously paused with [`Pallet::pause`].
+		///
+		/// Unpausing a single call of a paused pallet does not make it callable again, the
+		/// pallet has to be unpaused too.
+		#[pallet::weight(T::WeightInfo::unpause())]
+		pub fn unpause(
+			origin: OriginFor<T>,
+			pallet_name: PalletNameOf<T>,
+			call_name: Option<CallNameOf<T>>,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			let full_name = (pallet_name, call_name);
+			ensure!(PausedCalls::<T>::take(&full_name).is_some(), Error::<T>::IsUnpaused);
+
+			let (pallet_name, call_name) = full_name;
+			Self::deposit_event(Event::CallUnpaused { pallet_name, call_name });
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns true if the pallet, or the call of the pallet, is paused.
+	///
+	/// Names too long to be stored can't have been paused.
+	pub fn is_paused(pallet_name: &[u8], call_name: &[u8]) -> bool {
+		let pallet_name = match PalletNameOf::<T>::try_from(pallet_name.to_vec()) {
+			Ok(name) => name,
+			Err(_) => return false,
+		};
+
+		if PausedCalls::<T>::contains_key((pallet_name.clone(), None::<CallNameOf<T>>)) {
+			return true
+		}
+
+		match CallNameOf::<T>::try_from(call_name.to_vec()) {
+			Ok(call_name) => PausedCalls::<T>::contains_key((pallet_name, Some(call_name))),
+			Err(_) => false,
+		}
+	}
+}
+
+/// Filters out the paused calls.
+impl<T: Config> Contains<<T as Config>::RuntimeCall> for Pallet<T> {
+	fn contains(call: &<T as Config>::RuntimeCall) -> bool {
+		let CallMetadata { pallet_name, function_name } = call.get_call_metadata();
+		!Self::is_paused(pallet_name.as_bytes(), function_name.as_bytes())
+	}
+}
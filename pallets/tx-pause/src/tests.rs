@@ -0,0 +1,148 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, dispatch::Dispatchable};
+use sp_runtime::DispatchError::BadOrigin;
+
+fn name(name: &str) -> BoundedVec<u8, MaxNameLen> {
+	name.as_bytes().to_vec().try_into().unwrap()
+}
+
+fn transfer_call() -> RuntimeCall {
+	RuntimeCall::Balances(pallet_balances::Call::transfer { dest: BOB, value: 10 })
+}
+
+fn transfer() -> DispatchResultWithPostInfo {
+	transfer_call().dispatch(RuntimeOrigin::signed(ALICE))
+}
+
+mod pause {
+	use super::*;
+
+	#[test]
+	fn pause_pallet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(TxPause::pause(RuntimeOrigin::root(), name("Balances"), None));
+
+			assert!(!TxPause::contains(&transfer_call()));
+			assert_noop!(transfer(), frame_system::Error::<Test>::CallFiltered);
+			System::assert_last_event(RuntimeEvent::TxPause(Event::CallPaused {
+				pallet_name: name("Balances"),
+				call_name: None,
+			}));
+		})
+	}
+
+	#[test]
+	fn pause_call() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(TxPause::pause(
+				RuntimeOrigin::root(),
+				name("Balances"),
+				Some(name("transfer"))
+			));
+
+			assert_noop!(transfer(), frame_system::Error::<Test>::CallFiltered);
+			let transfer_all = RuntimeCall::Balances(pallet_balances::Call::transfer_all {
+				dest: BOB,
+				keep_alive: false,
+			});
+			assert!(TxPause::contains(&transfer_all));
+		})
+	}
+
+	#[test]
+	fn root_is_not_filtered() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(TxPause::pause(RuntimeOrigin::root(), name("Balances"), None));
+
+			let call = RuntimeCall::Balances(pallet_balances::Call::force_transfer {
+				source: ALICE,
+				dest: BOB,
+				value: 10,
+			});
+			assert_ok!(call.dispatch(RuntimeOrigin::root()));
+		})
+	}
+
+	#[test]
+	fn bad_origin() {
+		new_test_ext().execute_with(|| {
+			let err = TxPause::pause(RuntimeOrigin::signed(ALICE), name("Balances"), None);
+			assert_noop!(err, BadOrigin);
+		})
+	}
+
+	#[test]
+	fn is_unpausable() {
+		new_test_ext().execute_with(|| {
+			let err = TxPause::pause(RuntimeOrigin::root(), name("System"), None);
+			assert_noop!(err, Error::<Test>::IsUnpausable);
+			let err = TxPause::pause(RuntimeOrigin::root(), name("TxPause"), Some(name("unpause")));
+			assert_noop!(err, Error::<Test>::IsUnpausable);
+		})
+	}
+
+	#[test]
+	fn is_paused() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(TxPause::pause(RuntimeOrigin::root(), name("Balances"), None));
+
+			let err = TxPause::pause(RuntimeOrigin::root(), name("Balances"), None);
+			assert_noop!(err, Error::<Test>::IsPaused);
+		})
+	}
+}
+
+mod unpause {
+	use super::*;
+
+	#[test]
+	fn unpause() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(TxPause::pause(RuntimeOrigin::root(), name("Balances"), None));
+			assert_ok!(TxPause::unpause(RuntimeOrigin::root(), name("Balances"), None));
+
+			assert_ok!(transfer());
+			System::assert_has_event(RuntimeEvent::TxPause(Event::CallUnpaused {
+				pallet_name: name("Balances"),
+				call_name: None,
+			}));
+		})
+	}
+
+	#[test]
+	fn call_of_paused_pallet_stays_paused() {
+		new_test_ext().execute_with(|| {
+			let call_name = Some(name("transfer"));
+			assert_ok!(TxPause::pause(RuntimeOrigin::root(), name("Balances"), None));
+			assert_ok!(TxPause::pause(RuntimeOrigin::root(), name("Balances"), call_name.clone()));
+			assert_ok!(TxPause::unpause(RuntimeOrigin::root(), name("Balances"), call_name));
+
+			assert_noop!(transfer(), frame_system::Error::<Test>::CallFiltered);
+		})
+	}
+
+	#[test]
+	fn is_unpaused() {
+		new_test_ext().execute_with(|| {
+			let err = TxPause::unpause(RuntimeOrigin::root(), name("Balances"), None);
+			assert_noop!(err, Error::<Test>::IsUnpaused);
+		})
+	}
+}
@@ -51,8 +51,27 @@ use sp_runtime::{
 };
 use sp_std::prelude::*;
 use sp_version::RuntimeVersion;
-use ternoa_core_primitives::{AccountId, Balance, BlockNumber, Index, Signature};
-use ternoa_runtime_common::{impl_runtime_weights, BlockLength};
+use ternoa_core_primitives::{
+	AccountId, AccountIndex, Balance, BlockNumber, Hash, Index, Signature,
+};
+use ternoa_runtime_apis::{
+	auction::AuctionState,
+	mandate::PendingMandate,
+	marketplace::{Listing, MarketplaceId, SaleFees},
+	nft::{CollectionId, CollectionInfo, NFTId, NftInfo, NftStatus, Page},
+	rent::{ContractEvent, RentContractInfo},
+	staking_payout::{NominatorPayouts, ValidatorPayouts},
+};
+use ternoa_runtime_common::{
+	genesis_fees::pallet_genesis_fees,
+	governance::{migrations::UnlockDemocracyFunds, pallet_custom_origins},
+	impl_runtime_weights,
+	migrations::{
+		pallet_multi_block_migrations, QueueUnclaimedPayouts, SetNominationPoolsConfigs,
+		SetTransactionStorageConfigs, VersionedMigration, VersionedSteppedMigration,
+	},
+	BlockLength,
+};
 pub use version::VERSION;
 
 #[cfg(feature = "std")]
@@ -109,7 +128,6 @@ construct_runtime!(
 		AuthorityDiscovery: pallet_authority_discovery = 12,
 		Council: pallet_collective::<Instance2> = 24,
 		PhragmenElection: pallet_elections_phragmen = 25,
-		Democracy: pallet_democracy = 26,
 
 		// Elections pallets
 		//
@@ -147,11 +165,30 @@ construct_runtime!(
 		Assets: pallet_assets = 31,
 		Auction: ternoa_auction = 32,
 		Rent: ternoa_rent = 33,
+		NominationPools: pallet_nomination_pools = 34,
+		AssetTxPayment: pallet_asset_tx_payment = 35,
+		Bounties: pallet_bounties = 36,
+		ChildBounties: pallet_child_bounties = 37,
+		Proxy: pallet_proxy = 38,
+		Vesting: pallet_vesting = 39,
+		TxPause: ternoa_tx_pause = 40,
+		TransactionStorage: pallet_transaction_storage = 41,
+		Indices: pallet_indices = 42,
+		Recovery: pallet_recovery = 43,
+		StakingPayout: ternoa_staking_payout = 44,
+		FeeRouter: ternoa_fee_router = 45,
+		Referenda: pallet_referenda = 46,
+		ConvictionVoting: pallet_conviction_voting = 47,
+		Whitelist: pallet_whitelist = 48,
+		Origins: pallet_custom_origins = 49,
+		MandateTimelock: ternoa_mandate_timelock = 50,
+		MultiBlockMigrations: pallet_multi_block_migrations = 51,
+		GenesisFees: pallet_genesis_fees = 52,
 	}
 );
 
 /// The address format for describing accounts.
-pub type Address = sp_runtime::MultiAddress<AccountId, ()>;
+pub type Address = sp_runtime::MultiAddress<AccountId, AccountIndex>;
 /// Block header type as expected by this runtime.
 pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
 /// Block type as expected by this runtime.
@@ -172,7 +209,7 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -181,6 +218,29 @@ pub type UncheckedExtrinsic =
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, RuntimeCall, SignedExtra>;
+
+/// Migrations done in the block of the runtime upgrade.
+///
+/// They run before `MultiBlockMigrations` looks for pending stepped migrations, the ones gated on
+/// its storage version come first in the versions of an upgrade.
+pub type Migrations = (
+	// Sets the limits the live chains didn't get in their genesis. The pallet is only at version 0
+	// on those chains, the genesis of the new ones sets its current version.
+	VersionedMigration<NominationPools, SetNominationPoolsConfigs<Runtime>, 0, 3>,
+	// Sets the fees and the period the live chains didn't get in their genesis. The pallet has no
+	// storage version of its own, 1 records that the configuration is set. New chains are at 0 too
+	// and keep the configuration of their genesis.
+	VersionedMigration<TransactionStorage, SetTransactionStorageConfigs<Runtime>, 0, 1>,
+);
+
+/// Migrations done over several blocks by `MultiBlockMigrations`, only ever appended to.
+pub type SteppedMigrations = (
+	// Queues the payouts of the eras that ended before the automatic payouts.
+	VersionedSteppedMigration<MultiBlockMigrations, QueueUnclaimedPayouts<Runtime>, 0, 1>,
+	// Gives back the locks and deposits of the removed democracy pallet.
+	VersionedSteppedMigration<MultiBlockMigrations, UnlockDemocracyFunds<Runtime>, 1, 2>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -188,6 +248,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 impl_runtime_apis! {
@@ -355,6 +416,144 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl ternoa_runtime_apis::nft::NftApi<Block, AccountId> for Runtime {
+		fn nft(nft_id: NFTId) -> Option<NftInfo<AccountId>> {
+			ternoa_runtime_common::nft::nft::<Runtime>(nft_id)
+		}
+
+		fn nfts_by_owner(
+			owner: AccountId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<NftInfo<AccountId>> {
+			ternoa_runtime_common::nft::nfts_by_owner::<Runtime>(owner, start_after, limit)
+		}
+
+		fn collection(collection_id: CollectionId) -> Option<CollectionInfo<AccountId>> {
+			ternoa_runtime_common::nft::collection::<Runtime>(collection_id)
+		}
+
+		fn collection_nfts(
+			collection_id: CollectionId,
+			offset: u32,
+			limit: u32,
+		) -> Vec<NftInfo<AccountId>> {
+			ternoa_runtime_common::nft::collection_nfts::<Runtime>(collection_id, offset, limit)
+		}
+
+		fn nft_status(nft_id: NFTId) -> Option<NftStatus<AccountId>> {
+			ternoa_runtime_common::nft::nft_status::<Runtime>(nft_id)
+		}
+	}
+
+	impl ternoa_runtime_apis::marketplace::MarketplaceApi<Block, AccountId, Balance> for Runtime {
+		fn listings(
+			marketplace_id: MarketplaceId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<Listing<AccountId, Balance>> {
+			ternoa_runtime_common::marketplace::listings::<Runtime>(marketplace_id, start_after, limit)
+		}
+
+		fn listing(nft_id: NFTId) -> Option<Listing<AccountId, Balance>> {
+			ternoa_runtime_common::marketplace::listing::<Runtime>(nft_id)
+		}
+
+		fn buy_nft_fees(nft_id: NFTId) -> Option<SaleFees<Balance>> {
+			ternoa_runtime_common::marketplace::buy_nft_fees::<Runtime>(nft_id)
+		}
+	}
+
+	impl ternoa_runtime_apis::auction::AuctionApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn auction(nft_id: NFTId) -> Option<AuctionState<AccountId, Balance, BlockNumber>> {
+			ternoa_runtime_common::auction::auction::<Runtime>(nft_id)
+		}
+
+		fn auctions(
+			marketplace_id: MarketplaceId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<AuctionState<AccountId, Balance, BlockNumber>> {
+			ternoa_runtime_common::auction::auctions::<Runtime>(marketplace_id, start_after, limit)
+		}
+
+		fn complete_auction_fees(nft_id: NFTId) -> Option<SaleFees<Balance>> {
+			ternoa_runtime_common::auction::complete_auction_fees::<Runtime>(nft_id)
+		}
+	}
+
+	impl ternoa_runtime_apis::rent::RentApi<Block, AccountId, BlockNumber> for Runtime {
+		fn contract(nft_id: NFTId) -> Option<RentContractInfo<AccountId, BlockNumber>> {
+			ternoa_runtime_common::rent::contract::<Runtime>(nft_id)
+		}
+
+		fn contracts_by_renter(
+			renter: AccountId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<RentContractInfo<AccountId, BlockNumber>> {
+			ternoa_runtime_common::rent::contracts_by_renter::<Runtime>(renter, start_after, limit)
+		}
+
+		fn contracts_by_rentee(
+			rentee: AccountId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<RentContractInfo<AccountId, BlockNumber>> {
+			ternoa_runtime_common::rent::contracts_by_rentee::<Runtime>(rentee, start_after, limit)
+		}
+
+		fn upcoming_expirations(
+			until: BlockNumber,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<RentContractInfo<AccountId, BlockNumber>> {
+			ternoa_runtime_common::rent::upcoming_expirations::<Runtime>(until, start_after, limit)
+		}
+
+		fn contract_events() -> Vec<ContractEvent<AccountId>> {
+			ternoa_runtime_common::rent::contract_events::<Runtime>()
+		}
+	}
+
+	impl ternoa_runtime_apis::staking_payout::StakingPayoutApi<Block, AccountId> for Runtime {
+		fn validator_payouts(validator: AccountId) -> ValidatorPayouts {
+			use ternoa_staking_payout::StakingPayouts;
+
+			let mut paid = Staking::claimed_eras(&validator);
+			paid.sort_unstable();
+			ValidatorPayouts { pending: StakingPayout::pending_eras(&validator), paid }
+		}
+
+		fn nominator_payouts(nominator: AccountId) -> NominatorPayouts {
+			let (pending, paid) = StakingPayout::nominator_eras(&nominator);
+			NominatorPayouts { pending, paid }
+		}
+
+		fn pending_payouts() -> u32 {
+			StakingPayout::pending_payouts()
+		}
+	}
+
+	impl ternoa_runtime_apis::mandate::MandateApi<Block, Hash, BlockNumber> for Runtime {
+		fn pending_mandates() -> Vec<PendingMandate<Hash, BlockNumber>> {
+			MandateTimelock::pending_mandates()
+				.into_iter()
+				.map(|(index, mandate)| PendingMandate {
+					index,
+					call_hash: mandate.call_hash,
+					dispatch_at: mandate.dispatch_at,
+				})
+				.collect()
+		}
+	}
+
+	impl pallet_nomination_pools_runtime_api::NominationPoolsApi<Block, AccountId, Balance> for Runtime {
+		fn pending_rewards(member: AccountId) -> Balance {
+			NominationPools::pending_rewards(member).unwrap_or_default()
+		}
+	}
+
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
 			SessionKeys::generate(seed)
@@ -460,6 +659,10 @@ mod benches {
 		[ternoa_marketplace, Marketplace]
 		[ternoa_auction, Auction]
 		[ternoa_rent, Rent]
+		[ternoa_tx_pause, TxPause]
+		[ternoa_staking_payout, StakingPayout]
+		[ternoa_fee_router, FeeRouter]
+		[ternoa_mandate_timelock, MandateTimelock]
 		// Substrate
 		[pallet_babe, Babe]
 		[pallet_bags_list, BagsList]
@@ -479,11 +682,21 @@ mod benches {
 		[pallet_timestamp, Timestamp]
 		[pallet_treasury, Treasury]
 		[pallet_utility, Utility]
-		[pallet_democracy, Democracy]
 		[pallet_elections_phragmen, PhragmenElection]
 		[pallet_identity, Identity]
 		[pallet_multisig, Multisig]
 		[pallet_assets, Assets]
+		[pallet_bounties, Bounties]
+		[pallet_child_bounties, ChildBounties]
+		[pallet_proxy, Proxy]
+		[pallet_vesting, Vesting]
+		[pallet_transaction_storage, TransactionStorage]
+		[pallet_indices, Indices]
+		[pallet_recovery, Recovery]
+		[pallet_referenda, Referenda]
+		[pallet_conviction_voting, ConvictionVoting]
+		[pallet_whitelist, Whitelist]
+		[pallet_custom_origins, Origins]
 	);
 }
 
@@ -553,6 +766,173 @@ mod tests {
 			maximum_chain_accuracy.iter().fold(0, |acc, x| acc.checked_add(*x).unwrap());
 	}
 
+	#[test]
+	fn proxy_type_filters() {
+		use frame_support::traits::InstanceFilter;
+		use ternoa_runtime_common::proxy::ProxyType;
+
+		let transfer = RuntimeCall::Balances(BalancesCall::transfer {
+			dest: Address::Id(AccountId::new([0u8; 32])),
+			value: 1,
+		});
+		let remark = RuntimeCall::System(SystemCall::remark { remark: vec![] });
+
+		assert!(ProxyType::Any.filter(&transfer));
+		assert!(!ProxyType::NonTransfer.filter(&transfer));
+		assert!(ProxyType::NonTransfer.filter(&remark));
+		assert!(!ProxyType::NftOperator.filter(&remark));
+		assert!(!ProxyType::AuctionBidder.filter(&transfer));
+
+		let claim_index = RuntimeCall::Indices(pallet_indices::Call::claim { index: 0 });
+		let transfer_index = RuntimeCall::Indices(pallet_indices::Call::transfer {
+			new: Address::Id(AccountId::new([0u8; 32])),
+			index: 0,
+		});
+		assert!(ProxyType::NonTransfer.filter(&claim_index));
+		assert!(!ProxyType::NonTransfer.filter(&transfer_index));
+
+		assert!(ProxyType::Any.is_superset(&ProxyType::NftOperator));
+		assert!(ProxyType::NonTransfer.is_superset(&ProxyType::Staking));
+		assert!(!ProxyType::NonTransfer.is_superset(&ProxyType::NftOperator));
+		assert!(!ProxyType::Governance.is_superset(&ProxyType::Any));
+	}
+
+	#[test]
+	fn unpausable_pallets_exist() {
+		use frame_support::traits::GetCallMetadata;
+		use ternoa_runtime_common::tx_pause::UNPAUSABLE_PALLETS;
+
+		let pallet_names = RuntimeCall::get_module_names();
+		for name in UNPAUSABLE_PALLETS {
+			let name = sp_std::str::from_utf8(name).unwrap();
+			assert!(pallet_names.contains(&name), "{} is not a pallet with calls", name);
+		}
+	}
+
+	#[test]
+	fn ternoa_calls_can_be_recovered() {
+		use frame_support::{
+			assert_ok,
+			traits::{Contains, Currency},
+			BoundedVec,
+		};
+		use sp_runtime::{traits::Dispatchable, Permill};
+
+		let calls = [
+			RuntimeCall::NFT(ternoa_nft::Call::burn_nft { nft_id: 0 }),
+			RuntimeCall::Marketplace(ternoa_marketplace::Call::unlist_nft { nft_id: 0 }),
+			RuntimeCall::Rent(ternoa_rent::Call::cancel_contract { nft_id: 0 }),
+		];
+		let as_recovered = |account: &AccountId, call: RuntimeCall| {
+			RuntimeCall::Recovery(pallet_recovery::Call::as_recovered {
+				account: account.clone().into(),
+				call: Box::new(call),
+			})
+		};
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			let lost = AccountId::new([1u8; 32]);
+			let rescuer = AccountId::new([2u8; 32]);
+			for call in calls {
+				// `as_recovered` dispatches the call with a signed origin only subject to the base
+				// call filter.
+				assert!(<Runtime as frame_system::Config>::BaseCallFilter::contains(&call));
+				let recovered = as_recovered(&lost, call);
+				assert!(<Runtime as frame_system::Config>::BaseCallFilter::contains(&recovered));
+			}
+
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&lost, 1_000 * common_constants::currency::UNITS);
+			assert_ok!(Recovery::set_recovered(
+				RuntimeOrigin::root(),
+				lost.clone().into(),
+				rescuer.clone().into(),
+			));
+
+			// The rescuer mints and burns an NFT in the name of the lost account.
+			let create = RuntimeCall::NFT(ternoa_nft::Call::create_nft {
+				offchain_data: BoundedVec::try_from(b"offchain data".to_vec()).unwrap(),
+				royalty: Permill::from_percent(10),
+				collection_id: None,
+				is_soulbound: false,
+			});
+			assert_ok!(as_recovered(&lost, create).dispatch(RuntimeOrigin::signed(rescuer.clone())));
+			assert_eq!(
+				ternoa_nft::Nfts::<Runtime>::get(0).map(|nft| nft.owner),
+				Some(lost.clone())
+			);
+
+			let burn = RuntimeCall::NFT(ternoa_nft::Call::burn_nft { nft_id: 0 });
+			assert_ok!(as_recovered(&lost, burn).dispatch(RuntimeOrigin::signed(rescuer)));
+			assert!(ternoa_nft::Nfts::<Runtime>::get(0).is_none());
+		});
+	}
+
+	#[test]
+	fn governance_origins_have_a_track() {
+		use pallet_referenda::TracksInfo;
+
+		type Tracks = <Runtime as pallet_referenda::Config>::Tracks;
+
+		let origins = [
+			OriginCaller::system(frame_system::RawOrigin::Root),
+			OriginCaller::Origins(pallet_custom_origins::Origin::WhitelistedCaller),
+			OriginCaller::Origins(pallet_custom_origins::Origin::Treasurer),
+			OriginCaller::Origins(pallet_custom_origins::Origin::NftFeeAdmin),
+			OriginCaller::Origins(pallet_custom_origins::Origin::BridgeAdmin),
+			OriginCaller::Origins(pallet_custom_origins::Origin::StakingAdmin),
+			OriginCaller::Origins(pallet_custom_origins::Origin::GeneralAdmin),
+			OriginCaller::Origins(pallet_custom_origins::Origin::MembershipAdmin),
+		];
+		for (id, origin) in origins.iter().enumerate() {
+			let track = Tracks::track_for(origin).unwrap();
+			assert_eq!(track, id as u16);
+			assert!(Tracks::info(track).is_some());
+		}
+
+		let signed =
+			OriginCaller::system(frame_system::RawOrigin::Signed(AccountId::new([0u8; 32])));
+		assert!(Tracks::track_for(&signed).is_err());
+	}
+
+	#[test]
+	fn nft_fee_admin_only_changes_fees() {
+		use frame_support::traits::Contains;
+
+		let set_fee = RuntimeCall::NFT(ternoa_nft::Call::set_nft_mint_fee { fee: 0 });
+		let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		assert!(pallets::NftFeeCalls::contains(&set_fee));
+		assert!(!pallets::NftFeeCalls::contains(&remark));
+	}
+
+	#[test]
+	fn mandates_can_be_vetoed_by_a_root_referendum() {
+		use pallet_referenda::TracksInfo;
+
+		type Tracks = <Runtime as pallet_referenda::Config>::Tracks;
+
+		let root = Tracks::info(0).unwrap();
+		let quickest_veto = root.prepare_period + root.confirm_period + root.min_enactment_period;
+		assert!(ternoa_runtime_common::mandate::MandateDelay::get() > quickest_veto);
+	}
+
+	#[test]
+	fn root_or_half_of_the_committee_sets_the_fee_split() {
+		use frame_support::traits::EnsureOrigin;
+
+		type SplitOrigin = <Runtime as ternoa_fee_router::Config>::SplitOrigin;
+		type CommitteeOrigin =
+			pallet_collective::RawOrigin<AccountId, pallet_collective::Instance1>;
+
+		let half = RuntimeOrigin::from(CommitteeOrigin::Members(1, 2));
+		let third = RuntimeOrigin::from(CommitteeOrigin::Members(1, 3));
+		let signed = RuntimeOrigin::signed(AccountId::new([0u8; 32]));
+		assert!(SplitOrigin::try_origin(RuntimeOrigin::root()).is_ok());
+		assert!(SplitOrigin::try_origin(half).is_ok());
+		assert!(SplitOrigin::try_origin(third).is_err());
+		assert!(SplitOrigin::try_origin(signed).is_err());
+	}
+
 	#[test]
 	fn call_size() {
 		let size = core::mem::size_of::<RuntimeCall>();
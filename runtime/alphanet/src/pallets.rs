@@ -17,6 +17,9 @@
 use super::weights;
 use common::{
 	election_provider_multi_phase::BetterUnsignedThreshold,
+	genesis_fees::pallet_genesis_fees,
+	governance::pallet_custom_origins,
+	migrations::{pallet_multi_block_migrations, MigrationStepWeight},
 	staking::{BondingDuration, SessionsPerEra},
 	transaction_payment::TransactionByteFee,
 	BlockHashCount, BlockLength,
@@ -24,35 +27,40 @@ use common::{
 use frame_election_provider_support::{SequentialPhragmen, Weight};
 use frame_support::{
 	parameter_types,
-	traits::{ConstU32, EitherOfDiverse, KeyOwnerProofSystem, U128CurrencyToVote},
+	traits::{
+		ConstU32, Contains, EitherOf, EitherOfDiverse, KeyOwnerProofSystem, TotalIssuanceOf,
+		U128CurrencyToVote,
+	},
 	weights::{constants::RocksDbWeight, ConstantMultiplier, IdentityFee},
 	PalletId,
 };
-use frame_system::EnsureRoot;
+use frame_system::{EnsureRoot, EnsureRootWithSuccess, EnsureSigned};
 use pallet_grandpa::AuthorityId as GrandpaId;
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use pallet_transaction_payment::CurrencyAdapter;
 use parity_scale_codec::Encode;
 use sp_core::crypto::KeyTypeId;
 use sp_runtime::{
+	curve::PiecewiseLinear,
 	generic::{self, Era},
 	impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, OpaqueKeys, StaticLookup},
-	Perbill, SaturatedConversion,
+	traits::{BlakeTwo256, ConvertInto, OpaqueKeys, StaticLookup},
+	FixedU128, Perbill, SaturatedConversion,
 };
 use sp_std::vec::Vec;
 use sp_version::RuntimeVersion;
 use static_assertions::const_assert;
-use ternoa_core_primitives::{AccountId, Balance, BlockNumber, Hash, Index, Moment};
+use ternoa_core_primitives::{AccountId, AccountIndex, Balance, BlockNumber, Hash, Index, Moment};
 use ternoa_runtime_common as common;
 
 use crate::{
-	constants::time::EPOCH_DURATION_IN_SLOTS, AuthorityDiscovery, Babe, BagsList, Balances,
-	BlockWeights, Council, ElectionProviderMultiPhase, Grandpa, Historical, ImOnline, Marketplace,
-	OffchainSolutionLengthLimit, OffchainSolutionWeightLimit, Offences, OriginCaller, PalletInfo,
-	Preimage, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, Scheduler, Session, Signature,
-	SignedPayload, Staking, StakingRewards, System, TechnicalCommittee, Timestamp,
-	TransactionPayment, Treasury, UncheckedExtrinsic, NFT, VERSION,
+	constants::time::EPOCH_DURATION_IN_SLOTS, Assets, AuthorityDiscovery, Babe, BagsList, Balances,
+	BlockWeights, Bounties, ChildBounties, Council, ElectionProviderMultiPhase, FeeRouter, Grandpa,
+	Historical, ImOnline, Indices, Marketplace, NominationPools, OffchainSolutionLengthLimit,
+	OffchainSolutionWeightLimit, Offences, OriginCaller, PalletInfo, Preimage, Referenda, Runtime,
+	RuntimeCall, RuntimeEvent, RuntimeOrigin, Scheduler, Session, Signature, SignedPayload,
+	Staking, StakingPayout, StakingRewards, System, TechnicalCommittee, Timestamp,
+	TransactionPayment, Treasury, TxPause, UncheckedExtrinsic, NFT, VERSION,
 };
 
 pub use common::babe::BABE_GENESIS_EPOCH_CONFIG;
@@ -64,13 +72,21 @@ type RootOrAtLeastHalfOfCommittee = EitherOfDiverse<
 	EnsureRoot<AccountId>,
 	pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 2>,
 >;
+type RootOrTreasurer = EitherOfDiverse<EnsureRoot<AccountId>, pallet_custom_origins::Treasurer>;
+type RootOrBridgeAdmin = EitherOfDiverse<EnsureRoot<AccountId>, pallet_custom_origins::BridgeAdmin>;
+type RootOrStakingAdmin =
+	EitherOfDiverse<EnsureRoot<AccountId>, pallet_custom_origins::StakingAdmin>;
+type RootOrGeneralAdmin =
+	EitherOfDiverse<EnsureRoot<AccountId>, pallet_custom_origins::GeneralAdmin>;
+type RootOrMembershipAdmin =
+	EitherOfDiverse<EnsureRoot<AccountId>, pallet_custom_origins::MembershipAdmin>;
 
 parameter_types! {
 	pub const Version: RuntimeVersion = VERSION;
 }
 
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = TxPause;
 	type BlockWeights = BlockWeights;
 	type BlockLength = BlockLength;
 	type DbWeight = RocksDbWeight;
@@ -81,7 +97,7 @@ impl frame_system::Config for Runtime {
 	type Hash = Hash;
 	type Hashing = BlakeTwo256;
 	type AccountId = AccountId;
-	type Lookup = AccountIdLookup<AccountId, ()>;
+	type Lookup = Indices;
 	type Header = generic::Header<BlockNumber, BlakeTwo256>;
 	type RuntimeEvent = RuntimeEvent;
 	type BlockHashCount = BlockHashCount;
@@ -106,13 +122,34 @@ impl pallet_utility::Config for Runtime {
 
 impl pallet_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type OnChargeTransaction = CurrencyAdapter<Balances, StakingRewards>;
+	type OnChargeTransaction = CurrencyAdapter<Balances, FeeRouter>;
 	type OperationalFeeMultiplier = common::transaction_payment::OperationalFeeMultiplier;
 	type WeightToFee = IdentityFee<Balance>;
 	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
 	type FeeMultiplierUpdate = common::SlowAdjustingFeeUpdate<Self>;
 }
 
+parameter_types! {
+	// Until governance changes it, all the fees go to the staking rewards pot.
+	pub DefaultFeeSplit: ternoa_fee_router::FeeSplit = ternoa_fee_router::FeeSplit {
+		author: Perbill::zero(),
+		treasury: Perbill::zero(),
+		staking: Perbill::one(),
+		burn: Perbill::zero(),
+	};
+}
+
+impl ternoa_fee_router::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type SplitOrigin = RootOrAtLeastHalfOfCommittee;
+	type DefaultSplit = DefaultFeeSplit;
+	type Author = common::authorship::ToAuthor<Runtime>;
+	type Treasury = Treasury;
+	type StakingPot = StakingRewards;
+	type WeightInfo = weights::ternoa_fee_router::WeightInfo<Runtime>;
+}
+
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = common::balances::MaxLocks;
 	type Balance = Balance;
@@ -135,8 +172,9 @@ impl pallet_timestamp::Config for Runtime {
 impl pallet_treasury::Config for Runtime {
 	type PalletId = common::treasury::PalletId;
 	type Currency = Balances;
-	type ApproveOrigin = RootOrAtLeastHalfOfCommittee;
-	type RejectOrigin = RootOrAtLeastHalfOfCommittee;
+	// Approved proposals are not capped like `spend`, the treasurer track goes through the latter.
+	type ApproveOrigin = RootOrTreasurer;
+	type RejectOrigin = RootOrTreasurer;
 	type RuntimeEvent = RuntimeEvent;
 	type OnSlash = Treasury;
 	type ProposalBond = common::treasury::ProposalBond;
@@ -147,8 +185,33 @@ impl pallet_treasury::Config for Runtime {
 	type BurnDestination = ();
 	type MaxApprovals = common::treasury::MaxApprovals;
 	type WeightInfo = weights::pallet_treasury::WeightInfo<Runtime>;
-	type SpendFunds = ();
-	type SpendOrigin = frame_support::traits::NeverEnsureOrigin<Balance>;
+	type SpendFunds = Bounties;
+	type SpendOrigin = EitherOf<
+		EnsureRootWithSuccess<AccountId, common::governance::MaxBalance>,
+		pallet_custom_origins::Treasurer,
+	>;
+}
+
+impl pallet_bounties::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type BountyDepositBase = common::bounties::BountyDepositBase;
+	type BountyDepositPayoutDelay = common::bounties::BountyDepositPayoutDelay;
+	type BountyUpdatePeriod = common::bounties::BountyUpdatePeriod;
+	type CuratorDepositMultiplier = common::bounties::CuratorDepositMultiplier;
+	type CuratorDepositMin = common::bounties::CuratorDepositMin;
+	type CuratorDepositMax = common::bounties::CuratorDepositMax;
+	type BountyValueMinimum = common::bounties::BountyValueMinimum;
+	type DataDepositPerByte = common::bounties::DataDepositPerByte;
+	type MaximumReasonLength = common::bounties::MaximumReasonLength;
+	type WeightInfo = pallet_bounties::weights::SubstrateWeight<Runtime>;
+	type ChildBountyManager = ChildBounties;
+}
+
+impl pallet_child_bounties::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MaxActiveChildBountyCount = common::bounties::MaxActiveChildBountyCount;
+	type ChildBountyValueMinimum = common::bounties::ChildBountyValueMinimum;
+	type WeightInfo = pallet_child_bounties::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -265,7 +328,7 @@ where
 			frame_system::CheckEra::<Runtime>::from(era),
 			frame_system::CheckNonce::<Runtime>::from(nonce),
 			frame_system::CheckWeight::<Runtime>::new(),
-			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+			pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(tip, None),
 		);
 		let raw_payload = SignedPayload::new(call, extra)
 			.map_err(|e| {
@@ -319,6 +382,21 @@ impl frame_election_provider_support::onchain::Config for OnChainSeqPhragmen {
 	type WeightInfo = frame_election_provider_support::weights::SubstrateWeight<Runtime>; // TODO Weights
 }
 
+pallet_staking_reward_curve::build! {
+	const REWARD_CURVE: PiecewiseLinear<'static> = curve!(
+		min_inflation: 0_025_000,
+		max_inflation: 0_100_000,
+		ideal_stake: 0_500_000,
+		falloff: 0_050_000,
+		max_piece_count: 40,
+		test_precision: 0_005_000,
+	);
+}
+
+parameter_types! {
+	pub const RewardCurve: &'static PiecewiseLinear<'static> = &REWARD_CURVE;
+}
+
 impl pallet_staking::Config for Runtime {
 	type MaxNominations = common::staking::MaxNominations;
 	type Currency = Balances;
@@ -335,10 +413,9 @@ impl pallet_staking::Config for Runtime {
 	type SessionsPerEra = common::staking::SessionsPerEra;
 	type BondingDuration = common::staking::BondingDuration;
 	type SlashDeferDuration = common::staking::SlashDeferDuration;
-	/// A super-majority of the council can cancel the slash.
-	type SlashCancelOrigin = RootOrAtLeastHalfOfCommittee;
+	type SlashCancelOrigin = RootOrStakingAdmin;
 	type SessionInterface = Self;
-	type EraPayout = StakingRewards;
+	type EraPayout = StakingPayout;
 	type NextNewSession = Session;
 	type MaxNominatorRewardedPerValidator = common::staking::MaxNominatorRewardedPerValidator;
 	type OffendingValidatorsThreshold = common::staking::OffendingValidatorsThreshold;
@@ -349,10 +426,26 @@ impl pallet_staking::Config for Runtime {
 	type MaxUnlockingChunks = common::staking::MaxUnlockingChunks;
 	type HistoryDepth = common::staking::HistoryDepth;
 	type BenchmarkingConfig = common::staking::StakingBenchmarkingConfig;
-	type OnStakerSlash = (); // TODO To see NominationPools
+	type OnStakerSlash = NominationPools;
 	type WeightInfo = pallet_staking::weights::SubstrateWeight<Runtime>;
 }
 
+impl pallet_nomination_pools::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_nomination_pools::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type CurrencyBalance = Balance;
+	type RewardCounter = FixedU128;
+	type BalanceToU256 = common::nomination_pools::BalanceToU256;
+	type U256ToBalance = common::nomination_pools::U256ToBalance;
+	type StakingInterface = Staking;
+	type PostUnbondingPoolsWindow = common::nomination_pools::PostUnbondPoolsWindow;
+	type MaxMetadataLen = common::nomination_pools::MaxMetadataLen;
+	type MaxUnbonding = common::nomination_pools::MaxUnbonding;
+	type PalletId = common::nomination_pools::PalletId;
+	type MaxPointsToBalance = common::nomination_pools::MaxPointsToBalance;
+}
+
 impl pallet_election_provider_multi_phase::MinerConfig for Runtime {
 	type AccountId = AccountId;
 	type MaxLength = OffchainSolutionLengthLimit;
@@ -419,7 +512,7 @@ impl pallet_election_provider_multi_phase::Config for Runtime {
 		frame_election_provider_support::onchain::UnboundedExecution<OnChainSeqPhragmen>;
 	type Solver = common::election_provider_multi_phase::Solver<Self>;
 	type BenchmarkingConfig = common::election_provider_multi_phase::BenchmarkConfig;
-	type ForceOrigin = RootOrAtLeastHalfOfCommittee;
+	type ForceOrigin = RootOrStakingAdmin;
 	type WeightInfo = weights::pallet_election_provider_multi_phase::WeightInfo<Runtime>;
 	type MaxElectingVoters = common::election_provider_multi_phase::MaxElectingVoters;
 	type MaxElectableTargets = common::election_provider_multi_phase::MaxElectableTargets;
@@ -438,7 +531,7 @@ impl pallet_preimage::Config for Runtime {
 	type WeightInfo = weights::pallet_preimage::WeightInfo<Runtime>;
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
-	type ManagerOrigin = RootOrAtLeastHalfOfCommittee;
+	type ManagerOrigin = EnsureRoot<AccountId>;
 	type MaxSize = common::preimage::PreimageMaxSize;
 	type BaseDeposit = common::preimage::PreimageBaseDeposit;
 	type ByteDeposit = common::preimage::PreimageByteDeposit;
@@ -460,11 +553,11 @@ impl pallet_collective::Config<TechnicalCollective> for Runtime {
 // Pallet Membership
 impl pallet_membership::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type AddOrigin = RootOrAtLeastHalfOfCommittee;
-	type RemoveOrigin = RootOrAtLeastHalfOfCommittee;
-	type SwapOrigin = RootOrAtLeastHalfOfCommittee;
-	type ResetOrigin = RootOrAtLeastHalfOfCommittee;
-	type PrimeOrigin = RootOrAtLeastHalfOfCommittee;
+	type AddOrigin = RootOrMembershipAdmin;
+	type RemoveOrigin = RootOrMembershipAdmin;
+	type SwapOrigin = RootOrMembershipAdmin;
+	type ResetOrigin = RootOrMembershipAdmin;
+	type PrimeOrigin = RootOrMembershipAdmin;
 	type MembershipInitialized = TechnicalCommittee;
 	type MembershipChanged = TechnicalCommittee;
 	type MaxMembers = common::technical_collective::TechnicalMaxMembers;
@@ -472,11 +565,48 @@ impl pallet_membership::Config for Runtime {
 }
 
 // Pallet Membership
+// The technical committee issues its mandates through `MandateTimelock`, immediate mandates are
+// left to root.
 impl ternoa_mandate::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
+	type ExternalOrigin = EnsureRoot<AccountId>;
+}
+
+/// Mandates dispatched without waiting for the delay.
+pub struct EmergencyCalls;
+impl Contains<RuntimeCall> for EmergencyCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		use pallet_election_provider_multi_phase::Call as EpmCall;
+
+		match call {
+			RuntimeCall::TxPause(..) => true,
+			RuntimeCall::Staking(pallet_staking::Call::cancel_deferred_slash { .. }) => true,
+			RuntimeCall::ElectionProviderMultiPhase(
+				EpmCall::set_emergency_election_result { .. } | EpmCall::governance_fallback { .. },
+			) => true,
+			_ => false,
+		}
+	}
+}
+
+// Mandates of the technical committee are dispatched after a delay, during which half of the
+// council or a referendum can veto them.
+impl ternoa_mandate_timelock::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
 	type ExternalOrigin =
 		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 2>;
+	type VetoOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 2>,
+	>;
+	type EmergencyCalls = EmergencyCalls;
+	type Delay = common::mandate::MandateDelay;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = weights::ternoa_mandate_timelock::WeightInfo<Runtime>;
 }
 
 // Scheduler
@@ -493,7 +623,7 @@ impl pallet_scheduler::Config for Runtime {
 	type PalletsOrigin = OriginCaller;
 	type RuntimeCall = RuntimeCall;
 	type MaximumWeight = MaximumSchedulerWeight;
-	type ScheduleOrigin = RootOrAtLeastHalfOfCommittee;
+	type ScheduleOrigin = RootOrGeneralAdmin;
 	type MaxScheduledPerBlock = MaxScheduledPerBlock;
 	type WeightInfo = weights::pallet_scheduler::WeightInfo<Runtime>;
 	type OriginPrivilegeCmp = frame_support::traits::EqualPrivilegeOnly;
@@ -506,10 +636,25 @@ impl ternoa_staking_rewards::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type PalletId = common::staking_rewards::PalletId;
-	type ExternalOrigin = RootOrAtLeastHalfOfCommittee;
+	type ExternalOrigin = RootOrStakingAdmin;
 	type WeightInfo = weights::ternoa_staking_rewards::WeightInfo<Runtime>;
 }
 
+// Era payouts are taken from the staking rewards pot until governance switches to the reward
+// curve, the pot then pays the shortfall when the curve yields less than it.
+// The stakers of the ended eras are paid out automatically with the idle weight of the blocks.
+impl ternoa_staking_payout::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type ExternalOrigin = RootOrStakingAdmin;
+	type RewardCurve = RewardCurve;
+	type PotPayout = StakingRewards;
+	type Staking = Staking;
+	type MaxPayoutsPerBlock = common::staking::MaxPayoutsPerBlock;
+	type MaxPayoutAttempts = common::staking::MaxPayoutAttempts;
+	type WeightInfo = weights::ternoa_staking_payout::WeightInfo<Runtime>;
+}
+
 parameter_types! {
 	pub const ProposalLifetime: BlockNumber = 1000;
 	pub const InitialBridgeFee: Balance = 10_000_000_000_000_000_000;
@@ -520,7 +665,7 @@ impl ternoa_bridge::Config for Runtime {
 	type WeightInfo = weights::ternoa_bridge::WeightInfo<Runtime>;
 	type Currency = Balances;
 	type FeesCollector = Treasury;
-	type ExternalOrigin = RootOrAtLeastHalfOfCommittee;
+	type ExternalOrigin = RootOrBridgeAdmin;
 	type ChainId = common::bridge::ChainId;
 	type PalletId = common::bridge::PalletId;
 	type ProposalLifetime = ProposalLifetime;
@@ -568,58 +713,128 @@ impl pallet_elections_phragmen::Config for Runtime {
 	type MaxVoters = common::phragmen_election::MaxVoters;
 }
 
-// Democracy
-impl pallet_democracy::Config for Runtime {
-	type Proposal = RuntimeCall;
+// OpenGov
+impl pallet_conviction_voting::Config for Runtime {
+	type WeightInfo = pallet_conviction_voting::weights::SubstrateWeight<Runtime>;
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
-	type EnactmentPeriod = common::democracy::EnactmentPeriod;
-	type VoteLockingPeriod = common::democracy::VoteLockingPeriod;
-	type LaunchPeriod = common::democracy::LaunchPeriod;
-	type VotingPeriod = common::democracy::VotingPeriod;
-	type MinimumDeposit = common::democracy::MinimumDeposit;
-	/// A straight majority of the council can decide what their next motion is.
-	type ExternalOrigin =
-		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 2>;
-	/// A majority can have the next scheduled referendum be a straight majority-carries vote.
-	type ExternalMajorityOrigin =
-		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 2>;
-	/// A unanimous council can have the next scheduled referendum be a straight default-carries
-	/// (NTB) vote.
-	type ExternalDefaultOrigin =
-		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 1>;
-	/// Two thirds of the technical committee can have an `ExternalMajority/ExternalDefault` vote
-	/// be tabled immediately and with a shorter voting/enactment period.
-	type FastTrackOrigin =
-		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 2>;
-	type InstantOrigin =
-		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 2>;
-	type InstantAllowed = common::democracy::InstantAllowed;
-	type FastTrackVotingPeriod = common::democracy::FastTrackVotingPeriod;
-	// To cancel a proposal which has been passed, 2/3 of the council must agree to it.
-	type CancellationOrigin = EitherOfDiverse<
-		EnsureRoot<AccountId>,
-		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>,
-	>;
-	type BlacklistOrigin = EnsureRoot<AccountId>;
-	// To cancel a proposal before it has been passed, the technical committee must be 1/2 or
-	// Root must agree.
-	type CancelProposalOrigin = EitherOfDiverse<
-		EnsureRoot<AccountId>,
-		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 2>,
-	>;
-	// Any single technical committee member may veto a coming council proposal, however they can
-	// only do it once and it lasts only for the cooloff period.
-	type VetoOrigin = pallet_collective::EnsureMember<AccountId, TechnicalCollective>;
-	type CooloffPeriod = common::democracy::CooloffPeriod;
-	type PreimageByteDeposit = common::preimage::PreimageByteDeposit;
-	type OperationalPreimageOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
-	type Slash = Treasury;
+	type VoteLockingPeriod = common::governance::VoteLockingPeriod;
+	type MaxVotes = common::governance::MaxVotes;
+	type MaxTurnout = TotalIssuanceOf<Balances, AccountId>;
+	type Polls = Referenda;
+}
+
+impl pallet_referenda::Config for Runtime {
+	type WeightInfo = pallet_referenda::weights::SubstrateWeight<Runtime>;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
 	type Scheduler = Scheduler;
-	type PalletsOrigin = OriginCaller;
-	type MaxVotes = common::democracy::MaxVotes;
-	type WeightInfo = weights::pallet_democracy::WeightInfo<Runtime>;
-	type MaxProposals = common::democracy::MaxProposals;
+	type Currency = Balances;
+	type SubmitOrigin = EnsureSigned<AccountId>;
+	type CancelOrigin = EnsureRoot<AccountId>;
+	type KillOrigin = EnsureRoot<AccountId>;
+	type Slash = Treasury;
+	type Votes = pallet_conviction_voting::VotesOf<Runtime>;
+	type Tally = pallet_conviction_voting::TallyOf<Runtime>;
+	type SubmissionDeposit = common::governance::SubmissionDeposit;
+	type MaxQueued = common::governance::MaxQueued;
+	type UndecidingTimeout = common::governance::UndecidingTimeout;
+	type AlarmInterval = common::governance::AlarmInterval;
+	type Tracks = common::governance::TracksInfo<OriginCaller>;
+}
+
+// The technical committee whitelists the urgent calls, which can then go through the faster
+// whitelisted caller track.
+impl pallet_whitelist::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type WhitelistOrigin = RootOrAtLeastHalfOfCommittee;
+	type DispatchWhitelistedOrigin =
+		EitherOf<EnsureRoot<AccountId>, pallet_custom_origins::WhitelistedCaller>;
+	type PreimageProvider = Preimage;
+	type WeightInfo = pallet_whitelist::weights::SubstrateWeight<Runtime>;
+}
+
+/// Calls that the NFT fee admin track can dispatch.
+pub struct NftFeeCalls;
+impl Contains<RuntimeCall> for NftFeeCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::NFT(
+				ternoa_nft::Call::set_nft_mint_fee { .. } |
+					ternoa_nft::Call::set_secret_nft_mint_fee { .. }
+			) | RuntimeCall::Marketplace(ternoa_marketplace::Call::set_marketplace_mint_fee { .. })
+		)
+	}
+}
+
+/// Gives the current NFT mint fee again, a change that always succeeds.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct NftFeeBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_custom_origins::BenchmarkHelper<RuntimeCall> for NftFeeBenchmarkHelper {
+	fn nft_fee_call() -> RuntimeCall {
+		RuntimeCall::NFT(ternoa_nft::Call::set_nft_mint_fee {
+			fee: ternoa_nft::NftMintFee::<Runtime>::get(),
+		})
+	}
+}
+
+impl pallet_custom_origins::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type NftFeeOrigin = pallet_custom_origins::NftFeeAdmin;
+	type NftFeeCalls = NftFeeCalls;
+	type WeightInfo = weights::pallet_custom_origins::WeightInfo<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = NftFeeBenchmarkHelper;
+}
+
+impl pallet_genesis_fees::Config for Runtime {}
+
+// `vested_transfer` can be used by any account. Governance reaches `force_vested_transfer`, which
+// is root only, through the mandate pallet or a root referendum.
+impl pallet_vesting::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = common::vesting::MinVestedTransfer;
+	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+	const MAX_VESTING_SCHEDULES: u32 = common::vesting::MAX_VESTING_SCHEDULES;
+}
+
+// Stored data is indexed by the nodes and kept in the block bodies, a proof that it is still
+// available is included by the block authors every block.
+impl pallet_transaction_storage::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type RuntimeCall = RuntimeCall;
+	type FeeDestination = Treasury;
+	type WeightInfo = pallet_transaction_storage::weights::SubstrateWeight<Runtime>;
+	type MaxBlockTransactions = common::transaction_storage::MaxBlockTransactions;
+	type MaxTransactionSize = common::transaction_storage::MaxTransactionSize;
+}
+
+impl pallet_indices::Config for Runtime {
+	type AccountIndex = AccountIndex;
+	type Currency = Balances;
+	type Deposit = common::indices::IndexDeposit;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_indices::weights::SubstrateWeight<Runtime>;
+}
+
+// Once its recovery is completed, the rescuer dispatches calls as the lost account through
+// `as_recovered`, which lets it move the NFTs, listings and rent contracts of that account.
+impl pallet_recovery::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_recovery::weights::SubstrateWeight<Runtime>;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type ConfigDepositBase = common::recovery::ConfigDepositBase;
+	type FriendDepositFactor = common::recovery::FriendDepositFactor;
+	type MaxFriends = common::recovery::MaxFriends;
+	type RecoveryDeposit = common::recovery::RecoveryDeposit;
 }
 
 impl pallet_multisig::Config for Runtime {
@@ -632,6 +847,23 @@ impl pallet_multisig::Config for Runtime {
 	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
 }
 
+common::impl_proxy_type_filter!();
+
+impl pallet_proxy::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type ProxyType = common::proxy::ProxyType;
+	type ProxyDepositBase = common::proxy::ProxyDepositBase;
+	type ProxyDepositFactor = common::proxy::ProxyDepositFactor;
+	type MaxProxies = common::proxy::MaxProxies;
+	type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+	type MaxPending = common::proxy::MaxPending;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = common::proxy::AnnouncementDepositBase;
+	type AnnouncementDepositFactor = common::proxy::AnnouncementDepositFactor;
+}
+
 impl pallet_identity::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
@@ -642,8 +874,8 @@ impl pallet_identity::Config for Runtime {
 	type MaxAdditionalFields = common::identity::MaxAdditionalFields;
 	type MaxRegistrars = common::identity::MaxRegistrars;
 	type Slashed = Treasury;
-	type ForceOrigin = RootOrAtLeastHalfOfCommittee;
-	type RegistrarOrigin = RootOrAtLeastHalfOfCommittee;
+	type ForceOrigin = RootOrGeneralAdmin;
+	type RegistrarOrigin = RootOrGeneralAdmin;
 	type WeightInfo = weights::pallet_identity::WeightInfo<Runtime>;
 }
 
@@ -692,7 +924,7 @@ impl pallet_assets::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Balance = Balance;
 	type Currency = Balances;
-	type ForceOrigin = RootOrAtLeastHalfOfCommittee;
+	type ForceOrigin = RootOrGeneralAdmin;
 	type AssetId = u32;
 	type AssetDeposit = common::assets::AssetDeposit;
 	type AssetAccountDeposit = common::assets::AssetAccountDeposit;
@@ -705,6 +937,15 @@ impl pallet_assets::Config for Runtime {
 	type WeightInfo = weights::pallet_assets::WeightInfo<Runtime>;
 }
 
+impl pallet_asset_tx_payment::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Fungibles = Assets;
+	type OnChargeAssetTransaction = pallet_asset_tx_payment::FungiblesAdapter<
+		common::assets::FeeAssetConversion<Runtime>,
+		common::assets::AssetFeesToTreasury<Runtime>,
+	>;
+}
+
 parameter_types! {
 	pub const MinAuctionDuration: BlockNumber = 100;
 	pub const MaxAuctionDuration: BlockNumber = 2_592_000;
@@ -755,3 +996,18 @@ impl ternoa_rent::Config for Runtime {
 	type MaximumContractAvailabilityLimit = MaximumContractAvailabilityLimit;
 	type MaximumContractDurationLimit = MaximumContractDurationLimit;
 }
+
+impl ternoa_tx_pause::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PauseOrigin = RootOrAtLeastHalfOfCommittee;
+	type UnpausableCalls = common::tx_pause::UnpausableCalls;
+	type MaxNameLen = common::tx_pause::MaxNameLen;
+	type WeightInfo = weights::ternoa_tx_pause::WeightInfo<Runtime>;
+}
+
+impl pallet_multi_block_migrations::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Migrations = crate::SteppedMigrations;
+	type StepWeight = MigrationStepWeight;
+}
@@ -0,0 +1,22 @@
+//! Weights for `pallet_custom_origins`
+//!
+//! Estimated until the benchmark CLI is run on the reference machine with:
+//! `ternoa benchmark pallet --chain=alphanet-dev --steps=50 --repeat=20
+//! --pallet=pallet_custom_origins --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --heap-pages=4096 --output ./output`
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `pallet_custom_origins`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> ternoa_runtime_common::governance::pallet_custom_origins::WeightInfo for WeightInfo<T> {
+	// The dispatched fee change is added by the pallet.
+	fn dispatch_nft_fee_change() -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+	}
+}
@@ -0,0 +1,32 @@
+//! Weights for `ternoa_fee_router`
+//!
+//! Estimated until the benchmark CLI is run on the reference machine with:
+//! `ternoa benchmark pallet --chain=alphanet-dev --steps=50 --repeat=20
+//! --pallet=ternoa_fee_router --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --heap-pages=4096 --output ./output`
+//!
+//! The routing of each fee happens while the transaction is charged and belongs to the extrinsic
+//! base weight of `benchmark overhead`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `ternoa_fee_router`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> ternoa_fee_router::WeightInfo for WeightInfo<T> {
+	// Storage: FeeRouter Split (r:0 w:1)
+	fn set_split() -> Weight {
+		Weight::from_ref_time(44_664_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: FeeRouter BlockRoutedFees (r:1 w:1)
+	fn on_finalize() -> Weight {
+		Weight::from_ref_time(10_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+}
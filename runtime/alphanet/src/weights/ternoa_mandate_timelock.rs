@@ -0,0 +1,46 @@
+//! Weights for `ternoa_mandate_timelock`
+//!
+//! Estimated until the benchmark CLI is run on the reference machine with:
+//! `ternoa benchmark pallet --chain=alphanet-dev --steps=50 --repeat=20
+//! --pallet=ternoa_mandate_timelock --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --heap-pages=4096 --output ./output`
+//!
+//! `s` is the number of tasks already in the agenda of the dispatch block, its slope is the one of
+//! `pallet_scheduler`. The weight of the mandated call is added by the pallet.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `ternoa_mandate_timelock`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> ternoa_mandate_timelock::WeightInfo for WeightInfo<T> {
+	// Storage: MandateTimelock MandateCount (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: MandateTimelock PendingMandates (r:0 w:1)
+	fn propose(s: u32, ) -> Weight {
+		Weight::from_ref_time(69_480_000 as u64)
+			.saturating_add(Weight::from_ref_time(132_000 as u64).saturating_mul(s as u64))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: MandateTimelock PendingMandates (r:1 w:1)
+	fn enact() -> Weight {
+		Weight::from_ref_time(44_664_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: MandateTimelock PendingMandates (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn veto(s: u32, ) -> Weight {
+		Weight::from_ref_time(68_252_000 as u64)
+			.saturating_add(Weight::from_ref_time(590_000 as u64).saturating_mul(s as u64))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+}
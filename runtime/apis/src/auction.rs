@@ -0,0 +1,100 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+use crate::{
+	marketplace::{MarketplaceId, SaleFees},
+	nft::{NFTId, Page},
+};
+
+/// A bid placed on an auction.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(
+	feature = "std",
+	serde(
+		rename_all = "camelCase",
+		bound(
+			serialize = "AccountId: Serialize, Balance: std::fmt::Display",
+			deserialize = "AccountId: Deserialize<'de>, Balance: std::str::FromStr"
+		)
+	)
+)]
+pub struct Bid<AccountId, Balance> {
+	pub bidder: AccountId,
+	#[cfg_attr(feature = "std", serde(with = "crate::serde_balance"))]
+	pub amount: Balance,
+}
+
+/// Current state of an auction.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(
+	feature = "std",
+	serde(
+		rename_all = "camelCase",
+		bound(
+			serialize = "AccountId: Serialize, Balance: std::fmt::Display, BlockNumber: Serialize",
+			deserialize = "AccountId: Deserialize<'de>, Balance: std::str::FromStr, BlockNumber: Deserialize<'de>"
+		)
+	)
+)]
+pub struct AuctionState<AccountId, Balance, BlockNumber> {
+	pub nft_id: NFTId,
+	pub marketplace_id: MarketplaceId,
+	pub creator: AccountId,
+	pub start_block: BlockNumber,
+	pub end_block: BlockNumber,
+	/// The auction end was pushed back because of a late bid.
+	pub is_extended: bool,
+	#[cfg_attr(feature = "std", serde(with = "crate::serde_balance"))]
+	pub start_price: Balance,
+	#[cfg_attr(feature = "std", serde(with = "crate::serde_balance::option"))]
+	pub buy_it_price: Option<Balance>,
+	/// Bids ordered from the lowest to the highest.
+	pub bidders: Vec<Bid<AccountId, Balance>>,
+	pub highest_bid: Option<Bid<AccountId, Balance>>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the `ternoa_auction` pallet.
+	pub trait AuctionApi<AccountId, Balance, BlockNumber> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the state of the auction of an NFT.
+		fn auction(nft_id: NFTId) -> Option<AuctionState<AccountId, Balance, BlockNumber>>;
+
+		/// Returns up to `limit` auctions held on a marketplace, among the auctions following
+		/// `start_after`. At most [`crate::nft::MAX_SCANNED`] auctions are read.
+		fn auctions(
+			marketplace_id: MarketplaceId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<AuctionState<AccountId, Balance, BlockNumber>>;
+
+		/// Returns the amounts a `complete_auction` call would charge and distribute if the
+		/// auction ended now. `None` if the auction doesn't exist or has no bid.
+		fn complete_auction_fees(nft_id: NFTId) -> Option<SaleFees<Balance>>;
+	}
+}
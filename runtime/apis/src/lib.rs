@@ -0,0 +1,70 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime APIs exposed by the Ternoa runtimes and used by the node RPC.
+//!
+//! The types returned here are plain views over the Ternoa pallets storage so that clients don't
+//! have to decode raw storage themselves.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod auction;
+pub mod mandate;
+pub mod marketplace;
+pub mod nft;
+pub mod rent;
+pub mod staking_payout;
+
+/// Serializes balances as strings so that JavaScript clients don't lose precision.
+#[cfg(feature = "std")]
+pub mod serde_balance {
+	use serde::{de::Error, Deserialize, Deserializer, Serializer};
+	use std::{fmt::Display, str::FromStr};
+
+	pub fn serialize<S: Serializer, T: Display>(t: &T, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&t.to_string())
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>, T: FromStr>(
+		deserializer: D,
+	) -> Result<T, D::Error> {
+		let s = String::deserialize(deserializer)?;
+		s.parse::<T>().map_err(|_| D::Error::custom("Parse from string failed"))
+	}
+
+	/// Same as the parent module, for optional balances.
+	pub mod option {
+		use super::*;
+
+		pub fn serialize<S: Serializer, T: Display>(
+			t: &Option<T>,
+			serializer: S,
+		) -> Result<S::Ok, S::Error> {
+			match t {
+				Some(t) => serializer.serialize_some(&t.to_string()),
+				None => serializer.serialize_none(),
+			}
+		}
+
+		pub fn deserialize<'de, D: Deserializer<'de>, T: FromStr>(
+			deserializer: D,
+		) -> Result<Option<T>, D::Error> {
+			Option::<String>::deserialize(deserializer)?
+				.map(|s| s.parse::<T>().map_err(|_| D::Error::custom("Parse from string failed")))
+				.transpose()
+		}
+	}
+}
@@ -0,0 +1,48 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Index of a mandate.
+pub type MandateIndex = u32;
+
+/// A mandate of the technical committee waiting for its delay to pass.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PendingMandate<Hash, BlockNumber> {
+	pub index: MandateIndex,
+	/// Hash of the call to dispatch.
+	pub call_hash: Hash,
+	/// Block at which the call is dispatched, unless it is vetoed before.
+	pub dispatch_at: BlockNumber,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the time-locked mandates.
+	pub trait MandateApi<Hash, BlockNumber> where
+		Hash: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the pending mandates, oldest first.
+		fn pending_mandates() -> Vec<PendingMandate<Hash, BlockNumber>>;
+	}
+}
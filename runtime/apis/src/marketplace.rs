@@ -0,0 +1,97 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+
+use crate::nft::{NFTId, Page};
+
+/// Identifier of a marketplace.
+pub type MarketplaceId = u32;
+
+/// How the price paid for an NFT is split between the different parties.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(
+	feature = "std",
+	serde(
+		rename_all = "camelCase",
+		bound(
+			serialize = "Balance: std::fmt::Display",
+			deserialize = "Balance: std::str::FromStr"
+		)
+	)
+)]
+pub struct SaleFees<Balance> {
+	/// Total amount paid by the buyer.
+	#[cfg_attr(feature = "std", serde(with = "crate::serde_balance"))]
+	pub price: Balance,
+	/// Commission taken by the marketplace owner.
+	#[cfg_attr(feature = "std", serde(with = "crate::serde_balance"))]
+	pub commission: Balance,
+	/// Royalty paid to the NFT creator.
+	#[cfg_attr(feature = "std", serde(with = "crate::serde_balance"))]
+	pub royalty: Balance,
+	/// What is left for the seller.
+	#[cfg_attr(feature = "std", serde(with = "crate::serde_balance"))]
+	pub seller_proceeds: Balance,
+}
+
+/// An NFT listed for sale on a marketplace.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(
+	feature = "std",
+	serde(
+		rename_all = "camelCase",
+		bound(
+			serialize = "AccountId: Serialize, Balance: std::fmt::Display",
+			deserialize = "AccountId: Deserialize<'de>, Balance: std::str::FromStr"
+		)
+	)
+)]
+pub struct Listing<AccountId, Balance> {
+	pub nft_id: NFTId,
+	pub marketplace_id: MarketplaceId,
+	pub seller: AccountId,
+	/// Price after commission and royalty, as they would be charged by `buy_nft` right now.
+	pub fees: SaleFees<Balance>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the `ternoa_marketplace` pallet.
+	pub trait MarketplaceApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Returns up to `limit` NFTs listed on a marketplace, among the listings following
+		/// `start_after`. At most [`crate::nft::MAX_SCANNED`] listings are read.
+		fn listings(
+			marketplace_id: MarketplaceId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<Listing<AccountId, Balance>>;
+
+		/// Returns the listing of an NFT, if it is listed.
+		fn listing(nft_id: NFTId) -> Option<Listing<AccountId, Balance>>;
+
+		/// Returns the amounts a `buy_nft` call would charge and distribute for this NFT.
+		fn buy_nft_fees(nft_id: NFTId) -> Option<SaleFees<Balance>>;
+	}
+}
@@ -0,0 +1,124 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{Permill, RuntimeDebug};
+use sp_std::vec::Vec;
+
+/// Identifier of an NFT.
+pub type NFTId = u32;
+
+/// Identifier of a collection.
+pub type CollectionId = u32;
+
+/// Maximum number of items returned by one paginated call.
+pub const MAX_PAGE_SIZE: u32 = 1_000;
+
+/// Maximum number of storage entries read by one call scanning a storage map.
+pub const MAX_SCANNED: u32 = 10_000;
+
+/// Page of the items found by a call scanning a storage map.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Page<Item> {
+	pub items: Vec<Item>,
+	/// NFT to pass as `start_after` to resume the scan, `None` once the whole map was read.
+	pub next: Option<NFTId>,
+}
+
+/// State flags of an NFT.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct NftStatus<AccountId> {
+	/// The NFT was converted to a capsule.
+	pub is_capsule: bool,
+	/// The NFT is a secret NFT.
+	pub is_secret: bool,
+	/// The NFT is listed on a marketplace.
+	pub is_listed: bool,
+	/// The NFT is currently delegated.
+	pub is_delegated: bool,
+	/// Account the NFT is delegated to, if any.
+	pub delegated_to: Option<AccountId>,
+	/// The NFT cannot be transferred.
+	pub is_soulbound: bool,
+	/// The NFT is under a rent contract.
+	pub is_rented: bool,
+}
+
+/// Decoded view of an NFT.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct NftInfo<AccountId> {
+	pub nft_id: NFTId,
+	pub owner: AccountId,
+	pub creator: AccountId,
+	pub offchain_data: Vec<u8>,
+	pub collection_id: Option<CollectionId>,
+	pub royalty: Permill,
+	pub status: NftStatus<AccountId>,
+}
+
+/// Decoded view of a collection.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CollectionInfo<AccountId> {
+	pub collection_id: CollectionId,
+	pub owner: AccountId,
+	pub offchain_data: Vec<u8>,
+	/// Number of NFTs in the collection.
+	pub size: u32,
+	pub limit: Option<u32>,
+	pub is_closed: bool,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the `ternoa_nft` pallet.
+	pub trait NftApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// Returns the NFT with the given id.
+		fn nft(nft_id: NFTId) -> Option<NftInfo<AccountId>>;
+
+		/// Returns up to `limit` NFTs owned by `owner`, among the NFTs following `start_after`. At
+		/// most [`MAX_SCANNED`] NFTs are read.
+		fn nfts_by_owner(
+			owner: AccountId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<NftInfo<AccountId>>;
+
+		/// Returns the collection with the given id.
+		fn collection(collection_id: CollectionId) -> Option<CollectionInfo<AccountId>>;
+
+		/// Returns up to `limit` NFTs of a collection, skipping the first `offset` ones.
+		fn collection_nfts(
+			collection_id: CollectionId,
+			offset: u32,
+			limit: u32,
+		) -> Vec<NftInfo<AccountId>>;
+
+		/// Returns the secret, capsule, delegation and other state flags of an NFT.
+		fn nft_status(nft_id: NFTId) -> Option<NftStatus<AccountId>>;
+	}
+}
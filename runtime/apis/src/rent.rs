@@ -0,0 +1,123 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+use crate::nft::{NFTId, Page};
+
+/// How long a rent contract lasts once it has started.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum RentDuration<BlockNumber> {
+	/// The contract ends after the given number of blocks.
+	Fixed(BlockNumber),
+	/// The rentee pays every `period` blocks, until `max_duration` is reached if there is one.
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	Subscription { period: BlockNumber, max_duration: Option<BlockNumber> },
+}
+
+/// A rent contract, either waiting for a rentee or running.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RentContractInfo<AccountId, BlockNumber> {
+	pub nft_id: NFTId,
+	pub renter: AccountId,
+	pub rentee: Option<AccountId>,
+	/// Block at which the contract was accepted, `None` while it waits for a rentee.
+	pub start_block: Option<BlockNumber>,
+	pub duration: RentDuration<BlockNumber>,
+	pub renter_can_revoke: bool,
+	/// Block at which the contract will end, if it has started and has a bounded duration.
+	pub end_block: Option<BlockNumber>,
+}
+
+/// What happened to a rent contract.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum ContractEventKind<AccountId> {
+	/// The NFT was put up for rent.
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	Created { renter: AccountId },
+	/// A rentee was accepted and the contract started.
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	Accepted { rentee: AccountId },
+	/// The renter or the rentee revoked the contract.
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	Revoked { revoked_by: AccountId },
+	/// The contract reached its end, or was ended after a revocation.
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	Ended { revoked_by: Option<AccountId> },
+	/// Nobody rented the NFT before the end of its availability period.
+	Expired,
+	/// The renter withdrew the NFT before it was rented.
+	Canceled,
+}
+
+/// A rent contract event, as found in the events of a block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ContractEvent<AccountId> {
+	pub nft_id: NFTId,
+	pub kind: ContractEventKind<AccountId>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the `ternoa_rent` pallet.
+	pub trait RentApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the rent contract of an NFT.
+		fn contract(nft_id: NFTId) -> Option<RentContractInfo<AccountId, BlockNumber>>;
+
+		/// Returns up to `limit` contracts offered by `renter`, among the contracts following
+		/// `start_after`. At most [`crate::nft::MAX_SCANNED`] contracts are read.
+		fn contracts_by_renter(
+			renter: AccountId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<RentContractInfo<AccountId, BlockNumber>>;
+
+		/// Returns up to `limit` contracts rented by `rentee`, among the contracts following
+		/// `start_after`. At most [`crate::nft::MAX_SCANNED`] contracts are read.
+		fn contracts_by_rentee(
+			rentee: AccountId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<RentContractInfo<AccountId, BlockNumber>>;
+
+		/// Returns up to `limit` running contracts ending at or before `until`, among the
+		/// contracts following `start_after`. At most [`crate::nft::MAX_SCANNED`] contracts are
+		/// read. The contracts come in storage order, not sorted by end block.
+		fn upcoming_expirations(
+			until: BlockNumber,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<RentContractInfo<AccountId, BlockNumber>>;
+
+		/// Returns the rent contract events deposited in the block the call is made at.
+		fn contract_events() -> Vec<ContractEvent<AccountId>>;
+	}
+}
@@ -0,0 +1,65 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Index of a staking era.
+pub type EraIndex = u32;
+
+/// Progress of the automatic payouts of a validator and its nominators.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ValidatorPayouts {
+	/// Eras queued for payout, oldest first.
+	pub pending: Vec<EraIndex>,
+	/// Eras already paid out, within the staking history depth.
+	pub paid: Vec<EraIndex>,
+}
+
+/// Progress of the payouts of a nominator, within the staking history depth.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct NominatorPayouts {
+	/// Eras with a validator of the nominator left to pay out, oldest first.
+	pub pending: Vec<EraIndex>,
+	/// Eras paid out through all the validators of the nominator, oldest first.
+	pub paid: Vec<EraIndex>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the automatic staking payouts.
+	pub trait StakingPayoutApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// Returns the pending and paid eras of a validator.
+		fn validator_payouts(validator: AccountId) -> ValidatorPayouts;
+
+		/// Returns the pending and paid eras of a nominator.
+		///
+		/// Only the validators the nominator currently nominates are looked at, the eras it was
+		/// rewarded for through other validators are left out.
+		fn nominator_payouts(nominator: AccountId) -> NominatorPayouts;
+
+		/// Returns the number of payouts waiting to be processed.
+		fn pending_payouts() -> u32;
+	}
+}
@@ -14,7 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
 
-use frame_support::parameter_types;
+use frame_support::{
+	parameter_types,
+	traits::fungibles::{Balanced, CreditOf},
+};
+use pallet_asset_tx_payment::HandleCredit;
+use sp_runtime::traits::ConvertInto;
+use sp_std::marker::PhantomData;
 
 use crate::constants::currency::CAPS;
 
@@ -26,3 +32,45 @@ parameter_types! {
 	pub const MetadataDepositBase: u128 = 100 * CAPS;
 	pub const MetadataDepositPerByte: u128 = 10 * CAPS;
 }
+
+/// Converts transaction fees from CAPS to an asset using the ratio between the asset `min_balance`
+/// and the CAPS existential deposit. Only sufficient assets can be used, and the rate of an asset
+/// can be changed by the `ForceOrigin` through `force_asset_status`.
+pub type FeeAssetConversion<R> =
+	pallet_assets::BalanceToAssetBalance<pallet_balances::Pallet<R>, R, ConvertInto>;
+
+/// Transaction fees paid in assets can't be added to the CAPS staking rewards, they are sent to
+/// the treasury instead. If the treasury can't receive them, e.g. because the fees are below the
+/// `min_balance` of an asset it doesn't hold yet, they go to the author of the block. They are
+/// only burned when neither of them can receive them.
+pub struct AssetFeesToTreasury<R>(PhantomData<R>);
+
+type Assets<R> = pallet_assets::Pallet<R>;
+
+impl<R> HandleCredit<R::AccountId, Assets<R>> for AssetFeesToTreasury<R>
+where
+	R: pallet_assets::Config + pallet_treasury::Config + pallet_authorship::Config,
+{
+	fn handle_credit(credit: CreditOf<R::AccountId, Assets<R>>) {
+		let treasury = pallet_treasury::Pallet::<R>::account_id();
+		let credit = match <Assets<R> as Balanced<R::AccountId>>::resolve(&treasury, credit) {
+			Ok(()) => return,
+			Err(credit) => credit,
+		};
+
+		let credit = match <pallet_authorship::Pallet<R>>::author() {
+			Some(author) => match <Assets<R> as Balanced<R::AccountId>>::resolve(&author, credit) {
+				Ok(()) => return,
+				Err(credit) => credit,
+			},
+			None => credit,
+		};
+
+		log::warn!(
+			target: "runtime::assets",
+			"Burned {:?} of the asset {:?} paid as fees",
+			credit.peek(),
+			credit.asset(),
+		);
+	}
+}
@@ -0,0 +1,94 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Shared implementation of the `AuctionApi` runtime API.
+
+use sp_std::vec::Vec;
+use ternoa_auction::{AuctionData, Auctions, BalanceOf};
+use ternoa_marketplace::Marketplaces;
+use ternoa_nft::Nfts;
+use ternoa_runtime_apis::{
+	auction::{AuctionState, Bid},
+	marketplace::{MarketplaceId, SaleFees},
+	nft::{NFTId, Page},
+};
+
+use crate::{marketplace::sale_fees, nft::scan};
+
+/// Builds an [`AuctionState`] out of the id and the stored data of an auction.
+fn auction_state<T: ternoa_auction::Config>(
+	nft_id: NFTId,
+	data: AuctionData<T::AccountId, T::BlockNumber, BalanceOf<T>, T::BidderListLengthLimit>,
+) -> AuctionState<T::AccountId, BalanceOf<T>, T::BlockNumber> {
+	let bidders: Vec<_> = data
+		.bidders
+		.list
+		.into_iter()
+		.map(|(bidder, amount)| Bid { bidder, amount })
+		.collect();
+	AuctionState {
+		nft_id,
+		marketplace_id: data.marketplace_id,
+		creator: data.creator,
+		start_block: data.start_block,
+		end_block: data.end_block,
+		is_extended: data.is_extended,
+		start_price: data.start_price,
+		buy_it_price: data.buy_it_price,
+		highest_bid: bidders.last().cloned(),
+		bidders,
+	}
+}
+
+pub fn auction<T: ternoa_auction::Config>(
+	nft_id: NFTId,
+) -> Option<AuctionState<T::AccountId, BalanceOf<T>, T::BlockNumber>> {
+	Auctions::<T>::get(nft_id).map(|data| auction_state::<T>(nft_id, data))
+}
+
+pub fn auctions<T: ternoa_auction::Config>(
+	marketplace_id: MarketplaceId,
+	start_after: Option<NFTId>,
+	limit: u32,
+) -> Page<AuctionState<T::AccountId, BalanceOf<T>, T::BlockNumber>> {
+	let entries = match start_after {
+		Some(nft_id) => Auctions::<T>::iter_from(Auctions::<T>::hashed_key_for(nft_id)),
+		None => Auctions::<T>::iter(),
+	};
+
+	scan(entries, limit, |nft_id, data| {
+		(data.marketplace_id == marketplace_id).then(|| auction_state::<T>(nft_id, data))
+	})
+}
+
+/// The auction is settled at the highest bid, with the commission of the marketplace as it is
+/// configured at the time of the call.
+pub fn complete_auction_fees<
+	T: ternoa_auction::Config + ternoa_marketplace::Config + ternoa_nft::Config,
+>(
+	nft_id: NFTId,
+) -> Option<SaleFees<ternoa_marketplace::BalanceOf<T>>>
+where
+	ternoa_marketplace::BalanceOf<T>: From<BalanceOf<T>>,
+{
+	let auction = Auctions::<T>::get(nft_id)?;
+	let (_, price) = auction.bidders.list.last().cloned()?;
+	let commission_fee = Marketplaces::<T>::get(auction.marketplace_id)
+		.and_then(|marketplace| marketplace.commission_fee);
+	let royalty = Nfts::<T>::get(nft_id).map(|nft| nft.royalty).unwrap_or_default();
+
+	Some(sale_fees(price.into(), commission_fee, royalty))
+}
@@ -14,10 +14,27 @@
 // You should have received a copy of the GNU General Public License
 // along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
 
-use frame_support::parameter_types;
+use frame_support::{
+	parameter_types,
+	traits::{Currency, OnUnbalanced},
+};
+use pallet_balances::NegativeImbalance;
 use ternoa_core_primitives::BlockNumber;
 
 parameter_types! {
 	// Authorship
 	pub const UncleGenerations: BlockNumber = 0;
 }
+
+/// Gives the whole amount to the author of the current block.
+pub struct ToAuthor<R>(sp_std::marker::PhantomData<R>);
+impl<R> OnUnbalanced<NegativeImbalance<R>> for ToAuthor<R>
+where
+	R: pallet_balances::Config + pallet_authorship::Config,
+{
+	fn on_nonzero_unbalanced(amount: NegativeImbalance<R>) {
+		if let Some(author) = <pallet_authorship::Pallet<R>>::author() {
+			<pallet_balances::Pallet<R>>::resolve_creating(&author, amount);
+		}
+	}
+}
@@ -15,25 +15,26 @@
 // along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
 
 use frame_support::parameter_types;
+use sp_runtime::Permill;
 use ternoa_core_primitives::{Balance, BlockNumber};
 
-use crate::{
-	constants::{
-		currency::UNITS,
-		time::{DAYS, HOURS, MINUTES},
-	},
-	prod_or_fast,
+use crate::constants::{
+	currency::{CENTS, UNITS},
+	time::DAYS,
 };
 
 parameter_types! {
-	pub const LaunchPeriod: BlockNumber = prod_or_fast!(28 * DAYS, 1 * MINUTES);
-	pub const VotingPeriod: BlockNumber = prod_or_fast!(28 * DAYS, 1 * MINUTES);
-	pub const FastTrackVotingPeriod: BlockNumber = prod_or_fast!(3 * HOURS, 1 * MINUTES);
-	pub const MinimumDeposit: Balance = 100 * UNITS;
-	pub const EnactmentPeriod: BlockNumber = prod_or_fast!(28 * DAYS, 1 * MINUTES);
-	pub const VoteLockingPeriod: BlockNumber = prod_or_fast!(28 * DAYS, 1 * MINUTES); // Should be same as EnactmentPeriod
-	pub const CooloffPeriod: BlockNumber = prod_or_fast!(7 * DAYS, 1 * MINUTES);
-	pub const InstantAllowed: bool = true;
-	pub const MaxVotes: u32 = 100;
-	pub const MaxProposals: u32 = 100;
+	pub const BountyDepositBase: Balance = 1 * UNITS;
+	pub const BountyDepositPayoutDelay: BlockNumber = 8 * DAYS;
+	pub const BountyUpdatePeriod: BlockNumber = 90 * DAYS;
+	pub const MaximumReasonLength: u32 = 16384;
+	pub const CuratorDepositMultiplier: Permill = Permill::from_percent(50);
+	pub const CuratorDepositMin: Balance = 10 * UNITS;
+	pub const CuratorDepositMax: Balance = 200 * UNITS;
+	pub const BountyValueMinimum: Balance = 10 * UNITS;
+	pub const DataDepositPerByte: Balance = 1 * CENTS;
+
+	// Child bounties
+	pub const MaxActiveChildBountyCount: u32 = 100;
+	pub const ChildBountyValueMinimum: Balance = 1 * UNITS;
 }
@@ -0,0 +1,64 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Mint fees of the Ternoa pallets set at genesis.
+//!
+//! The NFT and marketplace pallets have no genesis config for their fees, this pallet gives them
+//! one so that a chain spec holds them in its human form as well as in its raw form.
+
+#[frame_support::pallet]
+pub mod pallet_genesis_fees {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config + ternoa_nft::Config + ternoa_marketplace::Config
+	{
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The fees left to `None` keep the defaults of their pallet.
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub nft_mint_fee: Option<ternoa_nft::BalanceOf<T>>,
+		pub secret_nft_mint_fee: Option<ternoa_nft::BalanceOf<T>>,
+		pub marketplace_mint_fee: Option<ternoa_marketplace::BalanceOf<T>>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { nft_mint_fee: None, secret_nft_mint_fee: None, marketplace_mint_fee: None }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			if let Some(fee) = self.nft_mint_fee {
+				ternoa_nft::NftMintFee::<T>::put(fee);
+			}
+			if let Some(fee) = self.secret_nft_mint_fee {
+				ternoa_nft::SecretNftMintFee::<T>::put(fee);
+			}
+			if let Some(fee) = self.marketplace_mint_fee {
+				ternoa_marketplace::MarketplaceMintFee::<T>::put(fee);
+			}
+		}
+	}
+}
@@ -0,0 +1,38 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks of [`pallet_custom_origins`].
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use frame_benchmarking::benchmarks;
+use frame_support::traits::EnsureOrigin;
+use sp_std::boxed::Box;
+
+use super::pallet_custom_origins::{BenchmarkHelper, Call, Config, Event, Pallet};
+
+benchmarks! {
+	// The fee change itself is dispatched too, so the result also covers the call given by the
+	// benchmark helper.
+	dispatch_nft_fee_change {
+		let origin = T::NftFeeOrigin::successful_origin();
+		let call = Box::new(T::BenchmarkHelper::nft_fee_call());
+	}: _<T::RuntimeOrigin>(origin, call)
+	verify {
+		let event: <T as Config>::RuntimeEvent = Event::<T>::NftFeeChangeDispatched.into();
+		frame_system::Pallet::<T>::assert_last_event(event.into());
+	}
+}
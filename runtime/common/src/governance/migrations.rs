@@ -0,0 +1,179 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Migrations from `pallet_democracy` to OpenGov.
+
+use frame_support::{
+	storage::unhashed,
+	storage_alias,
+	traits::{Get, LockIdentifier, LockableCurrency, ReservableCurrency},
+	weights::Weight,
+	Identity, StorageHasher, Twox128, Twox64Concat,
+};
+use pallet_democracy::{PreimageStatus, PropIndex};
+use parity_scale_codec::{Decode, Encode};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+use crate::migrations::SteppedMigration;
+
+/// Lock set by `pallet_democracy` on the balances of the voters.
+const DEMOCRACY_ID: LockIdentifier = *b"democrac";
+
+type BalanceOf<T> = <T as pallet_balances::Config>::Balance;
+type Balances<T> = pallet_balances::Pallet<T>;
+
+// The pallet is no longer part of the runtime, its storage is read under its former name. Only
+// the keys of the votes are needed, their values are not decoded.
+#[storage_alias]
+type VotingOf<T: pallet_balances::Config> =
+	StorageMap<Democracy, Twox64Concat, <T as frame_system::Config>::AccountId, ()>;
+#[storage_alias]
+type DepositOf<T: pallet_balances::Config> = StorageMap<
+	Democracy,
+	Twox64Concat,
+	PropIndex,
+	(Vec<<T as frame_system::Config>::AccountId>, BalanceOf<T>),
+>;
+#[storage_alias]
+type Preimages<T: pallet_balances::Config> = StorageMap<
+	Democracy,
+	Identity,
+	<T as frame_system::Config>::Hash,
+	PreimageStatus<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>,
+>;
+
+/// What [`UnlockDemocracyFunds`] releases or removes next.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+pub enum UnlockPhase {
+	/// The voting locks.
+	Votes,
+	/// The deposits of the proposals.
+	Deposits,
+	/// The deposits of the preimages.
+	Preimages,
+	/// The rest of the storage of the pallet.
+	Storage,
+}
+
+impl UnlockPhase {
+	fn next(self) -> Option<Self> {
+		match self {
+			Self::Votes => Some(Self::Deposits),
+			Self::Deposits => Some(Self::Preimages),
+			Self::Preimages => Some(Self::Storage),
+			Self::Storage => None,
+		}
+	}
+}
+
+/// Gives back what `pallet_democracy` still holds and removes its storage, to be run in the
+/// upgrade removing the pallet from the runtime.
+///
+/// The voting locks are removed and the deposits of the proposals and of the preimages are
+/// unreserved, then the rest of the storage of the pallet is cleared. The referenda still ongoing
+/// are dropped with it, the upgrade has to be enacted once the approved ones were enacted.
+pub struct UnlockDemocracyFunds<T>(PhantomData<T>);
+
+impl<T: pallet_balances::Config> UnlockDemocracyFunds<T> {
+	fn prefix() -> [u8; 16] {
+		Twox128::hash(b"Democracy")
+	}
+
+	/// Releases the next item of the phase, or removes up to `limit` of the remaining storage.
+	/// Returns the weight used and whether the phase is over.
+	fn release_next(phase: UnlockPhase, limit: Weight) -> (Weight, bool) {
+		let db = T::DbWeight::get();
+		match phase {
+			UnlockPhase::Votes => match VotingOf::<T>::drain().next() {
+				Some((who, ())) => {
+					Balances::<T>::remove_lock(DEMOCRACY_ID, &who);
+					(db.reads_writes(2, 2), false)
+				},
+				None => (db.reads(1), true),
+			},
+			UnlockPhase::Deposits => match DepositOf::<T>::drain().next() {
+				Some((_, (depositors, deposit))) => {
+					// Seconding a proposal reserves the deposit once more.
+					for who in &depositors {
+						Balances::<T>::unreserve(who, deposit);
+					}
+					let count = depositors.len() as u64;
+					(db.reads_writes(1 + count, 1 + count), false)
+				},
+				None => (db.reads(1), true),
+			},
+			UnlockPhase::Preimages => match Preimages::<T>::drain().next() {
+				Some((_, status)) => {
+					if let PreimageStatus::Available { provider, deposit, .. } = status {
+						Balances::<T>::unreserve(&provider, deposit);
+					}
+					(db.reads_writes(2, 2), false)
+				},
+				None => (db.reads(1), true),
+			},
+			UnlockPhase::Storage => {
+				let keys = (limit.ref_time() / db.write.max(1)).clamp(1, u32::MAX as u64) as u32;
+				let result = unhashed::clear_prefix(&Self::prefix(), Some(keys), None);
+				let weight = db.reads_writes(result.loops as u64, result.unique as u64);
+				(weight, result.maybe_cursor.is_none())
+			},
+		}
+	}
+}
+
+impl<T: pallet_balances::Config> SteppedMigration for UnlockDemocracyFunds<T> {
+	const NAME: &'static str = "UnlockDemocracyFunds";
+
+	type Cursor = UnlockPhase;
+
+	fn step(cursor: Option<UnlockPhase>, limit: Weight) -> (Option<UnlockPhase>, Weight) {
+		let mut phase = cursor.unwrap_or(UnlockPhase::Votes);
+		let mut weight = Weight::zero();
+		// At least one item is released so that each step makes progress.
+		loop {
+			let (used, is_over) = Self::release_next(phase, limit.saturating_sub(weight));
+			weight.saturating_accrue(used);
+			if is_over {
+				phase = match phase.next() {
+					Some(next) => next,
+					None => return (None, weight),
+				};
+			}
+
+			if !weight.all_lt(limit) {
+				return (Some(phase), weight)
+			}
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		frame_support::ensure!(
+			pallet_balances::Locks::<T>::iter_values()
+				.all(|locks| locks.iter().all(|lock| lock.id != DEMOCRACY_ID)),
+			"A democracy lock is left"
+		);
+		frame_support::ensure!(
+			!unhashed::contains_prefixed_key(&Self::prefix()),
+			"The democracy storage is not cleared"
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,60 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! OpenGov: referenda voted with conviction, on tracks having their own origin and parameters.
+//!
+//! Each track dispatches its referenda with a dedicated origin of [`pallet_custom_origins`], the
+//! pallets accept those origins instead of collective proportions. Urgent calls can be
+//! whitelisted by the technical committee and then dispatched through the whitelisted caller
+//! track, which decides faster.
+//!
+//! `pallet_democracy` was removed from the runtimes, see [`migrations`] for the release of its
+//! locks and deposits.
+
+use frame_support::parameter_types;
+use ternoa_core_primitives::{Balance, BlockNumber};
+
+use crate::{
+	constants::{
+		currency::UNITS,
+		time::{DAYS, MINUTES},
+	},
+	prod_or_fast,
+};
+
+mod benchmarking;
+pub mod migrations;
+pub mod origins;
+mod tracks;
+
+pub use origins::pallet_custom_origins;
+pub use tracks::TracksInfo;
+
+parameter_types! {
+	// Conviction voting
+	pub const VoteLockingPeriod: BlockNumber = prod_or_fast!(7 * DAYS, 1 * MINUTES);
+	pub const MaxVotes: u32 = 512;
+
+	// Referenda
+	pub const AlarmInterval: BlockNumber = 1;
+	pub const SubmissionDeposit: Balance = 10 * UNITS;
+	pub const UndecidingTimeout: BlockNumber = prod_or_fast!(14 * DAYS, 10 * MINUTES);
+	pub const MaxQueued: u32 = 100;
+
+	// Origins
+	pub const TreasurerMaxSpend: Balance = 100_000 * UNITS;
+	pub const MaxBalance: Balance = Balance::MAX;
+}
@@ -0,0 +1,178 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Origins of the OpenGov tracks.
+
+#[frame_support::pallet]
+pub mod pallet_custom_origins {
+	use frame_support::{
+		dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+		pallet_prelude::*,
+		traits::Contains,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_std::boxed::Box;
+	use ternoa_core_primitives::Balance;
+
+	use crate::governance::TreasurerMaxSpend;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The overarching call type.
+		type RuntimeCall: Parameter
+			+ Dispatchable<
+				RuntimeOrigin = <Self as frame_system::Config>::RuntimeOrigin,
+				PostInfo = PostDispatchInfo,
+			> + GetDispatchInfo;
+
+		/// Origin allowed to change the NFT fees.
+		type NftFeeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Root only calls changing the NFT fees.
+		type NftFeeCalls: Contains<<Self as Config>::RuntimeCall>;
+
+		/// Weight information for the extrinsics of this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Provides an NFT fee call to the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<<Self as Config>::RuntimeCall>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[derive(PartialEq, Eq, Clone, MaxEncodedLen, Encode, Decode, TypeInfo, RuntimeDebug)]
+	#[pallet::origin]
+	pub enum Origin {
+		/// Origin able to spend from the treasury, up to [`TreasurerMaxSpend`].
+		Treasurer,
+		/// Origin able to change the NFT and marketplace fees.
+		NftFeeAdmin,
+		/// Origin able to administrate the bridge.
+		BridgeAdmin,
+		/// Origin able to administrate staking.
+		StakingAdmin,
+		/// Origin able to force the identities, the registrars and the assets, and to schedule
+		/// calls.
+		GeneralAdmin,
+		/// Origin able to change the members of the technical committee.
+		MembershipAdmin,
+		/// Origin able to dispatch a call whitelisted by the technical committee.
+		WhitelistedCaller,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An NFT fee change was dispatched.
+		NftFeeChangeDispatched,
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The call does not change an NFT fee.
+		NotAnNftFeeCall,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Dispatches a root only NFT fee change with the root origin.
+		///
+		/// The error of the change is returned, nothing is dispatched then.
+		#[pallet::weight({
+			let info = call.get_dispatch_info();
+			(
+				T::WeightInfo::dispatch_nft_fee_change().saturating_add(info.weight),
+				info.class,
+			)
+		})]
+		pub fn dispatch_nft_fee_change(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			T::NftFeeOrigin::ensure_origin(origin)?;
+			ensure!(T::NftFeeCalls::contains(&call), Error::<T>::NotAnNftFeeCall);
+
+			let call_weight = call.get_dispatch_info().weight;
+			let post_info =
+				call.dispatch(frame_system::RawOrigin::Root.into()).map_err(|e| e.error)?;
+			Self::deposit_event(Event::NftFeeChangeDispatched);
+
+			let actual_weight = post_info.actual_weight.unwrap_or(call_weight);
+			Ok(Some(T::WeightInfo::dispatch_nft_fee_change().saturating_add(actual_weight)).into())
+		}
+	}
+
+	macro_rules! decl_unit_ensures {
+		( $name:ident: $success_type:ty = $success:expr ) => {
+			pub struct $name;
+			impl<O: Into<Result<Origin, O>> + From<Origin>> EnsureOrigin<O> for $name {
+				type Success = $success_type;
+				fn try_origin(o: O) -> Result<Self::Success, O> {
+					o.into().and_then(|o| match o {
+						Origin::$name => Ok($success),
+						r => Err(O::from(r)),
+					})
+				}
+				#[cfg(feature = "runtime-benchmarks")]
+				fn successful_origin() -> O {
+					O::from(Origin::$name)
+				}
+			}
+		};
+		( $name:ident ) => { decl_unit_ensures! { $name: () = () } };
+		( $name:ident: $success_type:ty = $success:expr, $( $rest:tt )* ) => {
+			decl_unit_ensures! { $name: $success_type = $success }
+			decl_unit_ensures! { $( $rest )* }
+		};
+		( $name:ident, $( $rest:tt )* ) => {
+			decl_unit_ensures! { $name }
+			decl_unit_ensures! { $( $rest )* }
+		};
+		() => {}
+	}
+	decl_unit_ensures!(
+		Treasurer: Balance = TreasurerMaxSpend::get(),
+		NftFeeAdmin,
+		BridgeAdmin,
+		StakingAdmin,
+		GeneralAdmin,
+		MembershipAdmin,
+		WhitelistedCaller,
+	);
+
+	/// Provides the benchmarks with a call accepted by [`Config::NftFeeCalls`].
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkHelper<Call> {
+		fn nft_fee_call() -> Call;
+	}
+
+	/// Weight functions needed for `pallet_custom_origins`.
+	pub trait WeightInfo {
+		fn dispatch_nft_fee_change() -> Weight;
+	}
+
+	// For tests
+	impl WeightInfo for () {
+		fn dispatch_nft_fee_change() -> Weight {
+			Weight::from_ref_time(20_000_000 as u64)
+		}
+	}
+}
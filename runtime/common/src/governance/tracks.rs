@@ -0,0 +1,210 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tracks of the OpenGov referenda.
+
+use frame_system::RawOrigin;
+use pallet_referenda::{Curve, TrackInfo};
+use sp_runtime::FixedI64;
+use sp_std::marker::PhantomData;
+use ternoa_core_primitives::{AccountId, Balance, BlockNumber};
+
+use super::pallet_custom_origins::Origin;
+use crate::constants::{
+	currency::UNITS,
+	time::{DAYS, HOURS, MINUTES},
+};
+
+const fn percent(x: i32) -> FixedI64 {
+	FixedI64::from_rational(x as u128, 100)
+}
+
+const APP_ROOT: Curve = Curve::make_reciprocal(4, 28, percent(80), percent(50), percent(100));
+const SUP_ROOT: Curve = Curve::make_linear(28, 28, percent(0), percent(50));
+const APP_WHITELISTED_CALLER: Curve =
+	Curve::make_reciprocal(16, 28 * 24, percent(96), percent(50), percent(100));
+const SUP_WHITELISTED_CALLER: Curve =
+	Curve::make_reciprocal(1, 28, percent(20), percent(5), percent(50));
+const APP_TREASURER: Curve = Curve::make_reciprocal(4, 28, percent(80), percent(50), percent(100));
+const SUP_TREASURER: Curve = Curve::make_linear(28, 28, percent(0), percent(50));
+const APP_NFT_FEE_ADMIN: Curve = Curve::make_linear(17, 28, percent(50), percent(100));
+const SUP_NFT_FEE_ADMIN: Curve =
+	Curve::make_reciprocal(12, 28, percent(1), percent(0), percent(50));
+const APP_BRIDGE_ADMIN: Curve = Curve::make_linear(17, 28, percent(50), percent(100));
+const SUP_BRIDGE_ADMIN: Curve = Curve::make_reciprocal(12, 28, percent(1), percent(0), percent(50));
+const APP_STAKING_ADMIN: Curve = Curve::make_linear(17, 28, percent(50), percent(100));
+const SUP_STAKING_ADMIN: Curve =
+	Curve::make_reciprocal(12, 28, percent(1), percent(0), percent(50));
+const APP_GENERAL_ADMIN: Curve =
+	Curve::make_reciprocal(4, 28, percent(80), percent(50), percent(100));
+const SUP_GENERAL_ADMIN: Curve =
+	Curve::make_reciprocal(7, 28, percent(10), percent(0), percent(50));
+const APP_MEMBERSHIP_ADMIN: Curve = Curve::make_linear(17, 28, percent(50), percent(100));
+const SUP_MEMBERSHIP_ADMIN: Curve =
+	Curve::make_reciprocal(12, 28, percent(1), percent(0), percent(50));
+
+const TRACKS_DATA: [(u16, TrackInfo<Balance, BlockNumber>); 8] = [
+	(
+		0,
+		TrackInfo {
+			name: "root",
+			max_deciding: 1,
+			decision_deposit: 10_000 * UNITS,
+			prepare_period: 2 * HOURS,
+			decision_period: 14 * DAYS,
+			confirm_period: 24 * HOURS,
+			min_enactment_period: 24 * HOURS,
+			min_approval: APP_ROOT,
+			min_support: SUP_ROOT,
+		},
+	),
+	(
+		1,
+		TrackInfo {
+			name: "whitelisted_caller",
+			max_deciding: 10,
+			decision_deposit: 1_000 * UNITS,
+			prepare_period: 30 * MINUTES,
+			decision_period: 14 * DAYS,
+			confirm_period: 10 * MINUTES,
+			min_enactment_period: 10 * MINUTES,
+			min_approval: APP_WHITELISTED_CALLER,
+			min_support: SUP_WHITELISTED_CALLER,
+		},
+	),
+	(
+		2,
+		TrackInfo {
+			name: "treasurer",
+			max_deciding: 10,
+			decision_deposit: 1_000 * UNITS,
+			prepare_period: 2 * HOURS,
+			decision_period: 14 * DAYS,
+			confirm_period: 24 * HOURS,
+			min_enactment_period: 24 * HOURS,
+			min_approval: APP_TREASURER,
+			min_support: SUP_TREASURER,
+		},
+	),
+	(
+		3,
+		TrackInfo {
+			name: "nft_fee_admin",
+			max_deciding: 10,
+			decision_deposit: 1_000 * UNITS,
+			prepare_period: 2 * HOURS,
+			decision_period: 14 * DAYS,
+			confirm_period: 3 * HOURS,
+			min_enactment_period: 10 * MINUTES,
+			min_approval: APP_NFT_FEE_ADMIN,
+			min_support: SUP_NFT_FEE_ADMIN,
+		},
+	),
+	(
+		4,
+		TrackInfo {
+			name: "bridge_admin",
+			max_deciding: 10,
+			decision_deposit: 1_000 * UNITS,
+			prepare_period: 2 * HOURS,
+			decision_period: 14 * DAYS,
+			confirm_period: 3 * HOURS,
+			min_enactment_period: 10 * MINUTES,
+			min_approval: APP_BRIDGE_ADMIN,
+			min_support: SUP_BRIDGE_ADMIN,
+		},
+	),
+	(
+		5,
+		TrackInfo {
+			name: "staking_admin",
+			max_deciding: 10,
+			decision_deposit: 1_000 * UNITS,
+			prepare_period: 2 * HOURS,
+			decision_period: 14 * DAYS,
+			confirm_period: 3 * HOURS,
+			min_enactment_period: 10 * MINUTES,
+			min_approval: APP_STAKING_ADMIN,
+			min_support: SUP_STAKING_ADMIN,
+		},
+	),
+	(
+		6,
+		TrackInfo {
+			name: "general_admin",
+			max_deciding: 10,
+			decision_deposit: 1_000 * UNITS,
+			prepare_period: 2 * HOURS,
+			decision_period: 14 * DAYS,
+			confirm_period: 24 * HOURS,
+			min_enactment_period: 24 * HOURS,
+			min_approval: APP_GENERAL_ADMIN,
+			min_support: SUP_GENERAL_ADMIN,
+		},
+	),
+	(
+		7,
+		TrackInfo {
+			name: "membership_admin",
+			max_deciding: 10,
+			decision_deposit: 1_000 * UNITS,
+			prepare_period: 2 * HOURS,
+			decision_period: 14 * DAYS,
+			confirm_period: 3 * HOURS,
+			min_enactment_period: 10 * MINUTES,
+			min_approval: APP_MEMBERSHIP_ADMIN,
+			min_support: SUP_MEMBERSHIP_ADMIN,
+		},
+	),
+];
+
+/// Tracks of the referenda, `O` being the `OriginCaller` of the runtime.
+pub struct TracksInfo<O>(PhantomData<O>);
+
+impl<O> pallet_referenda::TracksInfo<Balance, BlockNumber> for TracksInfo<O>
+where
+	O: Clone,
+	RawOrigin<AccountId>: TryFrom<O>,
+	Origin: TryFrom<O>,
+{
+	type Id = u16;
+	type RuntimeOrigin = O;
+
+	fn tracks() -> &'static [(Self::Id, TrackInfo<Balance, BlockNumber>)] {
+		&TRACKS_DATA[..]
+	}
+
+	fn track_for(id: &Self::RuntimeOrigin) -> Result<Self::Id, ()> {
+		if let Ok(system_origin) = RawOrigin::<AccountId>::try_from(id.clone()) {
+			match system_origin {
+				RawOrigin::Root => Ok(0),
+				_ => Err(()),
+			}
+		} else if let Ok(custom_origin) = Origin::try_from(id.clone()) {
+			match custom_origin {
+				Origin::WhitelistedCaller => Ok(1),
+				Origin::Treasurer => Ok(2),
+				Origin::NftFeeAdmin => Ok(3),
+				Origin::BridgeAdmin => Ok(4),
+				Origin::StakingAdmin => Ok(5),
+				Origin::GeneralAdmin => Ok(6),
+				Origin::MembershipAdmin => Ok(7),
+			}
+		} else {
+			Err(())
+		}
+	}
+}
@@ -25,28 +25,43 @@ use static_assertions::const_assert;
 use ternoa_core_primitives::BlockNumber;
 
 pub mod assets;
+pub mod auction;
 pub mod authorship;
 pub mod babe;
 pub mod bags_list;
 pub mod balances;
+pub mod bounties;
 pub mod bridge;
 pub mod constants;
 pub mod council;
-pub mod democracy;
 pub mod election_provider_multi_phase;
 pub mod election_provider_support;
+pub mod genesis_fees;
+pub mod governance;
 pub mod identity;
 pub mod imonline;
+pub mod indices;
+pub mod mandate;
+pub mod marketplace;
+pub mod migrations;
 pub mod multisig;
+pub mod nft;
+pub mod nomination_pools;
 pub mod phragmen_election;
 pub mod preimage;
+pub mod proxy;
+pub mod recovery;
+pub mod rent;
 pub mod shared;
 pub mod staking;
 pub mod staking_rewards;
 pub mod technical_collective;
 pub mod timestamp;
 pub mod transaction_payment;
+pub mod transaction_storage;
 pub mod treasury;
+pub mod tx_pause;
+pub mod vesting;
 pub mod voter_bags;
 
 #[macro_export]
@@ -0,0 +1,30 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::parameter_types;
+use ternoa_core_primitives::BlockNumber;
+
+use crate::{
+	constants::time::{DAYS, MINUTES},
+	prod_or_fast,
+};
+
+parameter_types! {
+	/// Time given to veto a mandate. The quickest root referendum takes 50 hours (2 hours of
+	/// preparation, 24 hours of confirmation and 24 hours before its enactment), the delay leaves
+	/// the voters several more days to reach the support of a veto.
+	pub const MandateDelay: BlockNumber = prod_or_fast!(7 * DAYS, 1 * MINUTES);
+}
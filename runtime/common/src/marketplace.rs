@@ -0,0 +1,142 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Shared implementation of the `MarketplaceApi` runtime API.
+
+use primitives::CompoundFee;
+use sp_runtime::{traits::AtLeast32BitUnsigned, Permill};
+use ternoa_marketplace::{BalanceOf, ListedNfts};
+use ternoa_nft::Nfts;
+use ternoa_runtime_apis::{
+	marketplace::{Listing, MarketplaceId, SaleFees},
+	nft::{NFTId, Page},
+};
+
+use crate::nft::scan;
+
+/// Splits `price` the same way `buy_nft` and `complete_auction` do: the marketplace commission
+/// is taken first, then the creator royalty is taken from what is left.
+pub fn sale_fees<Balance: AtLeast32BitUnsigned + Copy>(
+	price: Balance,
+	commission_fee: Option<CompoundFee<Balance>>,
+	royalty: Permill,
+) -> SaleFees<Balance> {
+	let commission = match commission_fee {
+		Some(CompoundFee::Flat(amount)) => amount.min(price),
+		Some(CompoundFee::Percentage(percent)) => percent * price,
+		None => Balance::zero(),
+	};
+	let after_commission = price.saturating_sub(commission);
+	let royalty = royalty * after_commission;
+
+	SaleFees {
+		price,
+		commission,
+		royalty,
+		seller_proceeds: after_commission.saturating_sub(royalty),
+	}
+}
+
+pub fn listing<T: ternoa_marketplace::Config + ternoa_nft::Config>(
+	nft_id: NFTId,
+) -> Option<Listing<T::AccountId, BalanceOf<T>>> {
+	let sale = ListedNfts::<T>::get(nft_id)?;
+	let royalty = Nfts::<T>::get(nft_id).map(|nft| nft.royalty).unwrap_or_default();
+
+	Some(Listing {
+		nft_id,
+		marketplace_id: sale.marketplace_id,
+		seller: sale.account_id,
+		fees: sale_fees(sale.price, sale.commission_fee, royalty),
+	})
+}
+
+pub fn listings<T: ternoa_marketplace::Config + ternoa_nft::Config>(
+	marketplace_id: MarketplaceId,
+	start_after: Option<NFTId>,
+	limit: u32,
+) -> Page<Listing<T::AccountId, BalanceOf<T>>> {
+	let entries = match start_after {
+		Some(nft_id) => ListedNfts::<T>::iter_from(ListedNfts::<T>::hashed_key_for(nft_id)),
+		None => ListedNfts::<T>::iter(),
+	};
+
+	scan(entries, limit, |nft_id, sale| {
+		(sale.marketplace_id == marketplace_id).then(|| listing::<T>(nft_id)).flatten()
+	})
+}
+
+pub fn buy_nft_fees<T: ternoa_marketplace::Config + ternoa_nft::Config>(
+	nft_id: NFTId,
+) -> Option<SaleFees<BalanceOf<T>>> {
+	listing::<T>(nft_id).map(|listing| listing.fees)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn fees(price: u128, commission: u128, royalty: u128, seller_proceeds: u128) -> SaleFees<u128> {
+		SaleFees { price, commission, royalty, seller_proceeds }
+	}
+
+	#[test]
+	fn royalty_is_taken_after_the_commission() {
+		let commission_fee = Some(CompoundFee::Percentage(Permill::from_percent(10)));
+		// The 20% royalty applies to the 900 left after the commission.
+		assert_eq!(
+			sale_fees(1_000u128, commission_fee, Permill::from_percent(20)),
+			fees(1_000, 100, 180, 720)
+		);
+	}
+
+	#[test]
+	fn flat_commission_is_capped_at_the_price() {
+		let royalty = Permill::from_percent(10);
+		assert_eq!(
+			sale_fees(1_000u128, Some(CompoundFee::Flat(300)), royalty),
+			fees(1_000, 300, 70, 630)
+		);
+		assert_eq!(
+			sale_fees(1_000u128, Some(CompoundFee::Flat(2_000)), royalty),
+			fees(1_000, 1_000, 0, 0)
+		);
+	}
+
+	#[test]
+	fn no_commission_leaves_the_royalty_on_the_whole_price() {
+		assert_eq!(sale_fees(1_000u128, None, Permill::from_percent(5)), fees(1_000, 0, 50, 950));
+		assert_eq!(sale_fees(1_000u128, None, Permill::zero()), fees(1_000, 0, 0, 1_000));
+	}
+
+	#[test]
+	fn shares_add_up_to_the_price() {
+		let commission_fees = [
+			None,
+			Some(CompoundFee::Flat(7)),
+			Some(CompoundFee::Percentage(Permill::from_parts(333_333))),
+			Some(CompoundFee::Percentage(Permill::one())),
+		];
+		for price in [0u128, 1, 999, 1_000_003, u128::MAX] {
+			for commission_fee in commission_fees.clone() {
+				for royalty in [Permill::zero(), Permill::from_parts(123_457), Permill::one()] {
+					let fees = sale_fees(price, commission_fee.clone(), royalty);
+					assert_eq!(fees.commission + fees.royalty + fees.seller_proceeds, price);
+				}
+			}
+		}
+	}
+}
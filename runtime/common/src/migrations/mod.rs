@@ -0,0 +1,154 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Ordered and versioned runtime migrations.
+//!
+//! A migration is gated on the storage version of a pallet: it only runs when the on-chain
+//! version is `FROM` and sets it to `TO` once done, so it never runs twice and a runtime can keep
+//! it in its list until every network it is deployed on went through it.
+//!
+//! - A [`Migration`] is done in the block of the upgrade, it is wrapped in a [`VersionedMigration`]
+//!   and the runtime passes a tuple of those to `frame_executive`.
+//! - A [`SteppedMigration`] is done over several blocks, it is wrapped in a
+//!   [`VersionedSteppedMigration`] and the runtime gives a tuple of those to
+//!   [`pallet_multi_block_migrations`], which runs them in order at the start of the blocks.
+//!
+//! Both kinds snapshot the state they need in `pre_upgrade` and check their invariants in
+//! `post_upgrade` when the runtime is built with `try-runtime`.
+//!
+//! Migrations fixing data of several pallets, or of pallets which can't get a new storage
+//! version, are gated on the storage version of [`pallet_multi_block_migrations`], which counts
+//! the runtime level migrations.
+
+use frame_support::{
+	parameter_types,
+	traits::{OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_runtime::Perbill;
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+mod multi_block;
+mod nomination_pools;
+mod staking_payout;
+mod transaction_storage;
+mod voter_list;
+
+pub use multi_block::{
+	pallet_multi_block_migrations, SteppedMigration, SteppedMigrations, VersionedSteppedMigration,
+};
+pub use nomination_pools::SetNominationPoolsConfigs;
+pub use staking_payout::QueueUnclaimedPayouts;
+pub use transaction_storage::SetTransactionStorageConfigs;
+pub use voter_list::RegenerateVoterList;
+
+parameter_types! {
+	/// Weight given to the stepped migrations at the start of each block.
+	pub MigrationStepWeight: Weight = Perbill::from_percent(20) * crate::MAXIMUM_BLOCK_WEIGHT;
+}
+
+/// A migration done in the block of the runtime upgrade.
+pub trait Migration {
+	/// Name used in the logs.
+	const NAME: &'static str;
+
+	/// Migrates the storage and returns the weight used.
+	fn migrate() -> Weight;
+
+	/// Snapshots the state checked by [`Self::post_upgrade`].
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		Ok(Vec::new())
+	}
+
+	/// Checks the invariants of the migrated state against the snapshot.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		Ok(())
+	}
+}
+
+/// Runs `M` when the on-chain storage version of `P` is `FROM`, and then sets it to `TO`.
+pub struct VersionedMigration<P, M, const FROM: u16, const TO: u16>(PhantomData<(P, M)>);
+
+impl<P, M, const FROM: u16, const TO: u16> VersionedMigration<P, M, FROM, TO>
+where
+	P: PalletInfoAccess,
+{
+	fn is_pending() -> bool {
+		StorageVersion::get::<P>() == FROM
+	}
+}
+
+impl<P, M, const FROM: u16, const TO: u16> OnRuntimeUpgrade for VersionedMigration<P, M, FROM, TO>
+where
+	P: PalletInfoAccess,
+	M: Migration,
+{
+	fn on_runtime_upgrade() -> Weight {
+		if !Self::is_pending() {
+			log::info!(
+				target: "runtime::migrations",
+				"{} skipped, {} is not at version {}",
+				M::NAME,
+				P::name(),
+				FROM,
+			);
+			return RocksDbWeight::get().reads(1)
+		}
+
+		log::info!(target: "runtime::migrations", "{} started", M::NAME);
+		let weight = M::migrate();
+		StorageVersion::new(TO).put::<P>();
+		log::info!(
+			target: "runtime::migrations",
+			"{} done, {} is at version {}",
+			M::NAME,
+			P::name(),
+			TO,
+		);
+
+		weight.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		use parity_scale_codec::Encode;
+
+		// The snapshot is only taken when the migration is going to run.
+		let state = if Self::is_pending() { Some(M::pre_upgrade()?) } else { None };
+		Ok(state.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		use parity_scale_codec::Decode;
+
+		let state = Option::<Vec<u8>>::decode(&mut &state[..])
+			.map_err(|_| "The pre-upgrade state can't be decoded")?;
+		if let Some(state) = state {
+			frame_support::ensure!(
+				StorageVersion::get::<P>() >= TO,
+				"The storage version was not updated"
+			);
+			M::post_upgrade(state)?;
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,660 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Migrations spread over several blocks.
+
+use frame_support::{
+	traits::{PalletInfoAccess, StorageVersion},
+	weights::{constants::RocksDbWeight, Weight},
+};
+use parity_scale_codec::{Decode, Encode, FullCodec};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// A migration done over several blocks, a step at a time.
+///
+/// The storage touched by the migration stays usable between the steps, the pallets using it must
+/// cope with data in the old and in the new format until the migration is done.
+pub trait SteppedMigration {
+	/// Name used in the logs.
+	const NAME: &'static str;
+
+	/// Where the next step starts.
+	type Cursor: FullCodec;
+
+	/// Migrates from the cursor, `None` being the start, using at most about `limit`.
+	///
+	/// Returns the cursor of the next step, `None` once the migration is done, and the weight
+	/// used. A step must always make progress, even when `limit` is too low.
+	fn step(cursor: Option<Self::Cursor>, limit: Weight) -> (Option<Self::Cursor>, Weight);
+
+	/// Snapshots the state checked by [`Self::post_upgrade`].
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		Ok(Vec::new())
+	}
+
+	/// Checks the invariants of the migrated state against the snapshot.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		Ok(())
+	}
+}
+
+/// Ordered list of stepped migrations, implemented for [`VersionedSteppedMigration`] and for the
+/// tuples of `SteppedMigrations`, `n` being the position of a migration in the flattened list.
+pub trait SteppedMigrations {
+	/// Number of migrations.
+	fn len() -> u32;
+
+	/// Name of the `n`th migration.
+	fn name(n: u32) -> Option<&'static str>;
+
+	/// Whether the `n`th migration still has to run.
+	fn is_pending(n: u32) -> bool;
+
+	/// Does a step of the `n`th migration, the cursors being encoded.
+	fn step(n: u32, cursor: Option<Vec<u8>>, limit: Weight) -> (Option<Vec<u8>>, Weight);
+
+	/// Marks the `n`th migration as done.
+	fn complete(n: u32) -> Weight;
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade(n: u32) -> Result<Vec<u8>, &'static str>;
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(n: u32, state: Vec<u8>) -> Result<(), &'static str>;
+}
+
+/// Runs `M` when the on-chain storage version of `P` is `FROM`, and then sets it to `TO`.
+pub struct VersionedSteppedMigration<P, M, const FROM: u16, const TO: u16>(PhantomData<(P, M)>);
+
+impl<P, M, const FROM: u16, const TO: u16> SteppedMigrations
+	for VersionedSteppedMigration<P, M, FROM, TO>
+where
+	P: PalletInfoAccess,
+	M: SteppedMigration,
+{
+	fn len() -> u32 {
+		1
+	}
+
+	fn name(n: u32) -> Option<&'static str> {
+		if n == 0 {
+			Some(M::NAME)
+		} else {
+			None
+		}
+	}
+
+	fn is_pending(n: u32) -> bool {
+		n == 0 && StorageVersion::get::<P>() == FROM
+	}
+
+	fn step(n: u32, cursor: Option<Vec<u8>>, limit: Weight) -> (Option<Vec<u8>>, Weight) {
+		if n != 0 {
+			return (None, Weight::zero())
+		}
+
+		// A cursor which can't be decoded restarts the migration, which has to be idempotent.
+		let cursor = cursor.and_then(|cursor| M::Cursor::decode(&mut &cursor[..]).ok());
+		let (next, weight) = M::step(cursor, limit);
+		(next.map(|next| next.encode()), weight)
+	}
+
+	fn complete(n: u32) -> Weight {
+		if n != 0 {
+			return Weight::zero()
+		}
+
+		StorageVersion::new(TO).put::<P>();
+		log::info!(
+			target: "runtime::migrations",
+			"{} done, {} is at version {}",
+			M::NAME,
+			P::name(),
+			TO,
+		);
+		RocksDbWeight::get().writes(1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade(_n: u32) -> Result<Vec<u8>, &'static str> {
+		M::pre_upgrade()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_n: u32, state: Vec<u8>) -> Result<(), &'static str> {
+		frame_support::ensure!(
+			StorageVersion::get::<P>() >= TO,
+			"The storage version was not updated"
+		);
+		M::post_upgrade(state)
+	}
+}
+
+impl SteppedMigrations for () {
+	fn len() -> u32 {
+		0
+	}
+
+	fn name(_n: u32) -> Option<&'static str> {
+		None
+	}
+
+	fn is_pending(_n: u32) -> bool {
+		false
+	}
+
+	fn step(_n: u32, _cursor: Option<Vec<u8>>, _limit: Weight) -> (Option<Vec<u8>>, Weight) {
+		(None, Weight::zero())
+	}
+
+	fn complete(_n: u32) -> Weight {
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade(_n: u32) -> Result<Vec<u8>, &'static str> {
+		Ok(Vec::new())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_n: u32, _state: Vec<u8>) -> Result<(), &'static str> {
+		Ok(())
+	}
+}
+
+/// Forwards a call to the element of the tuple holding the `n`th migration.
+macro_rules! for_nth {
+	($n:ident, $default:expr, [$( $m:ident ),+], $method:ident($( $arg:expr ),*)) => {{
+		let mut offset = 0u32;
+		$(
+			let len = $m::len();
+			if $n < offset + len {
+				return $m::$method($n - offset $(, $arg )*)
+			}
+			offset += len;
+		)+
+		let _ = offset;
+		$default
+	}};
+}
+
+macro_rules! impl_stepped_migrations_for_tuples {
+	($( $m:ident ),+) => {
+		impl<$( $m: SteppedMigrations ),+> SteppedMigrations for ($( $m, )+) {
+			fn len() -> u32 {
+				0 $( + $m::len() )+
+			}
+
+			fn name(n: u32) -> Option<&'static str> {
+				for_nth!(n, None, [$( $m ),+], name())
+			}
+
+			fn is_pending(n: u32) -> bool {
+				for_nth!(n, false, [$( $m ),+], is_pending())
+			}
+
+			fn step(n: u32, cursor: Option<Vec<u8>>, limit: Weight) -> (Option<Vec<u8>>, Weight) {
+				for_nth!(n, (None, Weight::zero()), [$( $m ),+], step(cursor, limit))
+			}
+
+			fn complete(n: u32) -> Weight {
+				for_nth!(n, Weight::zero(), [$( $m ),+], complete())
+			}
+
+			#[cfg(feature = "try-runtime")]
+			fn pre_upgrade(n: u32) -> Result<Vec<u8>, &'static str> {
+				for_nth!(n, Ok(Vec::new()), [$( $m ),+], pre_upgrade())
+			}
+
+			#[cfg(feature = "try-runtime")]
+			fn post_upgrade(n: u32, state: Vec<u8>) -> Result<(), &'static str> {
+				for_nth!(n, Ok(()), [$( $m ),+], post_upgrade(state))
+			}
+		}
+	};
+}
+
+impl_stepped_migrations_for_tuples!(A);
+impl_stepped_migrations_for_tuples!(A, B);
+impl_stepped_migrations_for_tuples!(A, B, C);
+impl_stepped_migrations_for_tuples!(A, B, C, D);
+impl_stepped_migrations_for_tuples!(A, B, C, D, E);
+impl_stepped_migrations_for_tuples!(A, B, C, D, E, F);
+impl_stepped_migrations_for_tuples!(A, B, C, D, E, F, G);
+impl_stepped_migrations_for_tuples!(A, B, C, D, E, F, G, H);
+
+/// Runs the [`SteppedMigrations`] of the runtime, a step at the start of each block, after each
+/// runtime upgrade bringing pending migrations.
+///
+/// The migrations are only ever appended to [`Config::Migrations`], the ones done on every
+/// network can be removed once no upgrade is in progress.
+#[frame_support::pallet]
+pub mod pallet_multi_block_migrations {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Migrations run in order.
+		type Migrations: SteppedMigrations;
+
+		/// Weight given to the migrations in each block.
+		#[pallet::constant]
+		type StepWeight: Get<Weight>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// Position of the running migration and its encoded cursor.
+	#[pallet::storage]
+	#[pallet::getter(fn ongoing)]
+	pub type Ongoing<T: Config> = StorageValue<_, (u32, Option<Vec<u8>>), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Pending migrations were found after a runtime upgrade.
+		UpgradeStarted { migrations: u32 },
+		/// The migration at this position is done.
+		MigrationCompleted { index: u32 },
+		/// All the migrations are done.
+		UpgradeCompleted,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let weight = T::DbWeight::get().reads(1);
+			// An upgrade done while migrating goes on from the current cursor.
+			if Ongoing::<T>::exists() {
+				return weight
+			}
+
+			let pending = (0..T::Migrations::len()).filter(|&n| T::Migrations::is_pending(n));
+			let migrations = pending.count() as u32;
+			if migrations == 0 {
+				return weight.saturating_add(T::DbWeight::get().reads(T::Migrations::len().into()))
+			}
+
+			Ongoing::<T>::put((0, None::<Vec<u8>>));
+			Self::deposit_event(Event::UpgradeStarted { migrations });
+
+			weight.saturating_add(T::DbWeight::get().reads_writes(T::Migrations::len().into(), 1))
+		}
+
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			let mut weight = T::DbWeight::get().reads(1);
+			if let Some((index, cursor)) = Ongoing::<T>::get() {
+				weight.saturating_accrue(Self::step(index, cursor, T::StepWeight::get()));
+			}
+			weight
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			let mut states = Vec::new();
+			for n in 0..T::Migrations::len() {
+				if T::Migrations::is_pending(n) {
+					states.push((n, T::Migrations::pre_upgrade(n)?));
+				}
+			}
+			Ok(states.encode())
+		}
+
+		/// The migrations are run to the end here so that their invariants can be checked.
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(states: Vec<u8>) -> Result<(), &'static str> {
+			let states = Vec::<(u32, Vec<u8>)>::decode(&mut &states[..])
+				.map_err(|_| "The pre-upgrade states can't be decoded")?;
+
+			while let Some((index, cursor)) = Ongoing::<T>::get() {
+				Self::step(index, cursor, Weight::MAX);
+			}
+			for (n, state) in states {
+				T::Migrations::post_upgrade(n, state)?;
+			}
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Steps the migrations from the given position within `limit`.
+		fn step(mut index: u32, mut cursor: Option<Vec<u8>>, limit: Weight) -> Weight {
+			let mut weight = Weight::zero();
+
+			while index < T::Migrations::len() && weight.all_lt(limit) {
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				if !T::Migrations::is_pending(index) {
+					index += 1;
+					cursor = None;
+					continue
+				}
+
+				let (next, used) =
+					T::Migrations::step(index, cursor.take(), limit.saturating_sub(weight));
+				weight.saturating_accrue(used);
+				match next {
+					Some(next) => cursor = Some(next),
+					None => {
+						weight.saturating_accrue(T::Migrations::complete(index));
+						Self::deposit_event(Event::MigrationCompleted { index });
+						index += 1;
+					},
+				}
+			}
+
+			if index < T::Migrations::len() {
+				Ongoing::<T>::put((index, cursor));
+			} else {
+				Ongoing::<T>::kill();
+				Self::deposit_event(Event::UpgradeCompleted);
+			}
+
+			weight.saturating_add(T::DbWeight::get().writes(1))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{pallet_multi_block_migrations::Event, *};
+	use crate::migrations::{Migration, VersionedMigration};
+	use frame_support::{
+		parameter_types,
+		traits::{ConstU32, ConstU64, Everything, OnInitialize, OnRuntimeUpgrade},
+	};
+	use sp_core::H256;
+	use sp_runtime::{
+		testing::Header,
+		traits::{BlakeTwo256, IdentityLookup},
+	};
+	use std::cell::RefCell;
+
+	type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+	type Block = frame_system::mocking::MockBlock<Test>;
+
+	frame_support::construct_runtime!(
+		pub enum Test where
+			Block = Block,
+			NodeBlock = Block,
+			UncheckedExtrinsic = UncheckedExtrinsic,
+		{
+			System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+			MultiBlockMigrations: pallet_multi_block_migrations::{Pallet, Storage, Event<T>},
+		}
+	);
+
+	impl frame_system::Config for Test {
+		type BaseCallFilter = Everything;
+		type BlockWeights = ();
+		type BlockLength = ();
+		type DbWeight = ();
+		type RuntimeOrigin = RuntimeOrigin;
+		type Index = u64;
+		type BlockNumber = u64;
+		type RuntimeCall = RuntimeCall;
+		type Hash = H256;
+		type Hashing = BlakeTwo256;
+		type AccountId = u64;
+		type Lookup = IdentityLookup<Self::AccountId>;
+		type Header = Header;
+		type RuntimeEvent = RuntimeEvent;
+		type BlockHashCount = ConstU64<250>;
+		type Version = ();
+		type PalletInfo = PalletInfo;
+		type AccountData = ();
+		type OnNewAccount = ();
+		type OnKilledAccount = ();
+		type SystemWeightInfo = ();
+		type SS58Prefix = ();
+		type OnSetCode = ();
+		type MaxConsumers = ConstU32<16>;
+	}
+
+	/// Weight of an item of the mock migrations.
+	const ITEM_WEIGHT: Weight = Weight::from_ref_time(10);
+
+	parameter_types! {
+		/// Two items per block.
+		pub StepWeight: Weight = Weight::from_ref_time(20).set_proof_size(u64::MAX);
+	}
+
+	impl pallet_multi_block_migrations::Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type Migrations = (
+			VersionedSteppedMigration<MultiBlockMigrations, First, 1, 2>,
+			VersionedSteppedMigration<MultiBlockMigrations, Second, 2, 3>,
+		);
+		type StepWeight = StepWeight;
+	}
+
+	/// Migrations done in the block of the upgrade, before the stepped ones.
+	type OneShots = (VersionedMigration<MultiBlockMigrations, OneShot, 0, 1>,);
+
+	thread_local! {
+		/// Items migrated so far, in order.
+		static MIGRATED: RefCell<Vec<(&'static str, u32)>> = RefCell::new(Vec::new());
+	}
+
+	fn migrated() -> Vec<(&'static str, u32)> {
+		MIGRATED.with(|migrated| migrated.borrow().clone())
+	}
+
+	/// Migrates the items of `name` from the cursor, as many as fit in `limit` and at least one.
+	fn migrate_items(
+		name: &'static str,
+		items: u32,
+		cursor: Option<u32>,
+		limit: Weight,
+	) -> (Option<u32>, Weight) {
+		let start = cursor.unwrap_or(0);
+		let mut next = start;
+		let mut weight = Weight::zero();
+		while next < items && (next == start || weight.saturating_add(ITEM_WEIGHT).all_lte(limit)) {
+			MIGRATED.with(|migrated| migrated.borrow_mut().push((name, next)));
+			weight.saturating_accrue(ITEM_WEIGHT);
+			next += 1;
+		}
+		((next < items).then_some(next), weight)
+	}
+
+	struct OneShot;
+
+	impl Migration for OneShot {
+		const NAME: &'static str = "OneShot";
+
+		fn migrate() -> Weight {
+			MIGRATED.with(|migrated| migrated.borrow_mut().push((Self::NAME, 0)));
+			ITEM_WEIGHT
+		}
+	}
+
+	/// Five items, done over three blocks.
+	struct First;
+
+	impl SteppedMigration for First {
+		const NAME: &'static str = "First";
+		type Cursor = u32;
+
+		fn step(cursor: Option<u32>, limit: Weight) -> (Option<u32>, Weight) {
+			migrate_items(Self::NAME, 5, cursor, limit)
+		}
+	}
+
+	/// Three items, done over two blocks.
+	struct Second;
+
+	impl SteppedMigration for Second {
+		const NAME: &'static str = "Second";
+		type Cursor = u32;
+
+		fn step(cursor: Option<u32>, limit: Weight) -> (Option<u32>, Weight) {
+			migrate_items(Self::NAME, 3, cursor, limit)
+		}
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| {
+			MIGRATED.with(|migrated| migrated.borrow_mut().clear());
+			// Events are not stored in the genesis block.
+			System::set_block_number(1);
+		});
+		ext
+	}
+
+	fn upgrade() {
+		OneShots::on_runtime_upgrade();
+		MultiBlockMigrations::on_runtime_upgrade();
+	}
+
+	fn run_to_block(n: u64) {
+		while System::block_number() < n {
+			let next = System::block_number() + 1;
+			System::set_block_number(next);
+			MultiBlockMigrations::on_initialize(next);
+		}
+	}
+
+	fn version() -> StorageVersion {
+		StorageVersion::get::<MultiBlockMigrations>()
+	}
+
+	fn events() -> Vec<Event<Test>> {
+		System::events()
+			.into_iter()
+			.filter_map(|record| match record.event {
+				RuntimeEvent::MultiBlockMigrations(event) => Some(event),
+				_ => None,
+			})
+			.collect()
+	}
+
+	fn items(name: &'static str, items: u32) -> Vec<(&'static str, u32)> {
+		(0..items).map(|item| (name, item)).collect()
+	}
+
+	#[test]
+	fn cursor_resumes_across_blocks() {
+		new_test_ext().execute_with(|| {
+			upgrade();
+			assert_eq!(version(), 1);
+			assert_eq!(MultiBlockMigrations::ongoing(), Some((0, None)));
+
+			run_to_block(2);
+			assert_eq!(MultiBlockMigrations::ongoing(), Some((0, Some(2u32.encode()))));
+			run_to_block(3);
+			assert_eq!(MultiBlockMigrations::ongoing(), Some((0, Some(4u32.encode()))));
+			assert_eq!(version(), 1);
+
+			// The last item fits, the version write ends the block.
+			run_to_block(4);
+			assert_eq!(MultiBlockMigrations::ongoing(), Some((1, None)));
+			assert_eq!(version(), 2);
+
+			let mut expected = vec![("OneShot", 0)];
+			expected.extend(items("First", 5));
+			assert_eq!(migrated(), expected);
+		});
+	}
+
+	#[test]
+	fn migrations_run_in_tuple_order() {
+		new_test_ext().execute_with(|| {
+			upgrade();
+			run_to_block(5);
+			assert_eq!(MultiBlockMigrations::ongoing(), Some((1, Some(2u32.encode()))));
+
+			run_to_block(6);
+			assert_eq!(MultiBlockMigrations::ongoing(), None);
+			assert_eq!(version(), 3);
+
+			let mut expected = vec![("OneShot", 0)];
+			expected.extend(items("First", 5));
+			expected.extend(items("Second", 3));
+			assert_eq!(migrated(), expected);
+			assert_eq!(
+				events(),
+				vec![
+					Event::UpgradeStarted { migrations: 2 },
+					Event::MigrationCompleted { index: 0 },
+					Event::MigrationCompleted { index: 1 },
+					Event::UpgradeCompleted,
+				]
+			);
+		});
+	}
+
+	#[test]
+	fn done_versions_are_skipped() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::new(2).put::<MultiBlockMigrations>();
+			upgrade();
+			assert_eq!(version(), 2);
+			assert_eq!(events(), vec![Event::UpgradeStarted { migrations: 1 }]);
+
+			run_to_block(2);
+			assert_eq!(MultiBlockMigrations::ongoing(), Some((1, Some(2u32.encode()))));
+			run_to_block(3);
+			assert_eq!(MultiBlockMigrations::ongoing(), None);
+			assert_eq!(version(), 3);
+			assert_eq!(migrated(), items("Second", 3));
+		});
+	}
+
+	#[test]
+	fn migrations_run_once() {
+		new_test_ext().execute_with(|| {
+			upgrade();
+			run_to_block(6);
+			assert_eq!(version(), 3);
+			let done = migrated();
+			let done_events = events();
+
+			upgrade();
+			run_to_block(8);
+			assert_eq!(MultiBlockMigrations::ongoing(), None);
+			assert_eq!(version(), 3);
+			assert_eq!(migrated(), done);
+			assert_eq!(events(), done_events);
+		});
+	}
+
+	#[test]
+	fn upgrade_while_migrating_keeps_the_cursor() {
+		new_test_ext().execute_with(|| {
+			upgrade();
+			run_to_block(2);
+			assert_eq!(MultiBlockMigrations::ongoing(), Some((0, Some(2u32.encode()))));
+
+			upgrade();
+			assert_eq!(MultiBlockMigrations::ongoing(), Some((0, Some(2u32.encode()))));
+			run_to_block(4);
+			assert_eq!(version(), 2);
+			assert_eq!(migrated().iter().filter(|(name, _)| *name == "First").count(), 5);
+		});
+	}
+}
@@ -0,0 +1,76 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Limits of the nomination pools on the chains that got the pallet through a runtime upgrade.
+
+use frame_support::{traits::Get, weights::Weight};
+use pallet_nomination_pools::{
+	BalanceOf, MaxPoolMembers, MaxPoolMembersPerPool, MaxPools, MinCreateBond, MinJoinBond,
+};
+use sp_runtime::traits::Zero;
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+use ternoa_core_primitives::Balance;
+
+use super::Migration;
+use crate::nomination_pools;
+
+/// Sets the minimum bonds and the limits of the pools to the values new chains get in their
+/// genesis. Without it, pools can be created and joined with any bond and in any number.
+///
+/// Limits already set, by the genesis of the chain or by `set_configs`, are left untouched.
+pub struct SetNominationPoolsConfigs<T>(PhantomData<T>);
+
+impl<T: pallet_nomination_pools::Config> SetNominationPoolsConfigs<T> {
+	fn is_unset() -> bool {
+		MinJoinBond::<T>::get().is_zero() &&
+			MinCreateBond::<T>::get().is_zero() &&
+			MaxPools::<T>::get().is_none() &&
+			MaxPoolMembers::<T>::get().is_none() &&
+			MaxPoolMembersPerPool::<T>::get().is_none()
+	}
+}
+
+impl<T> Migration for SetNominationPoolsConfigs<T>
+where
+	T: pallet_nomination_pools::Config,
+	BalanceOf<T>: From<Balance>,
+{
+	const NAME: &'static str = "SetNominationPoolsConfigs";
+
+	fn migrate() -> Weight {
+		let reads = T::DbWeight::get().reads(5);
+		if !Self::is_unset() {
+			log::info!(target: "runtime::migrations", "The nomination pools limits are already set");
+			return reads
+		}
+
+		MinJoinBond::<T>::put(BalanceOf::<T>::from(nomination_pools::MinJoinBond::get()));
+		MinCreateBond::<T>::put(BalanceOf::<T>::from(nomination_pools::MinCreateBond::get()));
+		MaxPools::<T>::put(nomination_pools::MaxPools::get());
+		MaxPoolMembers::<T>::put(nomination_pools::MaxMembers::get());
+		MaxPoolMembersPerPool::<T>::put(nomination_pools::MaxMembersPerPool::get());
+
+		reads.saturating_add(T::DbWeight::get().writes(5))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		frame_support::ensure!(!Self::is_unset(), "The nomination pools limits are not set");
+		Ok(())
+	}
+}
@@ -0,0 +1,72 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Automatic payouts of the eras that ended before the staking payout pallet was deployed.
+
+use frame_support::weights::Weight;
+use sp_staking::EraIndex;
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+use ternoa_staking_payout::Pallet as StakingPayout;
+
+use super::SteppedMigration;
+
+/// Queues the payouts not claimed yet of the ended eras still in the staking history, resuming
+/// after the last era queued.
+pub struct QueueUnclaimedPayouts<T>(PhantomData<T>);
+
+impl<T: ternoa_staking_payout::Config> SteppedMigration for QueueUnclaimedPayouts<T> {
+	const NAME: &'static str = "QueueUnclaimedPayouts";
+
+	type Cursor = EraIndex;
+
+	fn step(cursor: Option<EraIndex>, limit: Weight) -> (Option<EraIndex>, Weight) {
+		let eras = StakingPayout::<T>::history_eras();
+		let first = cursor.map_or(eras.start, |last| eras.start.max(last.saturating_add(1)));
+
+		let mut weight = T::DbWeight::get().reads(1);
+		let mut era_weight = Weight::zero();
+		for era in first..eras.end {
+			// At least one era is queued so that each step makes progress, the weight of the
+			// last era queued is the estimate of the next one.
+			if era > first && !weight.saturating_add(era_weight).all_lte(limit) {
+				return (Some(era - 1), weight)
+			}
+
+			era_weight = StakingPayout::<T>::queue_era(era);
+			weight.saturating_accrue(era_weight);
+		}
+
+		(None, weight)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		use ternoa_staking_payout::{PendingPayouts, StakingPayouts};
+
+		for era in StakingPayout::<T>::history_eras() {
+			for validator in T::Staking::era_validators(era) {
+				frame_support::ensure!(
+					PendingPayouts::<T>::contains_key((&validator, era)) ||
+						T::Staking::claimed_eras(&validator).contains(&era),
+					"A payout of the staking history is neither claimed nor queued"
+				);
+			}
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,88 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Fees and retention period of the transaction storage on the chains that got the pallet through
+//! a runtime upgrade.
+
+use frame_support::{storage_alias, traits::Get, weights::Weight};
+use pallet_transaction_storage::Pallet as TransactionStorage;
+use sp_runtime::traits::Zero;
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+use ternoa_core_primitives::{Balance, BlockNumber};
+
+use super::Migration;
+use crate::transaction_storage;
+
+// The pallet doesn't expose its configuration storage, it is only set in its genesis.
+#[storage_alias]
+type ByteFee<T: pallet_transaction_storage::Config> = StorageValue<TransactionStorage<T>, Balance>;
+#[storage_alias]
+type EntryFee<T: pallet_transaction_storage::Config> = StorageValue<TransactionStorage<T>, Balance>;
+#[storage_alias]
+type StoragePeriod<T: pallet_transaction_storage::Config> =
+	StorageValue<TransactionStorage<T>, <T as frame_system::Config>::BlockNumber, ValueQuery>;
+
+/// Sets the fees and the storage period to the values new chains get in their genesis. Without
+/// it, `store` and `renew` fail with `NotConfigured`.
+///
+/// Values already set, by the genesis of the chain, are left untouched.
+pub struct SetTransactionStorageConfigs<T>(PhantomData<T>);
+
+impl<T: pallet_transaction_storage::Config> SetTransactionStorageConfigs<T> {
+	fn is_unset() -> bool {
+		ByteFee::<T>::get().is_none() &&
+			EntryFee::<T>::get().is_none() &&
+			StoragePeriod::<T>::get().is_zero()
+	}
+}
+
+impl<T> Migration for SetTransactionStorageConfigs<T>
+where
+	T: pallet_transaction_storage::Config,
+	T::BlockNumber: From<BlockNumber>,
+{
+	const NAME: &'static str = "SetTransactionStorageConfigs";
+
+	fn migrate() -> Weight {
+		let reads = T::DbWeight::get().reads(3);
+		if !Self::is_unset() {
+			log::info!(
+				target: "runtime::migrations",
+				"The transaction storage configuration is already set"
+			);
+			return reads
+		}
+
+		ByteFee::<T>::put(transaction_storage::ByteFee::get());
+		EntryFee::<T>::put(transaction_storage::EntryFee::get());
+		StoragePeriod::<T>::put(T::BlockNumber::from(transaction_storage::StoragePeriod::get()));
+
+		reads.saturating_add(T::DbWeight::get().writes(3))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		frame_support::ensure!(
+			ByteFee::<T>::get().is_some() &&
+				EntryFee::<T>::get().is_some() &&
+				!StoragePeriod::<T>::get().is_zero(),
+			"The transaction storage configuration is not set"
+		);
+		Ok(())
+	}
+}
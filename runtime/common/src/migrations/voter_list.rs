@@ -0,0 +1,99 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Re-injection of the validators in the voter list of the staking pallet.
+
+use frame_election_provider_support::SortedListProvider;
+use frame_support::{traits::Get, weights::Weight};
+use pallet_staking::{Pallet as Staking, Validators};
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+use super::SteppedMigration;
+
+/// Inserts the validators missing from `pallet_staking::Config::VoterList`, resuming after the
+/// last validator processed.
+pub struct RegenerateVoterList<T>(PhantomData<T>);
+
+impl<T: pallet_staking::Config> RegenerateVoterList<T> {
+	/// Upper bound of the weight of a single insertion.
+	fn insert_weight() -> Weight {
+		T::DbWeight::get().reads_writes(6, 5)
+	}
+}
+
+impl<T: pallet_staking::Config> SteppedMigration for RegenerateVoterList<T> {
+	const NAME: &'static str = "RegenerateVoterList";
+
+	type Cursor = T::AccountId;
+
+	fn step(cursor: Option<T::AccountId>, limit: Weight) -> (Option<T::AccountId>, Weight) {
+		let mut validators = match cursor {
+			Some(last) => Validators::<T>::iter_keys_from(Validators::<T>::hashed_key_for(last)),
+			None => Validators::<T>::iter_keys(),
+		};
+		let weight_of = Staking::<T>::weight_of_fn();
+
+		let mut weight = Weight::zero();
+		let mut last = None;
+		// At least one validator is processed so that each step makes progress.
+		while last.is_none() || weight.saturating_add(Self::insert_weight()).all_lte(limit) {
+			let validator = match validators.next() {
+				Some(validator) => validator,
+				None => return (None, weight),
+			};
+
+			// Validators already in the list are left untouched.
+			if let Err(e) = T::VoterList::on_insert(validator.clone(), weight_of(&validator)) {
+				log::debug!(
+					target: "runtime::migrations",
+					"{:?} was not inserted in the voter list: {:?}",
+					validator,
+					e,
+				);
+			}
+			weight.saturating_accrue(Self::insert_weight());
+			last = Some(validator);
+		}
+
+		(last, weight)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		use parity_scale_codec::Encode;
+
+		Ok(T::VoterList::count().encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		use parity_scale_codec::Decode;
+
+		let prev_count =
+			u32::decode(&mut &state[..]).map_err(|_| "The voter count can't be decoded")?;
+		frame_support::ensure!(
+			T::VoterList::count() >= prev_count,
+			"Voters were removed from the voter list"
+		);
+		frame_support::ensure!(
+			Validators::<T>::iter_keys().all(|validator| T::VoterList::contains(&validator)),
+			"A validator is missing from the voter list"
+		);
+		T::VoterList::try_state()
+	}
+}
@@ -0,0 +1,126 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Shared implementation of the `NftApi` runtime API.
+
+use primitives::nfts::NFTData;
+use sp_std::vec::Vec;
+use ternoa_nft::{Collections, DelegatedNFTs, Nfts};
+use ternoa_runtime_apis::nft::{
+	CollectionId, CollectionInfo, NFTId, NftInfo, NftStatus, Page, MAX_PAGE_SIZE, MAX_SCANNED,
+};
+
+/// Reads up to [`MAX_SCANNED`] entries of a storage map, keeping the first `limit` items `f`
+/// makes out of them.
+pub(crate) fn scan<V, Item>(
+	entries: impl Iterator<Item = (NFTId, V)>,
+	limit: u32,
+	mut f: impl FnMut(NFTId, V) -> Option<Item>,
+) -> Page<Item> {
+	let limit = limit.clamp(1, MAX_PAGE_SIZE) as usize;
+	let mut items = Vec::new();
+	let mut scanned = 0;
+	let mut last = None;
+
+	for (nft_id, value) in entries {
+		if scanned == MAX_SCANNED || items.len() == limit {
+			return Page { items, next: last }
+		}
+		scanned += 1;
+		last = Some(nft_id);
+		if let Some(item) = f(nft_id, value) {
+			items.push(item);
+		}
+	}
+
+	Page { items, next: None }
+}
+
+/// Builds a [`NftInfo`] out of the id and the stored data of an NFT.
+fn nft_info<T: ternoa_nft::Config>(
+	nft_id: NFTId,
+	data: NFTData<T::AccountId, T::NFTOffchainDataLimit>,
+) -> NftInfo<T::AccountId> {
+	NftInfo {
+		nft_id,
+		owner: data.owner,
+		creator: data.creator,
+		offchain_data: data.offchain_data.into_inner(),
+		collection_id: data.collection_id,
+		royalty: data.royalty,
+		status: NftStatus {
+			is_capsule: data.state.is_capsule,
+			is_secret: data.state.is_secret,
+			is_listed: data.state.listed_for_sale,
+			is_delegated: data.state.is_delegated,
+			delegated_to: DelegatedNFTs::<T>::get(nft_id),
+			is_soulbound: data.state.is_soulbound,
+			is_rented: data.state.is_rented,
+		},
+	}
+}
+
+pub fn nft<T: ternoa_nft::Config>(nft_id: NFTId) -> Option<NftInfo<T::AccountId>> {
+	Nfts::<T>::get(nft_id).map(|data| nft_info::<T>(nft_id, data))
+}
+
+pub fn nfts_by_owner<T: ternoa_nft::Config>(
+	owner: T::AccountId,
+	start_after: Option<NFTId>,
+	limit: u32,
+) -> Page<NftInfo<T::AccountId>> {
+	let entries = match start_after {
+		Some(nft_id) => Nfts::<T>::iter_from(Nfts::<T>::hashed_key_for(nft_id)),
+		None => Nfts::<T>::iter(),
+	};
+
+	scan(entries, limit, |nft_id, data| (data.owner == owner).then(|| nft_info::<T>(nft_id, data)))
+}
+
+pub fn collection<T: ternoa_nft::Config>(
+	collection_id: CollectionId,
+) -> Option<CollectionInfo<T::AccountId>> {
+	Collections::<T>::get(collection_id).map(|data| CollectionInfo {
+		collection_id,
+		owner: data.owner,
+		offchain_data: data.offchain_data.into_inner(),
+		size: data.nfts.len() as u32,
+		limit: data.limit,
+		is_closed: data.is_closed,
+	})
+}
+
+pub fn collection_nfts<T: ternoa_nft::Config>(
+	collection_id: CollectionId,
+	offset: u32,
+	limit: u32,
+) -> Vec<NftInfo<T::AccountId>> {
+	let limit = limit.min(MAX_PAGE_SIZE) as usize;
+	let nfts = match Collections::<T>::get(collection_id) {
+		Some(collection) => collection.nfts,
+		None => return Vec::new(),
+	};
+
+	nfts.into_iter()
+		.skip(offset as usize)
+		.take(limit)
+		.filter_map(|nft_id| nft::<T>(nft_id))
+		.collect()
+}
+
+pub fn nft_status<T: ternoa_nft::Config>(nft_id: NFTId) -> Option<NftStatus<T::AccountId>> {
+	nft::<T>(nft_id).map(|nft| nft.status)
+}
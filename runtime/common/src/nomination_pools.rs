@@ -0,0 +1,54 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::{parameter_types, PalletId as FramePalletId};
+use sp_core::U256;
+use sp_runtime::traits::Convert;
+use ternoa_core_primitives::Balance;
+
+use crate::constants::currency::UNITS;
+
+parameter_types! {
+	pub const PalletId: FramePalletId = FramePalletId(*b"py/nopls");
+	// Pools can only be destroyed once their unbonding eras are over, plus this many eras.
+	pub const PostUnbondPoolsWindow: u32 = 4;
+	pub const MaxMetadataLen: u32 = 256;
+	pub const MaxUnbonding: u32 = 8;
+	// Allow pools to have up to 10x points per balance before being considered unhealthy.
+	pub const MaxPointsToBalance: u8 = 10;
+
+	// Set in the genesis of new chains and by `SetNominationPoolsConfigs` on the live ones, can be
+	// changed later on with `set_configs`.
+	pub const MinJoinBond: Balance = 1 * UNITS;
+	pub const MinCreateBond: Balance = 1_000 * UNITS;
+	pub const MaxPools: u32 = 512;
+	pub const MaxMembersPerPool: u32 = 1_024;
+	pub const MaxMembers: u32 = 512 * 1_024;
+}
+
+pub struct BalanceToU256;
+impl Convert<Balance, U256> for BalanceToU256 {
+	fn convert(balance: Balance) -> U256 {
+		U256::from(balance)
+	}
+}
+
+pub struct U256ToBalance;
+impl Convert<U256, Balance> for U256ToBalance {
+	fn convert(n: U256) -> Balance {
+		n.try_into().unwrap_or(Balance::MAX)
+	}
+}
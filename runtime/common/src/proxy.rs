@@ -0,0 +1,175 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::{parameter_types, RuntimeDebug};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use ternoa_core_primitives::Balance;
+
+use crate::constants::currency::deposit;
+
+parameter_types! {
+	// One storage item; key size 32, value size 8.
+	pub const ProxyDepositBase: Balance = deposit(1, 8);
+	// Additional storage item size of 33 bytes.
+	pub const ProxyDepositFactor: Balance = deposit(0, 33);
+	pub const MaxProxies: u32 = 32;
+	// One storage item; key size 32, value size 8.
+	pub const AnnouncementDepositBase: Balance = deposit(1, 8);
+	pub const AnnouncementDepositFactor: Balance = deposit(0, 66);
+	pub const MaxPending: u32 = 32;
+}
+
+/// The type used to represent the kinds of proxying allowed.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub enum ProxyType {
+	/// Can dispatch any call.
+	Any = 0,
+	/// Can dispatch anything that doesn't move funds, NFTs or assets.
+	NonTransfer = 1,
+	/// Can take part in governance and treasury decisions.
+	Governance = 2,
+	/// Can manage staking and nomination pools.
+	Staking = 3,
+	/// Can create and manage NFTs and collections, but not transfer them.
+	NftOperator = 4,
+	/// Can manage marketplaces and list NFTs, but not buy them.
+	MarketplaceOperator = 5,
+	/// Can bid on auctions and claim what is owed.
+	AuctionBidder = 6,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+/// Implements `InstanceFilter<RuntimeCall>` for [`ProxyType`].
+/// It expects the runtime to contain all the pallets both Ternoa runtimes share.
+#[macro_export]
+macro_rules! impl_proxy_type_filter {
+	() => {
+		impl frame_support::traits::InstanceFilter<RuntimeCall> for $crate::proxy::ProxyType {
+			fn filter(&self, c: &RuntimeCall) -> bool {
+				use $crate::proxy::ProxyType;
+
+				match self {
+					ProxyType::Any => true,
+					ProxyType::NonTransfer => match c {
+						RuntimeCall::System(..) |
+						RuntimeCall::Scheduler(..) |
+						RuntimeCall::Babe(..) |
+						RuntimeCall::Timestamp(..) |
+						RuntimeCall::Authorship(..) |
+						RuntimeCall::Session(..) |
+						RuntimeCall::Grandpa(..) |
+						RuntimeCall::ImOnline(..) |
+						RuntimeCall::Staking(..) |
+						RuntimeCall::ElectionProviderMultiPhase(..) |
+						RuntimeCall::BagsList(..) |
+						RuntimeCall::NominationPools(..) |
+						RuntimeCall::Council(..) |
+						RuntimeCall::PhragmenElection(..) |
+						RuntimeCall::Referenda(..) |
+						RuntimeCall::ConvictionVoting(..) |
+						RuntimeCall::Whitelist(..) |
+						RuntimeCall::TechnicalCommittee(..) |
+						RuntimeCall::TechnicalMembership(..) |
+						RuntimeCall::Treasury(..) |
+						RuntimeCall::Bounties(..) |
+						RuntimeCall::ChildBounties(..) |
+						RuntimeCall::Utility(..) |
+						RuntimeCall::Preimage(..) |
+						RuntimeCall::Identity(..) |
+						RuntimeCall::Multisig(..) |
+						RuntimeCall::Proxy(..) |
+						RuntimeCall::Vesting(pallet_vesting::Call::vest { .. }) |
+						RuntimeCall::Vesting(pallet_vesting::Call::vest_other { .. }) |
+						RuntimeCall::Indices(pallet_indices::Call::claim { .. }) |
+						RuntimeCall::Indices(pallet_indices::Call::free { .. }) |
+						RuntimeCall::Indices(pallet_indices::Call::freeze { .. }) => true,
+						_ => false,
+					},
+					ProxyType::Governance => match c {
+						RuntimeCall::Council(..) |
+						RuntimeCall::PhragmenElection(..) |
+						RuntimeCall::Referenda(..) |
+						RuntimeCall::ConvictionVoting(..) |
+						RuntimeCall::Whitelist(..) |
+						RuntimeCall::TechnicalCommittee(..) |
+						RuntimeCall::TechnicalMembership(..) |
+						RuntimeCall::Treasury(..) |
+						RuntimeCall::Bounties(..) |
+						RuntimeCall::ChildBounties(..) |
+						RuntimeCall::Utility(..) => true,
+						_ => false,
+					},
+					ProxyType::Staking => match c {
+						RuntimeCall::Staking(..) |
+						RuntimeCall::Session(..) |
+						RuntimeCall::BagsList(..) |
+						RuntimeCall::NominationPools(..) |
+						RuntimeCall::Utility(..) => true,
+						_ => false,
+					},
+					ProxyType::NftOperator => match c {
+						RuntimeCall::NFT(ternoa_nft::Call::transfer_nft { .. }) => false,
+						RuntimeCall::NFT(..) | RuntimeCall::Utility(..) => true,
+						_ => false,
+					},
+					ProxyType::MarketplaceOperator => match c {
+						RuntimeCall::Marketplace(ternoa_marketplace::Call::buy_nft { .. }) => false,
+						RuntimeCall::Marketplace(..) | RuntimeCall::Utility(..) => true,
+						_ => false,
+					},
+					ProxyType::AuctionBidder => match c {
+						RuntimeCall::Auction(ternoa_auction::Call::add_bid { .. }) |
+						RuntimeCall::Auction(ternoa_auction::Call::remove_bid { .. }) |
+						RuntimeCall::Auction(ternoa_auction::Call::buy_it_now { .. }) |
+						RuntimeCall::Auction(ternoa_auction::Call::claim { .. }) |
+						RuntimeCall::Utility(..) => true,
+						_ => false,
+					},
+				}
+			}
+
+			fn is_superset(&self, o: &Self) -> bool {
+				use $crate::proxy::ProxyType;
+
+				match (self, o) {
+					(x, y) if x == y => true,
+					(ProxyType::Any, _) => true,
+					(_, ProxyType::Any) => false,
+					(ProxyType::NonTransfer, ProxyType::Governance | ProxyType::Staking) => true,
+					_ => false,
+				}
+			}
+		}
+	};
+}
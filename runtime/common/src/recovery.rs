@@ -0,0 +1,30 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::parameter_types;
+use ternoa_core_primitives::Balance;
+
+use crate::constants::currency::UNITS;
+
+parameter_types! {
+	// Reserved by the account setting up its recovery configuration.
+	pub const ConfigDepositBase: Balance = 10 * UNITS;
+	pub const FriendDepositFactor: Balance = 1 * UNITS;
+	pub const MaxFriends: u32 = 9;
+	// Reserved by the rescuer for each recovery attempt, slashed if the attempt is closed by the
+	// lost account.
+	pub const RecoveryDeposit: Balance = 10 * UNITS;
+}
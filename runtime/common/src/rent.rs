@@ -0,0 +1,129 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Shared implementation of the `RentApi` runtime API.
+
+use sp_runtime::traits::Saturating;
+use sp_std::vec::Vec;
+use ternoa_rent::{BalanceOf, Contracts, Duration, Event, RentContractData};
+use ternoa_runtime_apis::{
+	nft::{NFTId, Page},
+	rent::{ContractEvent, ContractEventKind, RentContractInfo, RentDuration},
+};
+
+use crate::nft::scan;
+
+/// Builds a [`RentContractInfo`] out of the id and the stored data of a contract.
+fn contract_info<T: ternoa_rent::Config>(
+	nft_id: NFTId,
+	data: RentContractData<T::AccountId, T::BlockNumber, BalanceOf<T>, T::AccountSizeLimit>,
+) -> RentContractInfo<T::AccountId, T::BlockNumber> {
+	let (duration, end_block) = match data.duration {
+		Duration::Fixed(blocks) => (
+			RentDuration::Fixed(blocks),
+			data.start_block.map(|start| start.saturating_add(blocks)),
+		),
+		Duration::Subscription(period, max_duration) => (
+			RentDuration::Subscription { period, max_duration },
+			data.start_block.zip(max_duration).map(|(start, max)| start.saturating_add(max)),
+		),
+	};
+	RentContractInfo {
+		nft_id,
+		renter: data.renter,
+		rentee: data.rentee,
+		start_block: data.start_block,
+		duration,
+		renter_can_revoke: data.renter_can_revoke,
+		end_block,
+	}
+}
+
+pub fn contract<T: ternoa_rent::Config>(
+	nft_id: NFTId,
+) -> Option<RentContractInfo<T::AccountId, T::BlockNumber>> {
+	Contracts::<T>::get(nft_id).map(|data| contract_info::<T>(nft_id, data))
+}
+
+fn contracts_where<T: ternoa_rent::Config>(
+	start_after: Option<NFTId>,
+	limit: u32,
+	filter: impl Fn(&RentContractInfo<T::AccountId, T::BlockNumber>) -> bool,
+) -> Page<RentContractInfo<T::AccountId, T::BlockNumber>> {
+	let entries = match start_after {
+		Some(nft_id) => Contracts::<T>::iter_from(Contracts::<T>::hashed_key_for(nft_id)),
+		None => Contracts::<T>::iter(),
+	};
+
+	scan(entries, limit, |nft_id, data| {
+		let contract = contract_info::<T>(nft_id, data);
+		filter(&contract).then(|| contract)
+	})
+}
+
+pub fn contracts_by_renter<T: ternoa_rent::Config>(
+	renter: T::AccountId,
+	start_after: Option<NFTId>,
+	limit: u32,
+) -> Page<RentContractInfo<T::AccountId, T::BlockNumber>> {
+	contracts_where::<T>(start_after, limit, |contract| contract.renter == renter)
+}
+
+pub fn contracts_by_rentee<T: ternoa_rent::Config>(
+	rentee: T::AccountId,
+	start_after: Option<NFTId>,
+	limit: u32,
+) -> Page<RentContractInfo<T::AccountId, T::BlockNumber>> {
+	contracts_where::<T>(start_after, limit, |contract| contract.rentee.as_ref() == Some(&rentee))
+}
+
+pub fn upcoming_expirations<T: ternoa_rent::Config>(
+	until: T::BlockNumber,
+	start_after: Option<NFTId>,
+	limit: u32,
+) -> Page<RentContractInfo<T::AccountId, T::BlockNumber>> {
+	contracts_where::<T>(start_after, limit, |contract| {
+		contract.end_block.map_or(false, |end| end <= until)
+	})
+}
+
+/// Reads the events of the current block. Called at a given block hash, this returns the events
+/// of that block.
+pub fn contract_events<T: ternoa_rent::Config>() -> Vec<ContractEvent<T::AccountId>>
+where
+	<T as frame_system::Config>::RuntimeEvent: TryInto<Event<T>>,
+{
+	frame_system::Pallet::<T>::events()
+		.into_iter()
+		.filter_map(|record| record.event.try_into().ok())
+		.filter_map(|event| {
+			let (nft_id, kind) = match event {
+				Event::ContractCreated { nft_id, renter, .. } =>
+					(nft_id, ContractEventKind::Created { renter }),
+				Event::ContractStarted { nft_id, rentee, .. } =>
+					(nft_id, ContractEventKind::Accepted { rentee }),
+				Event::ContractRevoked { nft_id, revoked_by, .. } =>
+					(nft_id, ContractEventKind::Revoked { revoked_by }),
+				Event::ContractEnded { nft_id, revoked_by, .. } =>
+					(nft_id, ContractEventKind::Ended { revoked_by }),
+				Event::ContractExpired { nft_id, .. } => (nft_id, ContractEventKind::Expired),
+				Event::ContractCanceled { nft_id, .. } => (nft_id, ContractEventKind::Canceled),
+				_ => return None,
+			};
+			Some(ContractEvent { nft_id, kind })
+		})
+		.collect()
+}
@@ -14,11 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
 
-use frame_support::{
-	parameter_types,
-	traits::{ConstU32, Currency, Imbalance, OnUnbalanced},
-};
-use pallet_balances::NegativeImbalance;
+use frame_support::{parameter_types, traits::ConstU32};
 use sp_runtime::Perbill;
 
 use crate::election_provider_multi_phase::NposCompactSolution24;
@@ -37,6 +33,10 @@ parameter_types! {
 
 	pub const MaxUnlockingChunks: u32 = 32;
 	pub HistoryDepth: u32 = 84;
+	// Automatic payouts processed in a single block, within the block's idle weight.
+	pub const MaxPayoutsPerBlock: u32 = 16;
+	// Tries of an automatic payout before it is dropped and left to a manual payout.
+	pub const MaxPayoutAttempts: u32 = 3;
 }
 
 /// A reasonable benchmarking config for staking pallet.
@@ -47,23 +47,3 @@ impl pallet_staking::BenchmarkingConfig for StakingBenchmarkingConfig {
 }
 
 pub type EraPayout = ();
-
-pub struct DealWithFees<R>(sp_std::marker::PhantomData<R>);
-impl<R> OnUnbalanced<NegativeImbalance<R>> for DealWithFees<R>
-where
-	R: pallet_balances::Config + pallet_authorship::Config,
-{
-	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance<R>>) {
-		if let Some(mut amount) = fees_then_tips.next() {
-			// for fees, 100% to author
-			if let Some(tips) = fees_then_tips.next() {
-				// for tips, if any, 100% to author
-				tips.merge_into(&mut amount);
-			}
-
-			if let Some(author) = <pallet_authorship::Pallet<R>>::author() {
-				<pallet_balances::Pallet<R>>::resolve_creating(&author, amount);
-			}
-		}
-	}
-}
@@ -0,0 +1,36 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::parameter_types;
+use ternoa_core_primitives::{Balance, BlockNumber};
+
+use crate::constants::{
+	currency::{CENTS, MILLICENTS},
+	time::DAYS,
+};
+
+parameter_types! {
+	// Large enough for NFT media and capsule payloads while fitting in the normal dispatch part
+	// of a block.
+	pub const MaxTransactionSize: u32 = 2 * 1024 * 1024;
+	pub const MaxBlockTransactions: u32 = 512;
+
+	// Set in the genesis of new chains and by `SetTransactionStorageConfigs` on the live ones.
+	pub const ByteFee: Balance = 1 * MILLICENTS;
+	pub const EntryFee: Balance = 10 * CENTS;
+	// Stored data has to be renewed after this many blocks, authorities must keep it until then.
+	pub const StoragePeriod: BlockNumber = 7 * DAYS;
+}
@@ -0,0 +1,60 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::{
+	parameter_types,
+	traits::{Contains, Get},
+	BoundedVec,
+};
+
+parameter_types! {
+	pub const MaxNameLen: u32 = 256;
+}
+
+/// Pallets which can't be paused, either because the chain would stop producing or finalizing
+/// blocks without them or because governance needs them to undo a pause.
+pub const UNPAUSABLE_PALLETS: &[&[u8]] = &[
+	// Consensus
+	b"System",
+	b"Timestamp",
+	b"Babe",
+	b"Grandpa",
+	b"Authorship",
+	b"Session",
+	b"ImOnline",
+	// Governance
+	b"Scheduler",
+	b"Preimage",
+	b"TechnicalCommittee",
+	b"TechnicalMembership",
+	b"Mandate",
+	b"MandateTimelock",
+	b"Council",
+	b"PhragmenElection",
+	b"Referenda",
+	b"ConvictionVoting",
+	b"Whitelist",
+	b"Origins",
+	b"TxPause",
+];
+
+/// Matches every call of the [`UNPAUSABLE_PALLETS`].
+pub struct UnpausableCalls;
+impl<S: Get<u32>> Contains<(BoundedVec<u8, S>, Option<BoundedVec<u8, S>>)> for UnpausableCalls {
+	fn contains((pallet_name, _): &(BoundedVec<u8, S>, Option<BoundedVec<u8, S>>)) -> bool {
+		UNPAUSABLE_PALLETS.contains(&pallet_name.as_slice())
+	}
+}
@@ -44,7 +44,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	/// Version of the runtime specification. A full-node will not attempt to use its native
 	/// runtime in substitute for the on-chain Wasm runtime unless all of `spec_name`,
 	/// `spec_version` and `authoring_version` are the same between Wasm and native.
-	spec_version: 13,
+	spec_version: 53,
 
 	/// Version of the implementation of the specification. Nodes are free to ignore this; it
 	/// serves only as an indication that the code is different; as long as the other two versions
@@ -66,7 +66,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	/// dispatchable/module changing its index.
 	///
 	/// It need *not* change when a new module is added or when a dispatchable is added.
-	transaction_version: 1,
+	transaction_version: 4,
 
 	/// Version of the state implementation used by this runtime.
 	/// Use of an incorrect version is consensus breaking.
@@ -3,7 +3,7 @@ pub mod pallet_assets;
 pub mod pallet_bags_list;
 pub mod pallet_balances;
 pub mod pallet_collective;
-pub mod pallet_democracy;
+pub mod pallet_custom_origins;
 pub mod pallet_election_provider_multi_phase;
 pub mod pallet_elections_phragmen;
 pub mod pallet_identity;
@@ -18,9 +18,13 @@ pub mod pallet_treasury;
 pub mod pallet_utility;
 pub mod ternoa_auction;
 pub mod ternoa_bridge;
+pub mod ternoa_fee_router;
+pub mod ternoa_mandate_timelock;
 pub mod ternoa_marketplace;
 pub mod ternoa_nft;
 pub mod ternoa_rent;
+pub mod ternoa_staking_payout;
 pub mod ternoa_staking_rewards;
+pub mod ternoa_tx_pause;
 // pub mod pallet_session;
 // pub mod pallet_staking;
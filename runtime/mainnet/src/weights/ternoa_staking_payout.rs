@@ -0,0 +1,48 @@
+//! Weights for `ternoa_staking_payout`
+//!
+//! Estimated until the benchmark CLI is run on the reference machine with:
+//! `ternoa benchmark pallet --chain=alphanet-dev --steps=50 --repeat=20
+//! --pallet=ternoa_staking_payout --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --heap-pages=4096 --output ./output`
+//!
+//! `process_payout` is the bookkeeping of a payout that fails and is queued again, the payout
+//! itself is weighed by `pallet_staking`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `ternoa_staking_payout`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> ternoa_staking_payout::WeightInfo for WeightInfo<T> {
+	// Storage: StakingPayout Mode (r:0 w:1)
+	fn set_payout_mode() -> Weight {
+		Weight::from_ref_time(44_664_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Staking ErasRewardPoints (r:1 w:0)
+	// Storage: Staking Bonded (r:1 w:0)
+	// Storage: Staking Ledger (r:1 w:0)
+	// Storage: StakingPayout PendingPayouts (r:1 w:1)
+	// Storage: StakingPayout CounterForPendingPayouts (r:1 w:1)
+	/// The range of component `v` is `[0, 1000]`.
+	fn queue_era(v: u32, ) -> Weight {
+		Weight::from_ref_time(16_432_000 as u64)
+			.saturating_add(Weight::from_ref_time(21_740_000 as u64).saturating_mul(v as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((4 as u64).saturating_mul(v as u64)))
+			.saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(v as u64)))
+	}
+	// Storage: StakingPayout PendingPayouts (r:2 w:2)
+	// Storage: StakingPayout CounterForPendingPayouts (r:1 w:1)
+	// Storage: Staking Bonded (r:1 w:0)
+	// Storage: Staking Ledger (r:1 w:0)
+	fn process_payout() -> Weight {
+		Weight::from_ref_time(38_915_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+}
@@ -0,0 +1,33 @@
+//! Weights for `ternoa_tx_pause`
+//!
+//! Estimated until the benchmark CLI is run on the reference machine with:
+//! `ternoa benchmark pallet --chain=alphanet-dev --steps=50 --repeat=20
+//! --pallet=ternoa_tx_pause --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --heap-pages=4096 --output ./output`
+//!
+//! The benchmarks pause names of `MaxNameLen` bytes, the longest ones. The reads done by the call
+//! filter in every extrinsic belong to the extrinsic base weight of `benchmark overhead`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `ternoa_tx_pause`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> ternoa_tx_pause::WeightInfo for WeightInfo<T> {
+	// Storage: TxPause PausedCalls (r:1 w:1)
+	fn pause() -> Weight {
+		Weight::from_ref_time(44_664_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: TxPause PausedCalls (r:1 w:1)
+	fn unpause() -> Weight {
+		Weight::from_ref_time(44_664_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+}